@@ -0,0 +1,146 @@
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    io::{ErrorKind, Result as IoResult},
+    path::{Path, PathBuf},
+};
+
+/// Directory syllabus files are archived in when `SYLLABUS_STORE` isn't set
+pub const DEFAULT_SYLLABUS_STORE: &str = "syllabi";
+
+/// A content-addressed store of archived syllabus files
+///
+/// Every file is saved under the SHA-256 hash of its contents, so the same syllabus listed by
+/// several sections or semesters is only stored once.
+#[derive(Debug, Clone)]
+pub struct SyllabusStore {
+    root: PathBuf,
+}
+
+impl SyllabusStore {
+    /// Creates a store rooted at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Creates a store rooted at the `SYLLABUS_STORE` environment variable
+    pub fn from_env() -> Self {
+        dotenvy::dotenv().ok();
+
+        let root =
+            std::env::var("SYLLABUS_STORE").unwrap_or_else(|_| DEFAULT_SYLLABUS_STORE.to_owned());
+        Self::new(root)
+    }
+
+    /// The directory the store is rooted at
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Computes the hash a file is stored under
+    ///
+    /// # Arguments
+    /// * `bytes` - The contents of the file
+    ///
+    /// # Returns
+    /// The lowercase hexadecimal SHA-256 hash of the contents
+    pub fn hash(bytes: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    /// Finds where a file is stored
+    ///
+    /// # Arguments
+    /// * `hash` - The hash returned by [`SyllabusStore::put`]
+    ///
+    /// # Returns
+    /// The path of the file, or `None` if `hash` isn't a SHA-256 hash
+    pub fn path(&self, hash: &str) -> Option<PathBuf> {
+        let is_hash = hash.len() == 64
+            && hash
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+        if !is_hash {
+            return None;
+        }
+
+        // Shard by the first byte so no directory grows too large
+        Some(self.root.join(&hash[..2]).join(hash))
+    }
+
+    /// Stores a file, doing nothing if it is already stored
+    ///
+    /// # Arguments
+    /// * `bytes` - The contents of the file
+    ///
+    /// # Returns
+    /// The hash the file is stored under
+    pub fn put(&self, bytes: &[u8]) -> IoResult<String> {
+        let hash = Self::hash(bytes);
+        let path = self.path(&hash).expect("SHA-256 hashes are valid");
+
+        if !path.exists() {
+            let dir = path
+                .parent()
+                .expect("Stored files are in a shard directory");
+            fs::create_dir_all(dir)?;
+
+            // Write to a temporary file first so readers never see a partial file
+            let temp = dir.join(format!("{hash}.{}.tmp", uuid::Uuid::new_v4()));
+            fs::write(&temp, bytes)?;
+            fs::rename(&temp, &path)?;
+        }
+
+        Ok(hash)
+    }
+
+    /// Reads a stored file
+    ///
+    /// # Arguments
+    /// * `hash` - The hash returned by [`SyllabusStore::put`]
+    ///
+    /// # Returns
+    /// The contents of the file, or `None` if no file is stored under `hash`
+    pub fn get(&self, hash: &str) -> IoResult<Option<Vec<u8>>> {
+        let Some(path) = self.path(hash) else {
+            return Ok(None);
+        };
+
+        match fs::read(path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syllabus_store() {
+        let root = std::env::temp_dir().join(format!("syllabi-{}", uuid::Uuid::new_v4()));
+        let store = SyllabusStore::new(&root);
+
+        let hash = store.put(b"15-122 syllabus").unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(store.put(b"15-122 syllabus").unwrap(), hash);
+        assert_eq!(
+            store.get(&hash).unwrap().as_deref(),
+            Some(&b"15-122 syllabus"[..])
+        );
+        assert!(
+            store
+                .path(&hash)
+                .unwrap()
+                .starts_with(root.join(&hash[..2]))
+        );
+
+        assert_eq!(store.get(&SyllabusStore::hash(b"missing")).unwrap(), None);
+        assert_eq!(store.path("../../etc/passwd"), None);
+        assert_eq!(store.get("../../etc/passwd").unwrap(), None);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
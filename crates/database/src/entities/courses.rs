@@ -21,12 +21,16 @@ pub struct Model {
     pub crosslisted: Json,
     #[sea_orm(column_type = "Text", nullable)]
     pub notes: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub department: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::components::Entity")]
     Components,
+    #[sea_orm(has_many = "super::course_offerings::Entity")]
+    CourseOfferings,
 }
 
 impl Related<super::components::Entity> for Entity {
@@ -35,4 +39,10 @@ impl Related<super::components::Entity> for Entity {
     }
 }
 
+impl Related<super::course_offerings::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CourseOfferings.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
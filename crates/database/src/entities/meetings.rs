@@ -9,8 +9,10 @@ pub struct Model {
     pub id: Uuid,
     pub component_id: Uuid,
     pub days_pattern: String,
+    pub days_mask: Option<i16>,
     pub time_begin: Option<Time>,
     pub time_end: Option<Time>,
+    pub bldg_room: String,
     pub campus: String,
 }
 
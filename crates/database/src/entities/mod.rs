@@ -2,11 +2,17 @@
 
 pub mod prelude;
 
+pub mod catalog_courses;
 pub mod component_reservations;
 pub mod components;
+pub mod course_changes;
+pub mod course_offerings;
 pub mod courses;
 pub mod evaluations;
 pub mod instructor_meetings;
 pub mod instructors;
 pub mod meetings;
 pub mod reservations;
+pub mod scrape_runs;
+pub mod syllabi;
+pub mod syllabus_files;
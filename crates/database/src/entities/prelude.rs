@@ -1,10 +1,16 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.13
 
+pub use super::catalog_courses::Entity as CatalogCourses;
 pub use super::component_reservations::Entity as ComponentReservations;
 pub use super::components::Entity as Components;
+pub use super::course_changes::Entity as CourseChanges;
+pub use super::course_offerings::Entity as CourseOfferings;
 pub use super::courses::Entity as Courses;
 pub use super::evaluations::Entity as Evaluations;
 pub use super::instructor_meetings::Entity as InstructorMeetings;
 pub use super::instructors::Entity as Instructors;
 pub use super::meetings::Entity as Meetings;
 pub use super::reservations::Entity as Reservations;
+pub use super::scrape_runs::Entity as ScrapeRuns;
+pub use super::syllabi::Entity as Syllabi;
+pub use super::syllabus_files::Entity as SyllabusFiles;
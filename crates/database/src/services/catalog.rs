@@ -0,0 +1,315 @@
+use crate::entities::{catalog_courses, course_offerings};
+use chrono::Utc;
+use models::{
+    availability::OfferingHistory,
+    course_data::{ComponentType, CourseObject},
+    semester_report::Offering,
+    syllabus_data::{Season, Semester, Year},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection,
+    DatabaseTransaction, DbErr, EntityTrait, IntoActiveModel, QueryFilter, QuerySelect,
+    sea_query::OnConflict,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    str::FromStr,
+};
+use uuid::Uuid;
+
+/// The semester of a stored course or offering, to order them chronologically
+///
+/// # Arguments
+/// * `season` - Season code of the row (e.g., "F")
+/// * `year` - Full year of the row
+///
+/// # Returns
+/// The semester, or `None` if the season code is unknown, which orders before every semester
+pub fn stored_semester(season: &str, year: i16) -> Option<Semester> {
+    Some(Semester {
+        season: Season::from_str(season).ok()?,
+        year: Year(year as u16),
+    })
+}
+
+pub struct CatalogService;
+
+impl CatalogService {
+    /// Builds the offering recorded for a course about to be saved
+    ///
+    /// The offering is titled by its first lecture, or its first component if it has no
+    /// lectures.
+    ///
+    /// # Arguments
+    /// * `course_id` - Id of the saved course
+    /// * `course_obj` - The course about to be saved
+    /// * `description` - Description the course is saved with, which is the stored one when
+    ///   the course's details couldn't be fetched
+    pub fn to_offering(
+        course_id: Uuid,
+        course_obj: &CourseObject,
+        description: Option<String>,
+    ) -> course_offerings::Model {
+        let components = &course_obj.course.components;
+        let title = components
+            .iter()
+            .find(|component| component.component_type == ComponentType::Lecture)
+            .or(components.first())
+            .map(|component| component.title.clone());
+
+        course_offerings::Model {
+            id: Uuid::new_v4(),
+            number: course_obj.course.number.to_string(),
+            season: course_obj.course.season.as_str().to_owned(),
+            year: *course_obj.course.year as i16,
+            course_id: Some(course_id),
+            title,
+            description,
+            units: course_obj.course.units.to_string(),
+            recorded_at: Utc::now().into(),
+        }
+    }
+
+    /// Records offerings of courses that were just saved, adding their course numbers to the
+    /// catalog and updating each catalog course to its most recent offering
+    ///
+    /// # Arguments
+    /// * `txn` - The transaction the courses were saved in
+    /// * `offerings` - Offerings built with [`CatalogService::to_offering`]; an offering that
+    ///   was already recorded is replaced
+    pub async fn record_offerings(
+        txn: &DatabaseTransaction,
+        offerings: Vec<course_offerings::Model>,
+    ) -> Result<(), DbErr> {
+        if offerings.is_empty() {
+            return Ok(());
+        }
+
+        let updated_at = Utc::now();
+        // Rows are locked in order of course number, so concurrent batches can't deadlock
+        let numbers: BTreeSet<String> = offerings
+            .iter()
+            .map(|offering| offering.number.clone())
+            .collect();
+
+        catalog_courses::Entity::insert_many(numbers.iter().map(|number| {
+            catalog_courses::ActiveModel {
+                number: Set(number.clone()),
+                title: Set(None),
+                description: Set(None),
+                updated_at: Set(updated_at.into()),
+            }
+        }))
+        .on_conflict(
+            OnConflict::column(catalog_courses::Column::Number)
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(txn)
+        .await?;
+
+        for offering in offerings {
+            course_offerings::Entity::insert(offering.into_active_model().reset_all())
+                .on_conflict(
+                    OnConflict::columns([
+                        course_offerings::Column::Number,
+                        course_offerings::Column::Season,
+                        course_offerings::Column::Year,
+                    ])
+                    .update_columns([
+                        course_offerings::Column::CourseId,
+                        course_offerings::Column::Title,
+                        course_offerings::Column::Description,
+                        course_offerings::Column::Units,
+                        course_offerings::Column::RecordedAt,
+                    ])
+                    .to_owned(),
+                )
+                .exec(txn)
+                .await?;
+        }
+
+        // An older semester may have been saved, so look at every offering of each course
+        let mut latest: BTreeMap<String, course_offerings::Model> = BTreeMap::new();
+        for offering in course_offerings::Entity::find()
+            .filter(course_offerings::Column::Number.is_in(numbers))
+            .all(txn)
+            .await?
+        {
+            let semester = stored_semester(&offering.season, offering.year);
+            match latest.get(&offering.number) {
+                Some(seen) if stored_semester(&seen.season, seen.year) >= semester => {}
+                _ => {
+                    latest.insert(offering.number.clone(), offering);
+                }
+            }
+        }
+
+        for (number, offering) in latest {
+            catalog_courses::ActiveModel {
+                number: Set(number),
+                title: Set(offering.title),
+                description: Set(offering.description),
+                updated_at: Set(updated_at.into()),
+            }
+            .update(txn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get a course from the catalog with every offering of it
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `number` - Course number without a dash
+    ///
+    /// # Returns
+    /// The catalog course and its offerings, oldest first, or `None` if it was never offered
+    pub async fn get_course(
+        db: &DatabaseConnection,
+        number: &str,
+    ) -> Result<Option<(catalog_courses::Model, Vec<course_offerings::Model>)>, DbErr> {
+        let Some(course) = catalog_courses::Entity::find_by_id(number.to_owned())
+            .one(db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut offerings = course_offerings::Entity::find()
+            .filter(course_offerings::Column::Number.eq(number))
+            .all(db)
+            .await?;
+        offerings.sort_by_key(|offering| stored_semester(&offering.season, offering.year));
+
+        Ok(Some((course, offerings)))
+    }
+
+    /// Get every semester with recorded offerings
+    pub async fn get_recorded_semesters(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<(Season, Year)>, DbErr> {
+        let rows: Vec<(String, i16)> = course_offerings::Entity::find()
+            .select_only()
+            .column(course_offerings::Column::Season)
+            .column(course_offerings::Column::Year)
+            .distinct()
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(season, year)| Some((Season::from_str(&season).ok()?, Year(year as u16))))
+            .collect())
+    }
+
+    /// Get the semesters each of the given courses was offered in, out of every recorded
+    /// semester
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `numbers` - Course numbers without a dash
+    ///
+    /// # Returns
+    /// The [`OfferingHistory`] of each course that has been offered, keyed by course number
+    pub async fn get_offering_histories(
+        db: &DatabaseConnection,
+        numbers: &[String],
+    ) -> Result<HashMap<String, OfferingHistory>, DbErr> {
+        let rows: Vec<(String, String, i16)> = course_offerings::Entity::find()
+            .select_only()
+            .column(course_offerings::Column::Number)
+            .column(course_offerings::Column::Season)
+            .column(course_offerings::Column::Year)
+            .filter(course_offerings::Column::Number.is_in(numbers))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let mut offered: HashMap<String, Vec<(Season, Year)>> = HashMap::new();
+        for (number, season, year) in rows {
+            if let Ok(season) = Season::from_str(&season) {
+                offered
+                    .entry(number)
+                    .or_default()
+                    .push((season, Year(year as u16)));
+            }
+        }
+
+        let recorded = Self::get_recorded_semesters(db).await?;
+        Ok(offered
+            .into_iter()
+            .map(|(number, offered)| {
+                let history = OfferingHistory::new(offered, recorded.iter().copied());
+                (number, history)
+            })
+            .collect())
+    }
+
+    /// Get the offerings needed to compare a semester with earlier semesters
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `semester` - The semester to report on
+    /// * `baselines` - The semesters to compare it with
+    ///
+    /// # Returns
+    /// Every offering of the given semesters, along with every offering of the semester's
+    /// courses, or an empty list if nothing was offered in the semester
+    pub async fn get_report_offerings(
+        db: &DatabaseConnection,
+        semester: Semester,
+        baselines: &[Semester],
+    ) -> Result<Vec<Offering>, DbErr> {
+        let in_semester = |semester: &Semester| {
+            Condition::all()
+                .add(course_offerings::Column::Season.eq(semester.season.as_str()))
+                .add(course_offerings::Column::Year.eq(*semester.year as i16))
+        };
+
+        let mut offerings = course_offerings::Entity::find()
+            .filter(baselines.iter().fold(
+                Condition::any().add(in_semester(&semester)),
+                |any, baseline| any.add(in_semester(baseline)),
+            ))
+            .all(db)
+            .await?;
+
+        let numbers: HashSet<String> = offerings
+            .iter()
+            .filter(|offering| {
+                offering.season == semester.season.as_str()
+                    && offering.year == *semester.year as i16
+            })
+            .map(|offering| offering.number.clone())
+            .collect();
+        if numbers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut seen: HashSet<Uuid> = offerings.iter().map(|offering| offering.id).collect();
+        offerings.extend(
+            course_offerings::Entity::find()
+                .filter(course_offerings::Column::Number.is_in(numbers))
+                .all(db)
+                .await?
+                .into_iter()
+                .filter(|offering| seen.insert(offering.id)),
+        );
+
+        Ok(offerings
+            .into_iter()
+            .filter_map(|offering| {
+                Some(Offering {
+                    semester: stored_semester(&offering.season, offering.year)?,
+                    number: offering.number,
+                    title: offering.title,
+                })
+            })
+            .collect())
+    }
+}
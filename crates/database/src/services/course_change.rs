@@ -0,0 +1,81 @@
+use crate::entities::course_changes;
+use chrono::{DateTime, Utc};
+use models::course_change::CourseChange;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QueryOrder,
+};
+use uuid::Uuid;
+
+pub struct CourseChangeService;
+
+impl CourseChangeService {
+    /// The number of changes inserted in a single statement, which keeps a first scrape of a
+    /// semester well under Postgres's limit on bind parameters
+    const BATCH_SIZE: usize = 1000;
+
+    /// Records the changes detected by a scrape run, in the transaction saving the changed
+    /// courses so the changes are only recorded if the courses are saved
+    pub async fn save_changes(
+        db: &impl ConnectionTrait,
+        scrape_run_id: Uuid,
+        changes: Vec<CourseChange>,
+    ) -> Result<usize, DbErr> {
+        if changes.is_empty() {
+            return Ok(0);
+        }
+
+        let count = changes.len();
+        let detected_at = Utc::now();
+
+        let models: Vec<course_changes::ActiveModel> = changes
+            .into_iter()
+            .map(|change| course_changes::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                scrape_run_id: Set(scrape_run_id),
+                number: Set(change.number.to_string()),
+                season: Set(change.season.as_str().to_owned()),
+                year: Set(*change.year as i16),
+                change_type: Set(change.kind.as_str().to_owned()),
+                section: Set(change.section),
+                old_value: Set(change.old_value),
+                new_value: Set(change.new_value),
+                detected_at: Set(detected_at.into()),
+            })
+            .collect();
+
+        for batch in models.chunks(Self::BATCH_SIZE) {
+            course_changes::Entity::insert_many(batch.to_vec())
+                .exec_without_returning(db)
+                .await?;
+        }
+        Ok(count)
+    }
+
+    /// Get recorded changes in detection order, optionally limited to a semester and to
+    /// changes detected after `since`
+    pub async fn get_changes(
+        db: &DatabaseConnection,
+        since: Option<DateTime<Utc>>,
+        season: Option<String>,
+        year: Option<i16>,
+    ) -> Result<Vec<course_changes::Model>, DbErr> {
+        let mut query = course_changes::Entity::find();
+
+        if let Some(since) = since {
+            query = query.filter(course_changes::Column::DetectedAt.gt(since));
+        }
+        if let Some(season) = season {
+            query = query.filter(course_changes::Column::Season.eq(season));
+        }
+        if let Some(year) = year {
+            query = query.filter(course_changes::Column::Year.eq(year));
+        }
+
+        query
+            .order_by_asc(course_changes::Column::DetectedAt)
+            .order_by_asc(course_changes::Column::Number)
+            .all(db)
+            .await
+    }
+}
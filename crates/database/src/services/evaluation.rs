@@ -0,0 +1,150 @@
+use crate::entities::{components, courses, evaluations, instructors};
+use models::syllabus_data::{Season, Year};
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, QueryFilter,
+    QuerySelect, RelationTrait, TransactionTrait, prelude::Decimal,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A single row of a Faculty Course Evaluation export
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationRecord {
+    /// Course number without a dash (e.g., "15122")
+    pub number: String,
+    /// Seasons the evaluation may belong to; FCE exports don't distinguish the two summer
+    /// sessions, so a summer evaluation lists both
+    pub seasons: Vec<Season>,
+    pub year: Year,
+    /// Lecture or section code (e.g., "Lec 1" or "A")
+    pub section: String,
+    /// Instructor name as written in the export (e.g., "CERVESATO, ILIANO")
+    pub instructor: String,
+    pub course_short_name: String,
+    pub course_level: String,
+    pub total_students: i16,
+    pub num_responses: i16,
+    pub hours_per_week: Option<Decimal>,
+    pub interest_in_student_learning: Option<Decimal>,
+    pub clearly_explain_requirements: Option<Decimal>,
+    pub clear_learning_objectives: Option<Decimal>,
+    pub instructor_provides_feedback: Option<Decimal>,
+    pub demonstrate_importance: Option<Decimal>,
+    pub explains_subject_matter: Option<Decimal>,
+    pub show_respect_for_students: Option<Decimal>,
+    pub overall_teaching_rate: Option<Decimal>,
+    pub overall_course_rate: Option<Decimal>,
+}
+
+/// The outcome of an evaluation import
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    /// Number of evaluations saved
+    pub imported: usize,
+    /// Records whose component isn't in the database, as "{number} {section} {year}"
+    pub unmatched: Vec<String>,
+}
+
+pub struct EvaluationService;
+
+impl EvaluationService {
+    /// Saves evaluations for the components they belong to, replacing any previous evaluation
+    /// of the same component and instructor
+    ///
+    /// Instructors that aren't in the database yet are created.
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `records` - The evaluations to import
+    ///
+    /// # Returns
+    /// An [`ImportSummary`] with the records that could not be matched to a component
+    pub async fn import_evaluations(
+        db: &DatabaseConnection,
+        records: Vec<EvaluationRecord>,
+    ) -> Result<ImportSummary, DbErr> {
+        let txn = db.begin().await?;
+        let mut summary = ImportSummary::default();
+
+        let mut instructor_ids: HashMap<String, Uuid> = instructors::Entity::find()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|instructor| (instructor.name.to_lowercase(), instructor.id))
+            .collect();
+
+        for record in records {
+            let component_id: Option<Uuid> = components::Entity::find()
+                .select_only()
+                .column(components::Column::Id)
+                .join(JoinType::InnerJoin, components::Relation::Courses.def())
+                .filter(courses::Column::Number.eq(&record.number))
+                .filter(courses::Column::Year.eq(*record.year as i16))
+                .filter(
+                    courses::Column::Season
+                        .is_in(record.seasons.iter().map(|s| s.as_str().to_owned())),
+                )
+                .filter(components::Column::Code.eq(&record.section))
+                .into_tuple()
+                .one(&txn)
+                .await?;
+
+            let Some(component_id) = component_id else {
+                summary.unmatched.push(format!(
+                    "{} {} {}",
+                    record.number, record.section, *record.year
+                ));
+                continue;
+            };
+
+            let name = record.instructor.trim().to_owned();
+            let instructor_id = match instructor_ids.get(&name.to_lowercase()) {
+                Some(id) => *id,
+                None => {
+                    let id = Uuid::new_v4();
+                    instructors::Entity::insert(instructors::ActiveModel {
+                        id: Set(id),
+                        name: Set(name.clone()),
+                    })
+                    .exec(&txn)
+                    .await?;
+                    instructor_ids.insert(name.to_lowercase(), id);
+                    id
+                }
+            };
+
+            evaluations::Entity::delete_many()
+                .filter(evaluations::Column::ComponentId.eq(component_id))
+                .filter(evaluations::Column::InstructorId.eq(instructor_id))
+                .exec(&txn)
+                .await?;
+
+            evaluations::Entity::insert(evaluations::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                component_id: Set(component_id),
+                instructor_id: Set(instructor_id),
+                course_short_name: Set(record.course_short_name),
+                course_level: Set(record.course_level),
+                total_students: Set(record.total_students),
+                num_responses: Set(record.num_responses),
+                hours_per_week: Set(record.hours_per_week),
+                interest_in_student_learning: Set(record.interest_in_student_learning),
+                clearly_explain_requirements: Set(record.clearly_explain_requirements),
+                clear_learning_objectives: Set(record.clear_learning_objectives),
+                instructor_provides_feedback: Set(record.instructor_provides_feedback),
+                demonstrate_importance: Set(record.demonstrate_importance),
+                explains_subject_matter: Set(record.explains_subject_matter),
+                show_respect_for_students: Set(record.show_respect_for_students),
+                overall_teaching_rate: Set(record.overall_teaching_rate),
+                overall_course_rate: Set(record.overall_course_rate),
+            })
+            .exec(&txn)
+            .await?;
+
+            summary.imported += 1;
+        }
+
+        txn.commit().await?;
+        Ok(summary)
+    }
+}
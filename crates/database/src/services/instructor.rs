@@ -0,0 +1,130 @@
+use crate::entities::{components, courses, evaluations, instructors};
+use models::{
+    course_data::CourseObject,
+    instructor::{InstructorDirectory, InstructorMatch},
+};
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, JoinType, QueryFilter,
+    QuerySelect, RelationTrait, sea_query::Expr,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
+
+/// Instructor ids keyed by course number and the instructor name scraped for the course
+pub type InstructorIds = HashMap<(String, String), Uuid>;
+
+pub struct InstructorService;
+
+impl InstructorService {
+    /// Finds the stored instructor each scraped name refers to, creating the instructors that
+    /// aren't stored yet
+    ///
+    /// Scraped names are surnames, matched to stored instructors with
+    /// [`InstructorDirectory::find`], using who taught each course according to its
+    /// evaluations. A name that could refer to several instructors is left out rather than
+    /// guessed.
+    ///
+    /// # Arguments
+    /// * `txn` - The transaction the courses are saved in
+    /// * `course_objs` - The scraped courses
+    pub async fn resolve(
+        txn: &DatabaseTransaction,
+        course_objs: &[CourseObject],
+    ) -> Result<InstructorIds, DbErr> {
+        let stored = instructors::Entity::find().all(txn).await?;
+        let mut directory = InstructorDirectory::new(
+            stored
+                .iter()
+                .map(|instructor| (instructor.id, instructor.name.as_str())),
+        );
+
+        let names: BTreeSet<(String, &str)> = course_objs
+            .iter()
+            .flat_map(|course_obj| {
+                let number = course_obj.course.number.to_string();
+                course_obj
+                    .course
+                    .components
+                    .iter()
+                    .flat_map(|component| &component.meetings)
+                    .flat_map(|meeting| &meeting.instructors)
+                    .map(move |name| (number.clone(), name.as_str()))
+            })
+            .collect();
+
+        let numbers: BTreeSet<&str> = names.iter().map(|(number, _)| number.as_str()).collect();
+        let taught: HashSet<(Uuid, String)> = evaluations::Entity::find()
+            .select_only()
+            .column(evaluations::Column::InstructorId)
+            .column(courses::Column::Number)
+            .distinct()
+            .join(JoinType::InnerJoin, evaluations::Relation::Components.def())
+            .join(JoinType::InnerJoin, components::Relation::Courses.def())
+            .filter(courses::Column::Number.is_in(numbers))
+            .into_tuple()
+            .all(txn)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut instructor_ids = InstructorIds::new();
+        let mut new_instructors = Vec::new();
+        let mut ambiguous = BTreeSet::new();
+        for (number, name) in names {
+            let taught_course = |id| taught.contains(&(id, number.clone()));
+            let id = match directory.find(name, taught_course) {
+                InstructorMatch::Found(id) => id,
+                InstructorMatch::Ambiguous => {
+                    ambiguous.insert(name);
+                    continue;
+                }
+                InstructorMatch::Missing => {
+                    let id = Uuid::new_v4();
+                    directory.insert(id, name);
+                    new_instructors.push(instructors::ActiveModel {
+                        id: Set(id),
+                        name: Set(name.to_owned()),
+                    });
+                    id
+                }
+            };
+            instructor_ids.insert((number, name.to_owned()), id);
+        }
+
+        if !ambiguous.is_empty() {
+            println!(
+                "Not linking {} instructor names shared by several instructors",
+                ambiguous.len()
+            );
+        }
+        if !new_instructors.is_empty() {
+            println!("Adding {} new instructors", new_instructors.len());
+            instructors::Entity::insert_many(new_instructors)
+                .exec_without_returning(txn)
+                .await?;
+        }
+
+        Ok(instructor_ids)
+    }
+
+    /// Removes instructors who neither teach a stored meeting nor have evaluations, such as
+    /// an instructor stored by surname before their evaluations were imported
+    ///
+    /// # Returns
+    /// The number of instructors removed
+    pub async fn remove_unused(txn: &DatabaseTransaction) -> Result<u64, DbErr> {
+        Ok(instructors::Entity::delete_many()
+            .filter(Expr::cust(
+                r#"NOT EXISTS (
+                    SELECT 1 FROM "instructor_meetings"
+                    WHERE "instructor_meetings"."instructor_id" = "instructors"."id"
+                ) AND NOT EXISTS (
+                    SELECT 1 FROM "evaluations"
+                    WHERE "evaluations"."instructor_id" = "instructors"."id"
+                )"#,
+            ))
+            .exec(txn)
+            .await?
+            .rows_affected)
+    }
+}
@@ -1,2 +1,8 @@
+pub mod catalog;
+pub mod course_change;
+pub mod evaluation;
+pub mod instructor;
 pub mod query_course;
 pub mod save_course;
+pub mod scrape_run;
+pub mod syllabus;
@@ -1,13 +1,212 @@
-use crate::entities::{components, courses, instructor_meetings, instructors, meetings};
+use crate::{
+    entities::{components, courses, instructor_meetings, instructors, meetings},
+    services::catalog::{CatalogService, stored_semester},
+};
+use chrono::NaiveTime;
+use models::{
+    availability::OfferingHistory,
+    course_data::{
+        ComponentType, CourseComponent, CourseEntry, CourseMetadata, CourseObject, Meeting,
+        TimeBlock, TimeRange,
+    },
+    days::{DaySet, Days},
+    facets::{FacetCounts, Facets, count_units_buckets},
+    plan::CourseHistory,
+    requisite::{Expr, Prerequisites},
+    search_query::SearchTerms,
+    similarity::CourseDocument,
+    syllabus_data::{Season, Semester, Year},
+    units::Units,
+};
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, sea_query::ExprTrait,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait,
+    JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Statement, TransactionTrait,
+    sea_query::{self, ExprTrait, SimpleExpr},
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 use uuid::Uuid;
 
+/// A component with its meetings and their instructors
+pub type ComponentWithMeetings = (
+    components::Model,
+    Vec<(meetings::Model, Vec<instructors::Model>)>,
+);
+
+/// Filters on the courses listed by `/courses`, `None` for filters that aren't active
+#[derive(Debug, Clone, Default)]
+pub struct CourseFilters {
+    pub seasons: Option<Vec<String>>,
+    pub years: Option<Vec<i16>>,
+    /// First two digits of course numbers
+    pub departments: Option<Vec<String>>,
+    /// A web-style search query, see [`QueryCourseService::search_condition`]
+    pub search: Option<String>,
+    /// Days no meeting may be on
+    pub free_days: Option<DaySet>,
+    /// Days the course must meet on
+    pub required_days: Option<DaySet>,
+    /// Earliest time meetings may begin
+    pub earliest_start: Option<NaiveTime>,
+    /// Latest time meetings may end
+    pub latest_end: Option<NaiveTime>,
+    /// Blocks of time every meeting must fit in
+    pub free_blocks: Option<Vec<TimeBlock>>,
+    /// Whether meetings with days or times to be announced pass the day and time filters,
+    /// which they can't be shown to fit
+    pub include_tba: bool,
+}
+
+impl CourseFilters {
+    /// Builds a condition matching the courses that pass every active filter
+    pub fn condition(&self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(seasons) = &self.seasons {
+            condition = condition.add(courses::Column::Season.is_in(seasons.clone()));
+        }
+        if let Some(years) = &self.years {
+            condition = condition.add(courses::Column::Year.is_in(years.clone()));
+        }
+        if let Some(departments) = &self.departments {
+            // Departments are identified by the first two digits of the course number
+            condition = condition.add(departments.iter().fold(
+                Condition::any(),
+                |departments, department| {
+                    departments.add(courses::Column::Number.starts_with(department))
+                },
+            ));
+        }
+        if let Some(search) = &self.search {
+            condition = condition.add(QueryCourseService::search_condition(search));
+        }
+        if self.free_days.is_some()
+            || self.required_days.is_some()
+            || self.earliest_start.is_some()
+            || self.latest_end.is_some()
+            || self.free_blocks.is_some()
+        {
+            condition = condition.add(self.schedule_condition());
+        }
+        condition
+    }
+
+    /// Builds a condition matching courses that can be taken within the day and time filters
+    ///
+    /// A section fits if all of its meetings pass the filters. Courses match if every kind of
+    /// component they have (e.g., lectures and recitations) has a section that fits, and the
+    /// fitting sections meet on every required day.
+    fn schedule_condition(&self) -> SimpleExpr {
+        let tba = if self.include_tba { "TRUE" } else { "FALSE" };
+        let mut values: Vec<sea_query::Value> = Vec::new();
+        let mut bind = |value: sea_query::Value| {
+            values.push(value);
+            format!("${}", values.len())
+        };
+
+        // Each check passes meetings it can't be applied to only if TBA meetings are included
+        let mut checks = Vec::new();
+        if let Some(days) = self.free_days {
+            checks.push(format!(
+                r#"CASE WHEN "meetings"."days_mask" IS NULL THEN {tba}
+                ELSE ("meetings"."days_mask" & {}) = 0 END"#,
+                bind(i16::from(days.bits()).into())
+            ));
+        }
+        if let Some(start) = self.earliest_start {
+            checks.push(format!(
+                r#"CASE WHEN "meetings"."time_begin" IS NULL THEN {tba}
+                ELSE "meetings"."time_begin" >= {} END"#,
+                bind(start.into())
+            ));
+        }
+        if let Some(end) = self.latest_end {
+            checks.push(format!(
+                r#"CASE WHEN "meetings"."time_end" IS NULL THEN {tba}
+                ELSE "meetings"."time_end" <= {} END"#,
+                bind(end.into())
+            ));
+        }
+        if let Some(blocks) = &self.free_blocks {
+            // The days a meeting is covered by a block on, which must be all of its days
+            let covered = blocks
+                .iter()
+                .map(|block| {
+                    format!(
+                        r#"CASE WHEN "meetings"."time_begin" >= {} AND "meetings"."time_end" <= {}
+                        THEN {} ELSE 0 END"#,
+                        bind(block.time.begin.into()),
+                        bind(block.time.end.into()),
+                        bind(i16::from(block.days.bits()).into())
+                    )
+                })
+                .chain(["0".to_owned()])
+                .collect::<Vec<_>>()
+                .join(" | ");
+            checks.push(format!(
+                r#"CASE WHEN "meetings"."days_mask" IS NULL OR "meetings"."time_begin" IS NULL
+                THEN {tba} ELSE ("meetings"."days_mask" & ~({covered})) = 0 END"#
+            ));
+        }
+        let required = self
+            .required_days
+            .map(|days| bind(i16::from(days.bits()).into()));
+
+        let meeting_fits = if checks.is_empty() {
+            "TRUE".to_owned()
+        } else {
+            checks.join(" AND ")
+        };
+        // Sections without any meetings have an unknown schedule, like TBA meetings
+        let section_fits = format!(
+            r#"({tba} OR EXISTS (
+                SELECT 1 FROM "meetings" WHERE "meetings"."component_id" = "components"."id"
+            ))
+            AND NOT EXISTS (
+                SELECT 1 FROM "meetings"
+                WHERE "meetings"."component_id" = "components"."id" AND NOT ({meeting_fits})
+            )"#
+        );
+
+        let mut sql = format!(
+            r#"EXISTS (SELECT 1 FROM "components" WHERE "components"."course_id" = "courses"."id")
+            AND NOT EXISTS (
+                SELECT 1 FROM "components"
+                WHERE "components"."course_id" = "courses"."id"
+                GROUP BY "components"."component_type"
+                HAVING NOT bool_or({section_fits})
+            )"#
+        );
+        if let Some(required) = required {
+            sql.push_str(&format!(
+                r#" AND coalesce((
+                    SELECT bit_or("meetings"."days_mask") FROM "components"
+                    JOIN "meetings" ON "meetings"."component_id" = "components"."id"
+                    WHERE "components"."course_id" = "courses"."id" AND {section_fits}
+                ), 0) & {required} = {required}"#
+            ));
+        }
+
+        sea_query::Expr::cust_with_values(sql, values)
+    }
+}
+
 pub struct QueryCourseService;
 
 impl QueryCourseService {
+    /// Smallest trigram word similarity of a suggestion to a search that matched nothing,
+    /// below the threshold searches are matched with so near misses are suggested
+    const MIN_SUGGESTION_SIMILARITY: f32 = 0.3;
+
+    /// Labels of the times of day meetings start at, in the order they're listed in
+    const TIMES_OF_DAY: [&str; 4] = ["morning", "afternoon", "evening", "TBA"];
+
+    /// SQL expression of the time of day a meeting starts at
+    const TIME_OF_DAY: &str = r#"CASE
+        WHEN "meetings"."time_begin" IS NULL THEN 'TBA'
+        WHEN "meetings"."time_begin" < '12:00' THEN 'morning'
+        WHEN "meetings"."time_begin" < '17:00' THEN 'afternoon'
+        ELSE 'evening'
+    END"#;
+
     /// Get a single course with all its components, meetings, and instructors
     pub async fn get_course_by_id(
         db: &DatabaseConnection,
@@ -106,7 +305,7 @@ impl QueryCourseService {
 
     /// Get multiple courses with their components (for list view)
     pub async fn get_courses_with_components(
-        db: &DatabaseConnection,
+        db: &impl ConnectionTrait,
         course_ids: Vec<Uuid>,
     ) -> Result<
         Vec<(
@@ -236,4 +435,581 @@ impl QueryCourseService {
 
         Ok(results)
     }
+
+    /// Builds a condition matching courses whose description or archived syllabus text
+    /// matches a web-style search query (e.g., `"machine learning" -vision`), or whose titles or
+    /// instructors match it
+    ///
+    /// Titles and instructor names are also matched by trigram word similarity, so misspelled
+    /// and partial words (e.g., "machne learn") still find courses. Those are compared with the
+    /// query's words without its operators, leaving out titles and names containing an
+    /// excluded word.
+    pub fn search_condition(search: &str) -> SimpleExpr {
+        let terms = SearchTerms::parse(search);
+        // $3 onwards are the patterns of the excluded words
+        let excludes = |column: &str| -> String {
+            (0..terms.excluded.len())
+                .map(|i| format!(" AND {column} NOT ILIKE ${}", i + 3))
+                .collect()
+        };
+
+        let sql = format!(
+            r#"to_tsvector('english', coalesce("courses"."description", '')) @@ websearch_to_tsquery('english', $1)
+            OR EXISTS (
+                SELECT 1 FROM "components"
+                WHERE "components"."course_id" = "courses"."id"
+                    AND (to_tsvector('english', "components"."title") @@ websearch_to_tsquery('english', $1)
+                        OR ($2 <% "components"."title"{title_excludes}))
+            )
+            OR EXISTS (
+                SELECT 1 FROM "components"
+                JOIN "meetings" ON "meetings"."component_id" = "components"."id"
+                JOIN "instructor_meetings" ON "instructor_meetings"."meeting_id" = "meetings"."id"
+                JOIN "instructors" ON "instructors"."id" = "instructor_meetings"."instructor_id"
+                WHERE "components"."course_id" = "courses"."id"
+                    AND $2 <% "instructors"."name"{name_excludes}
+            )
+            OR EXISTS (
+                SELECT 1 FROM "syllabi"
+                JOIN "syllabus_files" ON "syllabus_files"."hash" = "syllabi"."hash"
+                WHERE "syllabi"."number" = "courses"."number"
+                    AND "syllabi"."season" = "courses"."season"
+                    AND "syllabi"."year" = "courses"."year"
+                    AND to_tsvector('english', "syllabus_files"."text") @@ websearch_to_tsquery('english', $1)
+            )"#,
+            title_excludes = excludes(r#""components"."title""#),
+            name_excludes = excludes(r#""instructors"."name""#),
+        );
+
+        let mut values = vec![search.to_owned(), terms.included_text()];
+        values.extend(terms.excluded.iter().map(|word| {
+            let escaped = word
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{escaped}%")
+        }));
+        sea_query::Expr::cust_with_values(sql, values)
+    }
+
+    /// Builds an expression ranking courses by how well they match a search query, to order
+    /// the courses matched by [`QueryCourseService::search_condition`] with
+    ///
+    /// The rank adds the full-text rank of the description to the best trigram word
+    /// similarity of a title and of an instructor name, so the best matches come first.
+    pub fn search_rank(search: &str) -> SimpleExpr {
+        sea_query::Expr::cust_with_values(
+            r#"ts_rank(to_tsvector('english', coalesce("courses"."description", '')), websearch_to_tsquery('english', $1))
+            + coalesce((
+                SELECT max(word_similarity($2, "components"."title")) FROM "components"
+                WHERE "components"."course_id" = "courses"."id"
+            ), 0)
+            + coalesce((
+                SELECT max(word_similarity($2, "instructors"."name")) FROM "components"
+                JOIN "meetings" ON "meetings"."component_id" = "components"."id"
+                JOIN "instructor_meetings" ON "instructor_meetings"."meeting_id" = "meetings"."id"
+                JOIN "instructors" ON "instructors"."id" = "instructor_meetings"."instructor_id"
+                WHERE "components"."course_id" = "courses"."id"
+            ), 0)"#,
+            [
+                search.to_owned(),
+                SearchTerms::parse(search).included_text(),
+            ],
+        )
+    }
+
+    /// Get titles and instructor names resembling a search query, to suggest when it matched
+    /// nothing
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `search` - The search query that matched nothing
+    /// * `limit` - Most suggestions to return
+    ///
+    /// # Returns
+    /// Distinct titles and names, most similar first
+    pub async fn get_search_suggestions(
+        db: &DatabaseConnection,
+        search: &str,
+        limit: u64,
+    ) -> Result<Vec<String>, DbErr> {
+        let search = SearchTerms::parse(search).included_text();
+        if search.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Lower the threshold of `<%` for this transaction only, so the trigram indexes find
+        // the near misses
+        let txn = db.begin().await?;
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)",
+            [Self::MIN_SUGGESTION_SIMILARITY.to_string().into()],
+        ))
+        .await?;
+
+        let rows = txn
+            .query_all(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"SELECT suggestion FROM (
+                    SELECT "title" AS suggestion FROM "components" WHERE $1 <% "title"
+                    UNION
+                    SELECT "name" FROM "instructors" WHERE $1 <% "name"
+                ) candidates
+                ORDER BY word_similarity($1, suggestion) DESC, suggestion
+                LIMIT $2"#,
+                [search.into(), (limit as i64).into()],
+            ))
+            .await?;
+        txn.commit().await?;
+
+        rows.iter()
+            .map(|row| row.try_get::<String>("", "suggestion"))
+            .collect()
+    }
+
+    /// Get the facet counts of the courses matching the filters
+    ///
+    /// Each facet is counted with every active filter except its own. Courses are counted once
+    /// per value of a meeting facet, even if several of their meetings share the value. The
+    /// facets are counted concurrently.
+    pub async fn get_facets(
+        db: &DatabaseConnection,
+        filters: &CourseFilters,
+    ) -> Result<Facets, DbErr> {
+        let without = |clear: fn(&mut CourseFilters)| {
+            let mut filters = filters.clone();
+            clear(&mut filters);
+            filters.condition()
+        };
+
+        let (department, season, year, units, campus, days, mut time_of_day) = futures::try_join!(
+            Self::count_courses_by(
+                db,
+                r#"left("courses"."number", 2)"#,
+                false,
+                without(|f| f.departments = None),
+            ),
+            Self::count_courses_by(
+                db,
+                r#""courses"."season""#,
+                false,
+                without(|f| f.seasons = None),
+            ),
+            Self::count_courses_by(
+                db,
+                r#""courses"."year"::text"#,
+                false,
+                without(|f| f.years = None),
+            ),
+            Self::count_courses_by(db, r#""courses"."units""#, false, filters.condition()),
+            Self::count_courses_by(db, r#""meetings"."campus""#, true, filters.condition()),
+            Self::count_courses_by(
+                db,
+                r#""meetings"."days_pattern""#,
+                true,
+                without(|f| (f.free_days, f.required_days) = (None, None)),
+            ),
+            Self::count_courses_by(
+                db,
+                Self::TIME_OF_DAY,
+                true,
+                without(|f| (f.earliest_start, f.latest_end) = (None, None)),
+            ),
+        )?;
+        let units = count_units_buckets(units);
+        time_of_day.sort_by_key(|(label, _)| {
+            Self::TIMES_OF_DAY
+                .iter()
+                .position(|time_of_day| time_of_day == label)
+        });
+
+        Ok(Facets {
+            department,
+            season,
+            year,
+            units,
+            campus,
+            days,
+            time_of_day,
+        })
+    }
+
+    /// Counts the courses matching a condition by a value of the course, or of its meetings
+    ///
+    /// # Arguments
+    /// * `value` - SQL expression of the value to count by
+    /// * `meetings` - Whether the value is of a meeting, so the meetings are joined
+    ///
+    /// # Returns
+    /// The number of courses with each non-empty value, ordered by value
+    async fn count_courses_by(
+        db: &DatabaseConnection,
+        value: &str,
+        meetings: bool,
+        condition: Condition,
+    ) -> Result<FacetCounts, DbErr> {
+        let mut query = courses::Entity::find()
+            .select_only()
+            .column_as(sea_query::Expr::cust(value), "value")
+            .column_as(
+                sea_query::Expr::cust(r#"count(DISTINCT "courses"."id")"#),
+                "count",
+            )
+            .filter(condition)
+            .group_by(sea_query::Expr::cust(value))
+            .order_by_asc(sea_query::Expr::cust(value));
+        if meetings {
+            query = query
+                .join(JoinType::InnerJoin, courses::Relation::Components.def())
+                .join(JoinType::InnerJoin, components::Relation::Meetings.def());
+        }
+
+        let rows: Vec<(String, i64)> = query.into_tuple().all(db).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|(value, _)| !value.is_empty())
+            .map(|(value, count)| (value, count as u64))
+            .collect())
+    }
+
+    /// Get every stored course of a semester as [`CourseObject`]s, e.g. to diff against a
+    /// fresh scrape
+    pub async fn get_semester_course_objects(
+        db: &impl ConnectionTrait,
+        season: Season,
+        year: Year,
+    ) -> Result<Vec<CourseObject>, DbErr> {
+        let course_ids: Vec<Uuid> = courses::Entity::find()
+            .select_only()
+            .column(courses::Column::Id)
+            .filter(courses::Column::Season.eq(season.as_str()))
+            .filter(courses::Column::Year.eq(*year as i16))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let courses = Self::get_courses_with_components(db, course_ids).await?;
+
+        courses
+            .into_iter()
+            .map(|(course, components)| Self::to_course_object(course, components))
+            .collect()
+    }
+
+    /// Get the course with a number offered in a semester, with its components, meetings, and
+    /// instructors
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `number` - Course number without a dash
+    /// * `season` - Season of the offering
+    /// * `year` - Year of the offering
+    pub async fn get_course_offering(
+        db: &DatabaseConnection,
+        number: &str,
+        season: Season,
+        year: Year,
+    ) -> Result<Option<(courses::Model, Vec<ComponentWithMeetings>)>, DbErr> {
+        let course_id: Option<Uuid> = courses::Entity::find()
+            .select_only()
+            .column(courses::Column::Id)
+            .filter(courses::Column::Number.eq(number))
+            .filter(courses::Column::Season.eq(season.as_str()))
+            .filter(courses::Column::Year.eq(*year as i16))
+            .into_tuple()
+            .one(db)
+            .await?;
+
+        match course_id {
+            Some(course_id) => Self::get_course_by_id(db, course_id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Get the description and component titles of every stored course, to build a
+    /// [`SimilarityIndex`](models::similarity::SimilarityIndex) from
+    ///
+    /// Titles are ordered lectures first, so the first is the course title.
+    pub async fn get_course_documents(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<CourseDocument>, DbErr> {
+        let courses: Vec<(Uuid, String, String, i16, Option<String>)> = courses::Entity::find()
+            .select_only()
+            .column(courses::Column::Id)
+            .column(courses::Column::Number)
+            .column(courses::Column::Season)
+            .column(courses::Column::Year)
+            .column(courses::Column::Description)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let mut components: Vec<(Uuid, String, String, String)> = components::Entity::find()
+            .select_only()
+            .column(components::Column::CourseId)
+            .column(components::Column::Title)
+            .column(components::Column::ComponentType)
+            .column(components::Column::Code)
+            .into_tuple()
+            .all(db)
+            .await?;
+        components.sort_by(|(_, _, a_type, a_code), (_, _, b_type, b_code)| {
+            (b_type == "Lecture")
+                .cmp(&(a_type == "Lecture"))
+                .then_with(|| a_code.cmp(b_code))
+        });
+
+        let mut titles: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for (course_id, title, _, _) in components {
+            let course_titles = titles.entry(course_id).or_default();
+            if !course_titles.contains(&title) {
+                course_titles.push(title);
+            }
+        }
+
+        Ok(courses
+            .into_iter()
+            .filter_map(|(id, number, season, year, description)| {
+                Some(CourseDocument {
+                    number,
+                    semester: stored_semester(&season, year)?,
+                    description,
+                    titles: titles.remove(&id).unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    /// Get the name of every stored instructor
+    pub async fn get_instructor_names(db: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+        instructors::Entity::find()
+            .select_only()
+            .column(instructors::Column::Name)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// Get the name of each department by the first two digits of its course numbers
+    ///
+    /// A department listed under several names gets the one most of its courses are listed
+    /// under.
+    pub async fn get_department_names(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<(String, String)>, DbErr> {
+        let code = r#"left("courses"."number", 2)"#;
+        courses::Entity::find()
+            .select_only()
+            .column_as(sea_query::Expr::cust(code), "code")
+            .column_as(
+                sea_query::Expr::cust(r#"mode() WITHIN GROUP (ORDER BY "courses"."department")"#),
+                "name",
+            )
+            .filter(courses::Column::Department.is_not_null())
+            .group_by(sea_query::Expr::cust(code))
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// Get every semester with stored courses
+    pub async fn get_semesters(db: &DatabaseConnection) -> Result<Vec<(Season, Year)>, DbErr> {
+        let rows: Vec<(String, i16)> = courses::Entity::find()
+            .select_only()
+            .column(courses::Column::Season)
+            .column(courses::Column::Year)
+            .distinct()
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(season, year)| Some((Season::from_str(&season).ok()?, Year(year as u16))))
+            .collect())
+    }
+
+    /// Get the prerequisites of every course, taken from its most recent offering
+    ///
+    /// # Returns
+    /// The prerequisites keyed by course number, `None` for courses without any
+    pub async fn get_latest_prerequisites(
+        db: &DatabaseConnection,
+    ) -> Result<HashMap<String, Option<Expr>>, DbErr> {
+        let rows: Vec<(String, String, i16, Option<String>)> = courses::Entity::find()
+            .select_only()
+            .column(courses::Column::Number)
+            .column(courses::Column::Season)
+            .column(courses::Column::Year)
+            .column(courses::Column::Prerequisites)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let mut latest: HashMap<String, (Semester, Option<String>)> = HashMap::new();
+        for (number, season, year, prerequisites) in rows {
+            let semester = stored_semester(&season, year)
+                .ok_or_else(|| DbErr::Type(format!("Failed to parse season '{season}'")))?;
+
+            match latest.get(&number) {
+                Some((seen, _)) if *seen >= semester => {}
+                _ => {
+                    latest.insert(number, (semester, prerequisites));
+                }
+            }
+        }
+
+        latest
+            .into_iter()
+            .map(|(number, (_, prerequisites))| {
+                let prerequisites = prerequisites
+                    .as_deref()
+                    .map(serde_json::from_str::<Expr>)
+                    .transpose()
+                    .map_err(|e| DbErr::Type(format!("Failed to deserialize Expr: {e}")))?;
+                Ok((number, prerequisites))
+            })
+            .collect()
+    }
+
+    /// Get the history of the given courses for validating a plan
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `numbers` - Course numbers without a dash
+    ///
+    /// # Returns
+    /// The [`CourseHistory`] of each course that has been offered, keyed by course number
+    pub async fn get_course_histories(
+        db: &DatabaseConnection,
+        numbers: &[String],
+    ) -> Result<HashMap<String, CourseHistory>, DbErr> {
+        let courses = courses::Entity::find()
+            .filter(courses::Column::Number.is_in(numbers))
+            .all(db)
+            .await?;
+
+        let mut latest: HashMap<String, (Semester, courses::Model)> = HashMap::new();
+        for course in courses {
+            let semester = stored_semester(&course.season, course.year).ok_or_else(|| {
+                DbErr::Type(format!("Failed to parse season '{}'", course.season))
+            })?;
+
+            match latest.get(&course.number) {
+                Some((seen, _)) if *seen >= semester => {}
+                _ => {
+                    latest.insert(course.number.clone(), (semester, course));
+                }
+            }
+        }
+
+        // Offerings are kept for semesters that are no longer stored
+        let mut offerings = CatalogService::get_offering_histories(db, numbers).await?;
+
+        latest
+            .into_iter()
+            .map(|(number, (_, course))| {
+                let course = Self::to_course_object(course, Vec::new())?;
+                let (prerequisites, corequisites) = course
+                    .metadata
+                    .map(|metadata| (metadata.prerequisites.into_inner(), metadata.corequisites))
+                    .unwrap_or_default();
+                let history = CourseHistory {
+                    prerequisites,
+                    corequisites,
+                    units: course.course.units,
+                    offerings: offerings
+                        .remove(&number)
+                        .unwrap_or_else(|| OfferingHistory::new(Vec::new(), Vec::new())),
+                };
+                Ok((number, history))
+            })
+            .collect()
+    }
+
+    /// Converts a stored course back into the [`CourseObject`] it was saved from.
+    ///
+    /// Reservations are not stored, so they are always empty.
+    pub fn to_course_object(
+        course: courses::Model,
+        components: Vec<ComponentWithMeetings>,
+    ) -> Result<CourseObject, DbErr> {
+        let parse_err = |field: &str, value: &str| {
+            DbErr::Type(format!(
+                "Failed to parse {field} '{value}' of course {}",
+                course.number
+            ))
+        };
+
+        let season =
+            Season::from_str(&course.season).map_err(|_| parse_err("season", &course.season))?;
+        let units =
+            Units::from_str(&course.units).map_err(|_| parse_err("units", &course.units))?;
+
+        let prerequisites = course
+            .prerequisites
+            .as_deref()
+            .map(serde_json::from_str::<Expr>)
+            .transpose()
+            .map_err(|e| DbErr::Type(format!("Failed to deserialize Expr: {e}")))?;
+
+        let json_strings = |value: &serde_json::Value| -> Vec<String> {
+            value
+                .as_array()
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let components = components
+            .into_iter()
+            .map(|(component, meetings)| CourseComponent {
+                component_type: ComponentType::from(component.component_type),
+                title: component.title,
+                code: component.code,
+                meetings: meetings
+                    .into_iter()
+                    .map(|(meeting, instructors)| Meeting {
+                        days: Days::from(meeting.days_pattern),
+                        time: meeting
+                            .time_begin
+                            .zip(meeting.time_end)
+                            .and_then(|(begin, end)| TimeRange::new(begin, end)),
+                        bldg_room: meeting.bldg_room,
+                        campus: meeting.campus,
+                        instructors: instructors
+                            .into_iter()
+                            .map(|instructor| instructor.name)
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(CourseObject {
+            course: CourseEntry {
+                number: course.number.into(),
+                units,
+                components,
+                season,
+                year: Year(course.year as u16),
+                department: course.department,
+            },
+            metadata: Some(CourseMetadata {
+                related_urls: json_strings(&course.related_urls),
+                special_permission: course.special_permission,
+                description: course.description,
+                prerequisites: Prerequisites::from(prerequisites),
+                corequisites: json_strings(&course.corequisites).into(),
+                crosslisted: json_strings(&course.crosslisted).into(),
+                notes: course.notes,
+                reservations: Vec::new(),
+            }),
+        })
+    }
 }
@@ -1,213 +1,722 @@
-use crate::entities::{components, courses, meetings};
-use futures::future::try_join_all;
+use crate::{
+    entities::{components, courses, instructor_meetings, meetings},
+    services::{
+        catalog::CatalogService,
+        course_change::CourseChangeService,
+        instructor::{InstructorIds, InstructorService},
+        query_course::QueryCourseService,
+    },
+};
 use models::{
-    course_data::{ComponentType, CourseObject},
-    syllabus_data::SyllabusMap,
+    course_change::diff_courses,
+    course_data::{ComponentType, CourseObject, Meeting},
+    days::Days,
+    syllabus_data::{Season, SyllabusMap, Year},
 };
 use sea_orm::{
-    ActiveValue::Set, DatabaseConnection, DbErr, EntityTrait, TransactionTrait,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection,
+    DatabaseTransaction, DbErr, EntityTrait, IntoActiveModel, JoinType, QueryFilter, QuerySelect,
+    RelationTrait, TransactionTrait, sea_query::Expr,
 };
-use std::sync::Arc;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use uuid::Uuid;
 
+/// How a save changed the stored courses of a semester
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SaveSummary {
+    /// Courses that weren't stored yet
+    pub added: usize,
+    /// Stored courses whose course, lecture, section, or meeting rows changed
+    pub updated: usize,
+    /// Stored courses the scrape no longer lists
+    pub removed: usize,
+    /// Changes recorded for the scrape run the courses were saved by
+    pub changes: usize,
+}
+
+/// The stored rows of a semester, grouped by the row they belong to
+struct StoredSemester {
+    /// Courses by course number
+    courses: HashMap<String, courses::Model>,
+    /// Components by course id
+    components: HashMap<Uuid, Vec<components::Model>>,
+    /// Meetings by component id
+    meetings: HashMap<Uuid, Vec<meetings::Model>>,
+    /// Instructor links by meeting id
+    instructor_meetings: HashMap<Uuid, Vec<instructor_meetings::Model>>,
+}
+
+impl StoredSemester {
+    async fn load(txn: &DatabaseTransaction, season: Season, year: Year) -> Result<Self, DbErr> {
+        let in_semester = Condition::all()
+            .add(courses::Column::Season.eq(season.as_str()))
+            .add(courses::Column::Year.eq(*year as i16));
+
+        let courses = courses::Entity::find()
+            .filter(in_semester.clone())
+            .all(txn)
+            .await?;
+        let components = components::Entity::find()
+            .join(JoinType::InnerJoin, components::Relation::Courses.def())
+            .filter(in_semester.clone())
+            .all(txn)
+            .await?;
+        let meetings = meetings::Entity::find()
+            .join(JoinType::InnerJoin, meetings::Relation::Components.def())
+            .join(JoinType::InnerJoin, components::Relation::Courses.def())
+            .filter(in_semester.clone())
+            .all(txn)
+            .await?;
+        let instructor_meetings = instructor_meetings::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                instructor_meetings::Relation::Meetings.def(),
+            )
+            .join(JoinType::InnerJoin, meetings::Relation::Components.def())
+            .join(JoinType::InnerJoin, components::Relation::Courses.def())
+            .filter(in_semester)
+            .all(txn)
+            .await?;
+
+        let mut stored = Self {
+            courses: courses
+                .into_iter()
+                .map(|course| (course.number.clone(), course))
+                .collect(),
+            components: HashMap::new(),
+            meetings: HashMap::new(),
+            instructor_meetings: HashMap::new(),
+        };
+        for component in components {
+            stored
+                .components
+                .entry(component.course_id)
+                .or_default()
+                .push(component);
+        }
+        for meeting in meetings {
+            stored
+                .meetings
+                .entry(meeting.component_id)
+                .or_default()
+                .push(meeting);
+        }
+        for link in instructor_meetings {
+            stored
+                .instructor_meetings
+                .entry(link.meeting_id)
+                .or_default()
+                .push(link);
+        }
+        Ok(stored)
+    }
+}
+
+/// Which stored courses of a semester a save replaces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveScope {
+    /// Every stored course, so the ones missing from the save are removed
+    Semester,
+    /// Only the stored courses saved again, so the semester's other courses are kept
+    Courses,
+}
+
+impl SaveScope {
+    /// Whether saving the courses with the given numbers replaces a stored course
+    fn replaces(self, number: &str, saved: &HashSet<String>) -> bool {
+        match self {
+            Self::Semester => true,
+            Self::Courses => saved.contains(number),
+        }
+    }
+}
+
+/// Rows to insert once the stored rows are updated, inserted in the order of their foreign keys
+#[derive(Default)]
+struct NewRows {
+    courses: Vec<courses::ActiveModel>,
+    components: Vec<components::ActiveModel>,
+    meetings: Vec<meetings::ActiveModel>,
+    instructor_meetings: Vec<instructor_meetings::ActiveModel>,
+}
+
+/// Ids of the stored rows to delete
+#[derive(Default)]
+struct StaleRows {
+    courses: Vec<Uuid>,
+    components: Vec<Uuid>,
+    meetings: Vec<Uuid>,
+    instructor_meetings: Vec<Uuid>,
+}
+
 pub struct SaveCourseService;
 
 impl SaveCourseService {
-    /// The number of courses to save in a single batch
+    /// The number of rows inserted or deleted in a single statement
     const BATCH_SIZE: usize = 200;
 
-    pub async fn save_courses(
+    /// Saves a freshly scraped semester, removing the stored courses it no longer lists
+    ///
+    /// Courses are updated in place, matched by course number, and their lectures and sections
+    /// by code, so their ids stay stable and the evaluations, reservations, and syllabus URLs
+    /// attached to them survive. The semester is saved in a single transaction, so a failed
+    /// save leaves it as it was.
+    ///
+    /// # Arguments
+    /// * `scrape_run_id` - The scrape run the courses were fetched by, to record how they
+    ///   changed in the same transaction; `None` to save them without recording changes
+    pub async fn save_semester(
         db: &DatabaseConnection,
+        season: Season,
+        year: Year,
         course_objs: Vec<CourseObject>,
         syllabus_map: SyllabusMap,
-    ) -> Result<Vec<Uuid>, DbErr> {
-        let total_courses = course_objs.len();
+        scrape_run_id: Option<Uuid>,
+    ) -> Result<SaveSummary, DbErr> {
+        let txn = db.begin().await?;
+        let summary = Self::save(
+            &txn,
+            season,
+            year,
+            course_objs,
+            &syllabus_map,
+            SaveScope::Semester,
+            scrape_run_id,
+        )
+        .await?;
+        txn.commit().await?;
+
         println!(
-            "Starting to save {} courses in batches of {}",
-            total_courses,
-            Self::BATCH_SIZE
+            "Saved {}{}: {} courses added, {} updated, {} removed",
+            season.as_str(),
+            year,
+            summary.added,
+            summary.updated,
+            summary.removed
         );
+        Ok(summary)
+    }
 
-        let syllabus_map = Arc::new(syllabus_map);
-
-        let batch_futures =
-            course_objs
-                .chunks(Self::BATCH_SIZE)
-                .enumerate()
-                .map(|(batch_idx, batch)| {
-                    let db = db.clone();
-                    let syllabus_map = Arc::clone(&syllabus_map);
-                    let batch_vec = batch.to_vec();
-
-                    async move {
-                        let batch_start = batch_idx * Self::BATCH_SIZE;
-                        let batch_end =
-                            std::cmp::min(batch_start + Self::BATCH_SIZE, total_courses);
-
-                        println!(
-                            "Processing batch {}/{}: courses {}-{}",
-                            batch_idx + 1,
-                            total_courses.div_ceil(Self::BATCH_SIZE),
-                            batch_start + 1,
-                            batch_end
-                        );
-
-                        let result = Self::save_course_batch(&db, batch_vec, &syllabus_map).await;
-
-                        match &result {
-                            Ok(ids) => println!(
-                                "Completed batch {}, {} courses processed",
-                                batch_idx + 1,
-                                ids.len()
-                            ),
-                            Err(e) => eprintln!("Error in batch {}: {}", batch_idx + 1, e),
-                        }
-
-                        result
-                    }
-                });
-
-        let all_batch_results: Vec<Vec<Uuid>> = try_join_all(batch_futures).await?;
-        let all_course_ids = all_batch_results.into_iter().flatten().collect();
+    /// Saves some courses of a semester in place, leaving its other stored courses as they are
+    ///
+    /// Used to save courses fetched again after failing, without the rest of their semester.
+    /// Changes are recorded as with [`Self::save_semester`], for the given courses only.
+    pub async fn update_courses(
+        db: &DatabaseConnection,
+        season: Season,
+        year: Year,
+        course_objs: Vec<CourseObject>,
+        syllabus_map: SyllabusMap,
+        scrape_run_id: Option<Uuid>,
+    ) -> Result<SaveSummary, DbErr> {
+        let txn = db.begin().await?;
+        let summary = Self::save(
+            &txn,
+            season,
+            year,
+            course_objs,
+            &syllabus_map,
+            SaveScope::Courses,
+            scrape_run_id,
+        )
+        .await?;
+        txn.commit().await?;
 
-        println!("Successfully saved all {total_courses} courses");
-        Ok(all_course_ids)
+        println!(
+            "Saved {}{}: {} courses added, {} updated",
+            season.as_str(),
+            year,
+            summary.added,
+            summary.updated
+        );
+        Ok(summary)
     }
 
-    async fn save_course_batch(
-        db: &DatabaseConnection,
+    /// Upserts courses of a semester, removing the stored courses in `scope` missing from them,
+    /// and records how they changed if `scrape_run_id` is given
+    async fn save(
+        txn: &DatabaseTransaction,
+        season: Season,
+        year: Year,
         course_objs: Vec<CourseObject>,
         syllabus_map: &SyllabusMap,
-    ) -> Result<Vec<Uuid>, DbErr> {
-        let txn = db.begin().await?;
+        scope: SaveScope,
+        scrape_run_id: Option<Uuid>,
+    ) -> Result<SaveSummary, DbErr> {
+        let mut summary = SaveSummary::default();
+        let saved_numbers: HashSet<String> = course_objs
+            .iter()
+            .map(|course_obj| course_obj.course.number.to_string())
+            .collect();
 
-        // Collect all data for bulk insertion
-        let mut all_courses = Vec::new();
-        let mut all_components = Vec::new();
-        let mut all_meetings = Vec::new();
-        let mut course_ids = Vec::new();
+        // Diffed before anything is saved, against the stored courses the save replaces
+        if let Some(scrape_run_id) = scrape_run_id {
+            let mut replaced =
+                QueryCourseService::get_semester_course_objects(txn, season, year).await?;
+            replaced
+                .retain(|stored| scope.replaces(&stored.course.number.to_string(), &saved_numbers));
+            let changes = diff_courses(&replaced, &course_objs);
+            summary.changes =
+                CourseChangeService::save_changes(txn, scrape_run_id, changes).await?;
+        }
 
-        // Prepare all data first
-        for (idx, course_obj) in course_objs.into_iter().enumerate() {
-            if idx.is_multiple_of(10) {
-                println!("  Saving course {idx} in current batch");
-            }
+        let instructor_ids = InstructorService::resolve(txn, &course_objs).await?;
+        let mut stored = StoredSemester::load(txn, season, year).await?;
+        stored
+            .courses
+            .retain(|number, _| scope.replaces(number, &saved_numbers));
 
-            let course_id = Uuid::new_v4();
-            course_ids.push(course_id);
+        let mut new_rows = NewRows::default();
+        let mut stale_rows = StaleRows::default();
+        let mut offerings = Vec::new();
 
-            // Prepare course
-            all_courses.push(Self::course_to_active_model(&course_obj));
+        for course_obj in course_objs {
+            let number = course_obj.course.number.to_string();
 
-            // Prepare components for this course
-            for component in course_obj.course.components {
-                let component_id = Uuid::new_v4();
+            let stored_course = stored.courses.remove(&number);
+            let is_new = stored_course.is_none();
+            let (course_id, description, mut changed) = match stored_course {
+                Some(course) => {
+                    let course_id = course.id;
+                    let mut course = course.into_active_model();
+                    Self::set_course_fields(&mut course, &course_obj);
+                    let description = course.description.clone().take().flatten();
 
+                    let changed = course.is_changed();
+                    if changed {
+                        course.update(txn).await?;
+                    }
+                    (course_id, description, changed)
+                }
+                None => {
+                    let course_id = Uuid::new_v4();
+                    let course = Self::new_course(course_id, &course_obj);
+                    let description = course.description.clone().take().flatten();
+
+                    new_rows.courses.push(course);
+                    summary.added += 1;
+                    (course_id, description, false)
+                }
+            };
+            offerings.push(CatalogService::to_offering(
+                course_id,
+                &course_obj,
+                description,
+            ));
+
+            let mut stored_components = stored.components.remove(&course_id).unwrap_or_default();
+            for component in course_obj.course.components {
                 let key = (
                     course_obj.course.year,
                     course_obj.course.season,
-                    course_obj.course.number.to_string(),
+                    number.clone(),
                     component.code.clone(),
                 );
                 let syllabus_url = syllabus_map.get(&key).cloned();
 
-                all_components.push(components::ActiveModel {
-                    id: Set(component_id),
-                    course_id: Set(course_id),
-                    title: Set(component.title),
-                    component_type: Set(match component.component_type {
-                        ComponentType::Lecture => "Lecture".to_string(),
-                        ComponentType::Section => "Section".to_string(),
-                    }),
-                    code: Set(component.code),
-                    syllabus_url: Set(syllabus_url),
-                });
+                let stored_component = stored_components
+                    .iter()
+                    .position(|stored| stored.code == component.code)
+                    .map(|i| stored_components.swap_remove(i));
+                let component_id = match stored_component {
+                    Some(stored_component) => {
+                        let component_id = stored_component.id;
+                        let mut row = stored_component.into_active_model();
+                        row.title.set_if_not_equals(component.title);
+                        row.component_type
+                            .set_if_not_equals(component_type_name(component.component_type));
+                        // Syllabi missing from this scrape's registries keep their stored URL
+                        if let Some(url) = syllabus_url {
+                            row.syllabus_url.set_if_not_equals(Some(url));
+                        }
 
-                // Prepare meetings for this component
-                for meeting in component.meetings {
-                    let meeting_id = Uuid::new_v4();
+                        if row.is_changed() {
+                            row.update(txn).await?;
+                            changed = true;
+                        }
+                        component_id
+                    }
+                    None => {
+                        let component_id = Uuid::new_v4();
+                        new_rows.components.push(components::ActiveModel {
+                            id: Set(component_id),
+                            course_id: Set(course_id),
+                            title: Set(component.title),
+                            component_type: Set(component_type_name(component.component_type)),
+                            code: Set(component.code),
+                            syllabus_url: Set(syllabus_url),
+                        });
+                        changed = true;
+                        component_id
+                    }
+                };
+
+                changed |= Self::save_meetings(
+                    txn,
+                    &number,
+                    component_id,
+                    component.meetings,
+                    &instructor_ids,
+                    &mut stored,
+                    &mut new_rows,
+                    &mut stale_rows,
+                )
+                .await?;
+            }
+
+            // Cancelled lectures and sections
+            if !stored_components.is_empty() {
+                stale_rows
+                    .components
+                    .extend(stored_components.iter().map(|component| component.id));
+                changed = true;
+            }
+
+            if changed && !is_new {
+                summary.updated += 1;
+            }
+        }
+
+        // The replaced courses left over are missing from the save
+        summary.removed = stored.courses.len();
+        stale_rows
+            .courses
+            .extend(stored.courses.values().map(|course| course.id));
+
+        // Deleting a course or component deletes the rows under it
+        Self::delete_in_batches::<instructor_meetings::Entity>(
+            txn,
+            instructor_meetings::Column::Id,
+            stale_rows.instructor_meetings,
+        )
+        .await?;
+        Self::delete_in_batches::<meetings::Entity>(txn, meetings::Column::Id, stale_rows.meetings)
+            .await?;
+        Self::delete_in_batches::<components::Entity>(
+            txn,
+            components::Column::Id,
+            stale_rows.components,
+        )
+        .await?;
+        Self::delete_in_batches::<courses::Entity>(txn, courses::Column::Id, stale_rows.courses)
+            .await?;
 
-                    all_meetings.push(meetings::ActiveModel {
+        Self::insert_in_batches(txn, new_rows.courses).await?;
+        Self::insert_in_batches(txn, new_rows.components).await?;
+        Self::insert_in_batches(txn, new_rows.meetings).await?;
+        Self::insert_in_batches(txn, new_rows.instructor_meetings).await?;
+        InstructorService::remove_unused(txn).await?;
+        CatalogService::record_offerings(txn, offerings).await?;
+
+        Ok(summary)
+    }
+
+    /// Updates the stored meetings of a component to the scraped ones
+    ///
+    /// Unchanged meetings keep their rows, and changed meetings reuse the remaining rows.
+    ///
+    /// # Returns
+    /// Whether any meeting or instructor link changed
+    #[allow(clippy::too_many_arguments)]
+    async fn save_meetings(
+        txn: &DatabaseTransaction,
+        number: &str,
+        component_id: Uuid,
+        meetings: Vec<Meeting>,
+        instructor_ids: &InstructorIds,
+        stored: &mut StoredSemester,
+        new_rows: &mut NewRows,
+        stale_rows: &mut StaleRows,
+    ) -> Result<bool, DbErr> {
+        let mut unmatched = stored.meetings.remove(&component_id).unwrap_or_default();
+        let mut changed = false;
+
+        let mut pending = Vec::new();
+        for meeting in meetings {
+            let unchanged = unmatched.iter().position(|stored_meeting| {
+                let mut row = stored_meeting.clone().into_active_model();
+                Self::set_meeting_fields(&mut row, &meeting);
+                !row.is_changed()
+            });
+            match unchanged {
+                Some(i) => {
+                    let meeting_id = unmatched.swap_remove(i).id;
+                    changed |= Self::link_instructors(
+                        number,
+                        meeting_id,
+                        &meeting,
+                        instructor_ids,
+                        stored,
+                        new_rows,
+                        stale_rows,
+                    );
+                }
+                None => pending.push(meeting),
+            }
+        }
+
+        for meeting in pending {
+            let meeting_id = match unmatched.pop() {
+                Some(stored_meeting) => {
+                    let meeting_id = stored_meeting.id;
+                    let mut row = stored_meeting.into_active_model();
+                    Self::set_meeting_fields(&mut row, &meeting);
+                    row.update(txn).await?;
+                    meeting_id
+                }
+                None => {
+                    let meeting_id = Uuid::new_v4();
+                    let mut row = meetings::ActiveModel {
                         id: Set(meeting_id),
                         component_id: Set(component_id),
-                        days_pattern: Set(meeting.days.to_string()),
-                        time_begin: Set(meeting.time.as_ref().map(|t| t.begin)),
-                        time_end: Set(meeting.time.as_ref().map(|t| t.end)),
-                        campus: Set(meeting.campus),
-                    });
+                        ..Default::default()
+                    };
+                    Self::set_meeting_fields(&mut row, &meeting);
+                    new_rows.meetings.push(row);
+                    meeting_id
                 }
-            }
+            };
+
+            Self::link_instructors(
+                number,
+                meeting_id,
+                &meeting,
+                instructor_ids,
+                stored,
+                new_rows,
+                stale_rows,
+            );
+            changed = true;
         }
 
-        // Bulk insert everything at once
-        if !all_courses.is_empty() {
-            courses::Entity::insert_many(all_courses).exec(&txn).await?;
+        if !unmatched.is_empty() {
+            stale_rows
+                .meetings
+                .extend(unmatched.iter().map(|meeting| meeting.id));
+            changed = true;
         }
-        if !all_components.is_empty() {
-            components::Entity::insert_many(all_components)
-                .exec(&txn)
+
+        Ok(changed)
+    }
+
+    /// Links a meeting to the instructors it was scraped with, unlinking any others
+    ///
+    /// # Returns
+    /// Whether any link was added or removed
+    fn link_instructors(
+        number: &str,
+        meeting_id: Uuid,
+        meeting: &Meeting,
+        instructor_ids: &InstructorIds,
+        stored: &mut StoredSemester,
+        new_rows: &mut NewRows,
+        stale_rows: &mut StaleRows,
+    ) -> bool {
+        let links = stored
+            .instructor_meetings
+            .remove(&meeting_id)
+            .unwrap_or_default();
+        let wanted: BTreeSet<Uuid> = meeting
+            .instructors
+            .iter()
+            .filter_map(|name| {
+                instructor_ids
+                    .get(&(number.to_owned(), name.clone()))
+                    .copied()
+            })
+            .collect();
+        let linked: BTreeSet<Uuid> = links.iter().map(|link| link.instructor_id).collect();
+
+        let stale: Vec<Uuid> = links
+            .iter()
+            .filter(|link| !wanted.contains(&link.instructor_id))
+            .map(|link| link.id)
+            .collect();
+        let missing: Vec<Uuid> = wanted.difference(&linked).copied().collect();
+        let changed = !stale.is_empty() || !missing.is_empty();
+
+        stale_rows.instructor_meetings.extend(stale);
+        new_rows
+            .instructor_meetings
+            .extend(
+                missing
+                    .into_iter()
+                    .map(|instructor_id| instructor_meetings::ActiveModel {
+                        id: Set(Uuid::new_v4()),
+                        instructor_id: Set(instructor_id),
+                        meeting_id: Set(meeting_id),
+                    }),
+            );
+        changed
+    }
+
+    async fn insert_in_batches<A>(txn: &DatabaseTransaction, rows: Vec<A>) -> Result<(), DbErr>
+    where
+        A: ActiveModelTrait,
+        <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+    {
+        for batch in rows.chunks(Self::BATCH_SIZE) {
+            A::Entity::insert_many(batch.to_vec())
+                .exec_without_returning(txn)
                 .await?;
         }
-        if !all_meetings.is_empty() {
-            meetings::Entity::insert_many(all_meetings)
-                .exec(&txn)
+        Ok(())
+    }
+
+    async fn delete_in_batches<E: EntityTrait>(
+        txn: &DatabaseTransaction,
+        id: E::Column,
+        ids: Vec<Uuid>,
+    ) -> Result<(), DbErr> {
+        for batch in ids.chunks(Self::BATCH_SIZE) {
+            E::delete_many()
+                .filter(id.is_in(batch.to_vec()))
+                .exec(txn)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Sets the syllabus URL of already stored components
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `syllabus_map` - Syllabus URLs keyed by year, season, course number, and component code
+    ///
+    /// # Returns
+    /// The number of components that were updated
+    pub async fn set_syllabus_urls(
+        db: &DatabaseConnection,
+        syllabus_map: &SyllabusMap,
+    ) -> Result<u64, DbErr> {
+        let txn = db.begin().await?;
+        let mut updated = 0;
+
+        for ((year, season, number, code), url) in syllabus_map {
+            let component_ids: Vec<Uuid> = components::Entity::find()
+                .select_only()
+                .column(components::Column::Id)
+                .join(JoinType::InnerJoin, components::Relation::Courses.def())
+                .filter(courses::Column::Number.eq(number))
+                .filter(courses::Column::Season.eq(season.as_str()))
+                .filter(courses::Column::Year.eq(**year as i16))
+                .filter(components::Column::Code.eq(code))
+                .into_tuple()
+                .all(&txn)
                 .await?;
+
+            if component_ids.is_empty() {
+                continue;
+            }
+
+            updated += components::Entity::update_many()
+                .col_expr(components::Column::SyllabusUrl, Expr::value(url.clone()))
+                .filter(components::Column::Id.is_in(component_ids))
+                .exec(&txn)
+                .await?
+                .rows_affected;
         }
 
         txn.commit().await?;
-        Ok(course_ids)
+        Ok(updated)
     }
 
-    fn course_to_active_model(course_obj: &CourseObject) -> courses::ActiveModel {
-        courses::ActiveModel {
-            id: Set(Uuid::new_v4()),
+    /// Builds a course that isn't stored yet
+    fn new_course(course_id: Uuid, course_obj: &CourseObject) -> courses::ActiveModel {
+        let mut course = courses::ActiveModel {
+            id: Set(course_id),
             number: Set(course_obj.course.number.to_string()),
-            units: Set(course_obj.course.units.to_string()),
             season: Set(course_obj.course.season.as_str().to_owned()),
             year: Set(*course_obj.course.year as i16),
+            // Defaults for courses whose details couldn't be fetched
+            related_urls: Set(Default::default()),
+            special_permission: Set(false),
+            description: Set(None),
+            prerequisites: Set(None),
+            corequisites: Set(Default::default()),
+            crosslisted: Set(Default::default()),
+            notes: Set(None),
+            department: Set(None),
+            ..Default::default()
+        };
+        Self::set_course_fields(&mut course, course_obj);
+        course
+    }
 
-            // When metadata is None, default to empty vec of related URLs
-            related_urls: Set(course_obj
-                .metadata
-                .as_ref()
-                .map(|m| m.related_urls.to_owned().into())
-                .unwrap_or_default()),
-
-            // When metadata is None, default no special permission
-            special_permission: Set(course_obj
-                .metadata
-                .as_ref()
-                .map(|m| m.special_permission)
-                .unwrap_or(false)),
-
-            // Description and prerequisites can be None
-            description: Set(course_obj
-                .metadata
-                .as_ref()
-                .and_then(|m| m.description.to_owned())),
-            prerequisites: Set(course_obj
-                .metadata
-                .as_ref()
-                .and_then(|m| m.prerequisites.clone().into_inner())
-                .and_then(|expr| serde_json::to_string(&expr).ok())), // Handle serialization errors gracefully
-
-            // For corequisites and crosslisted, use the same behavior as related URLs
-            corequisites: Set(course_obj
-                .metadata
-                .as_ref()
-                .map(|m| m.corequisites.to_vec().into())
-                .unwrap_or_default()),
-            crosslisted: Set(course_obj
-                .metadata
-                .as_ref()
-                .map(|m| m.crosslisted.to_vec().into())
-                .unwrap_or_default()),
-
-            // Notes can also be None
-            notes: Set(course_obj
-                .metadata
-                .as_ref()
-                .and_then(|m| m.notes.to_owned())),
+    /// Sets the fields of a course row to a scraped course, leaving the fields that come from
+    /// its details as they are when the details couldn't be fetched
+    fn set_course_fields(course: &mut courses::ActiveModel, course_obj: &CourseObject) {
+        course
+            .units
+            .set_if_not_equals(course_obj.course.units.to_string());
+        if let Some(department) = &course_obj.course.department {
+            course
+                .department
+                .set_if_not_equals(Some(department.clone()));
         }
+
+        let Some(metadata) = &course_obj.metadata else {
+            return;
+        };
+        course
+            .related_urls
+            .set_if_not_equals(metadata.related_urls.to_owned().into());
+        course
+            .special_permission
+            .set_if_not_equals(metadata.special_permission);
+        course
+            .description
+            .set_if_not_equals(metadata.description.to_owned());
+        course.prerequisites.set_if_not_equals(
+            metadata
+                .prerequisites
+                .clone()
+                .into_inner()
+                .and_then(|expr| serde_json::to_string(&expr).ok()), // Handle serialization errors gracefully
+        );
+        course
+            .corequisites
+            .set_if_not_equals(metadata.corequisites.to_vec().into());
+        course
+            .crosslisted
+            .set_if_not_equals(metadata.crosslisted.to_vec().into());
+        course.notes.set_if_not_equals(metadata.notes.to_owned());
+    }
+
+    fn set_meeting_fields(row: &mut meetings::ActiveModel, meeting: &Meeting) {
+        row.days_pattern.set_if_not_equals(meeting.days.to_string());
+        row.days_mask.set_if_not_equals(match meeting.days {
+            Days::Days(days) => Some(days.bits().into()),
+            Days::TBA => None,
+        });
+        row.time_begin
+            .set_if_not_equals(meeting.time.as_ref().map(|t| t.begin));
+        row.time_end
+            .set_if_not_equals(meeting.time.as_ref().map(|t| t.end));
+        row.bldg_room.set_if_not_equals(meeting.bldg_room.clone());
+        row.campus.set_if_not_equals(meeting.campus.clone());
+    }
+}
+
+fn component_type_name(component_type: ComponentType) -> String {
+    match component_type {
+        ComponentType::Lecture => "Lecture".to_string(),
+        ComponentType::Section => "Section".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_keeps_rest_of_semester() {
+        let retried = HashSet::from(["15122".to_owned()]);
+
+        // Saving retried courses only replaces, and so can only remove, those courses
+        assert!(SaveScope::Courses.replaces("15122", &retried));
+        assert!(!SaveScope::Courses.replaces("15213", &retried));
+        // Saving a whole semester removes the stored courses it no longer lists
+        assert!(SaveScope::Semester.replaces("15213", &retried));
     }
 }
@@ -0,0 +1,121 @@
+use crate::entities::scrape_runs;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, QuerySelect, Statement, prelude::DateTimeWithTimeZone,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Per-semester totals recorded with a scrape run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SemesterSummary {
+    pub season: String,
+    pub year: i16,
+    pub course_count: usize,
+}
+
+/// Everything a finished scrape run reports about itself
+#[derive(Debug, Clone, Default)]
+pub struct ScrapeRunSummary {
+    /// The semesters that were fetched, with their course counts
+    pub semesters: Vec<SemesterSummary>,
+    /// Courses whose details could not be fetched (e.g., "15122 F25")
+    pub detail_failures: Vec<String>,
+    /// Problems encountered while parsing the scraped data
+    pub diagnostics: serde_json::Value,
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<serde_json::Value, DbErr> {
+    serde_json::to_value(value).map_err(|e| DbErr::Json(e.to_string()))
+}
+
+pub struct ScrapeRunService;
+
+impl ScrapeRunService {
+    /// Records the start of a scrape run and returns its ID
+    pub async fn start_run(db: &DatabaseConnection) -> Result<Uuid, DbErr> {
+        let id = Uuid::new_v4();
+
+        scrape_runs::ActiveModel {
+            id: Set(id),
+            started_at: Set(Utc::now().into()),
+            finished_at: Set(None),
+            semesters: Set(serde_json::json!([])),
+            course_count: Set(0),
+            detail_failures: Set(serde_json::json!([])),
+            diagnostics: Set(serde_json::json!([])),
+        }
+        .insert(db)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Marks a scrape run as finished and stores its summary
+    pub async fn finish_run(
+        db: &DatabaseConnection,
+        id: Uuid,
+        summary: ScrapeRunSummary,
+    ) -> Result<(), DbErr> {
+        let course_count: usize = summary.semesters.iter().map(|s| s.course_count).sum();
+
+        scrape_runs::ActiveModel {
+            id: Set(id),
+            finished_at: Set(Some(Utc::now().into())),
+            semesters: Set(to_json(&summary.semesters)?),
+            course_count: Set(course_count as i32),
+            detail_failures: Set(to_json(&summary.detail_failures)?),
+            diagnostics: Set(summary.diagnostics),
+            ..Default::default()
+        }
+        .update(db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the finish time of the most recent completed scrape run, if any
+    pub async fn get_last_finished(
+        db: &DatabaseConnection,
+    ) -> Result<Option<DateTime<Utc>>, DbErr> {
+        let finished_at = scrape_runs::Entity::find()
+            .select_only()
+            .column_as(scrape_runs::Column::FinishedAt.max(), "finished_at")
+            .into_tuple::<Option<DateTimeWithTimeZone>>()
+            .one(db)
+            .await?;
+
+        Ok(finished_at
+            .flatten()
+            .map(|finished_at| finished_at.with_timezone(&Utc)))
+    }
+
+    /// Returns when each semester was last successfully scraped, keyed by (season, year)
+    pub async fn get_last_updated(
+        db: &DatabaseConnection,
+    ) -> Result<HashMap<(String, i16), DateTime<Utc>>, DbErr> {
+        // Only the semester summaries are read, not the runs' failures and diagnostics
+        let rows = db
+            .query_all(Statement::from_string(
+                db.get_database_backend(),
+                r#"SELECT "semester"->>'season' AS "season",
+                    ("semester"->>'year')::smallint AS "year",
+                    max("finished_at") AS "finished_at"
+                FROM "scrape_runs", json_array_elements("semesters") AS "semester"
+                WHERE "finished_at" IS NOT NULL
+                GROUP BY 1, 2"#,
+            ))
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let season: String = row.try_get("", "season")?;
+                let year: i16 = row.try_get("", "year")?;
+                let finished_at: DateTimeWithTimeZone = row.try_get("", "finished_at")?;
+                Ok(((season, year), finished_at.with_timezone(&Utc)))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,182 @@
+use crate::entities::{courses, syllabi, syllabus_files};
+use chrono::Utc;
+use models::syllabus_data::{Season, Year};
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    TransactionTrait, sea_query::OnConflict,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// A syllabus file that was downloaded and stored in the archive
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedFile {
+    /// SHA-256 hash the file is stored under
+    pub hash: String,
+    /// MIME type of the file (e.g., "application/pdf")
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Plain text extracted from the file
+    pub text: String,
+}
+
+/// The archived syllabus of a component
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedSyllabus {
+    /// Course number without a dash (e.g., "15122")
+    pub number: String,
+    pub season: Season,
+    pub year: Year,
+    /// Component code (e.g., "Lec 1" or "A")
+    pub code: String,
+    /// URL the file was downloaded from
+    pub source_url: String,
+    /// Hash of the [`ArchivedFile`]
+    pub hash: String,
+}
+
+pub struct SyllabusService;
+
+impl SyllabusService {
+    /// Records archived files and the syllabi that use them, replacing the archive of any
+    /// component that already had one
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `files` - Newly archived files; files that are already recorded are skipped
+    /// * `syllabi` - The syllabi, whose files must be in `files` or already recorded
+    ///
+    /// # Returns
+    /// The number of syllabi recorded
+    pub async fn save_archived(
+        db: &DatabaseConnection,
+        files: Vec<ArchivedFile>,
+        syllabi: Vec<ArchivedSyllabus>,
+    ) -> Result<usize, DbErr> {
+        let txn = db.begin().await?;
+        let archived_at = Utc::now();
+
+        for file in files {
+            let model = syllabus_files::ActiveModel {
+                hash: Set(file.hash),
+                content_type: Set(file.content_type),
+                size_bytes: Set(file.size_bytes),
+                text: Set(file.text),
+                archived_at: Set(archived_at.into()),
+            };
+            syllabus_files::Entity::insert(model)
+                .on_conflict(
+                    OnConflict::column(syllabus_files::Column::Hash)
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .do_nothing()
+                .exec(&txn)
+                .await?;
+        }
+
+        let count = syllabi.len();
+        for syllabus in syllabi {
+            let model = syllabi::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                number: Set(syllabus.number),
+                season: Set(syllabus.season.as_str().to_owned()),
+                year: Set(*syllabus.year as i16),
+                code: Set(syllabus.code),
+                source_url: Set(syllabus.source_url),
+                hash: Set(syllabus.hash),
+                archived_at: Set(archived_at.into()),
+            };
+            syllabi::Entity::insert(model)
+                .on_conflict(
+                    OnConflict::columns([
+                        syllabi::Column::Number,
+                        syllabi::Column::Season,
+                        syllabi::Column::Year,
+                        syllabi::Column::Code,
+                    ])
+                    .update_columns([
+                        syllabi::Column::SourceUrl,
+                        syllabi::Column::Hash,
+                        syllabi::Column::ArchivedAt,
+                    ])
+                    .to_owned(),
+                )
+                .exec(&txn)
+                .await?;
+        }
+
+        txn.commit().await?;
+        Ok(count)
+    }
+
+    /// Get the source URLs that were already archived, so unchanged syllabi aren't downloaded
+    /// again
+    pub async fn get_archived_urls(
+        db: &DatabaseConnection,
+    ) -> Result<HashMap<String, String>, DbErr> {
+        Ok(syllabi::Entity::find()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|syllabus| (syllabus.source_url, syllabus.hash))
+            .collect())
+    }
+
+    /// Get an archived file by its hash
+    pub async fn get_file(
+        db: &DatabaseConnection,
+        hash: &str,
+    ) -> Result<Option<syllabus_files::Model>, DbErr> {
+        syllabus_files::Entity::find_by_id(hash.to_owned())
+            .one(db)
+            .await
+    }
+
+    /// Get the archived syllabi of the given courses' components
+    ///
+    /// # Arguments
+    /// * `db` - The database connection
+    /// * `courses` - The courses to look up
+    ///
+    /// # Returns
+    /// The hash of each archived syllabus, keyed by course ID and component code
+    pub async fn get_hashes(
+        db: &DatabaseConnection,
+        courses: &[courses::Model],
+    ) -> Result<HashMap<(Uuid, String), String>, DbErr> {
+        if courses.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let course_ids: HashMap<(&str, &str, i16), Uuid> = courses
+            .iter()
+            .map(|course| {
+                let offering = (course.number.as_str(), course.season.as_str(), course.year);
+                (offering, course.id)
+            })
+            .collect();
+        let numbers: HashSet<&str> = courses
+            .iter()
+            .map(|course| course.number.as_str())
+            .collect();
+
+        let syllabi = syllabi::Entity::find()
+            .filter(syllabi::Column::Number.is_in(numbers))
+            .all(db)
+            .await?;
+
+        Ok(syllabi
+            .into_iter()
+            .filter_map(|syllabus| {
+                let offering = (
+                    syllabus.number.as_str(),
+                    syllabus.season.as_str(),
+                    syllabus.year,
+                );
+                let course_id = *course_ids.get(&offering)?;
+                Some(((course_id, syllabus.code), syllabus.hash))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    diagnostics::Diagnostics,
+    fetch::{FetchOutput, extract_year, parse_soc},
+};
+use models::{
+    course_data::CourseObject,
+    syllabus_data::{Semester, Year},
+};
+use std::{
+    fs,
+    io::Result as IoResult,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// An archived SOC file for a past semester
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedFile {
+    pub semester: Semester,
+    pub path: PathBuf,
+}
+
+/// Reads the semester an archived SOC file is for from its name
+///
+/// The semester ends the file stem, so both `F18.dat` and `sched_layout_F18.dat` are for Fall
+/// 2018.
+///
+/// # Arguments
+/// * `path` - Path of the archived file
+///
+/// # Returns
+/// The semester, or `None` if the name doesn't end with one
+pub fn semester_of(path: &Path) -> Option<Semester> {
+    let stem = path.file_stem()?.to_str()?;
+    let semester = stem.rsplit(['_', '-', ' ']).next()?;
+
+    Semester::from_str(semester).ok()
+}
+
+/// Lists the archived SOC files in a directory, oldest semester first
+///
+/// Files whose name doesn't end with a semester are skipped.
+///
+/// # Arguments
+/// * `dir` - The directory holding the archived files
+pub fn find_archived_files(dir: impl AsRef<Path>) -> IoResult<Vec<ArchivedFile>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        match semester_of(&path) {
+            Some(semester) => files.push(ArchivedFile { semester, path }),
+            None => println!("Skipping {}: no semester in its name", path.display()),
+        }
+    }
+
+    files.sort_by_key(|file| (*file.semester.year, file.semester.season.term_order()));
+    Ok(files)
+}
+
+/// Decodes the contents of an archived file
+///
+/// Older SOC files were exported as Latin-1 rather than UTF-8, so anything that isn't valid
+/// UTF-8 is read as Latin-1.
+pub fn decode(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes)
+        .unwrap_or_else(|e| e.into_bytes().into_iter().map(char::from).collect())
+}
+
+/// Reads and parses an archived SOC file
+///
+/// The courses have no metadata, since the courseDetails pages of past semesters are gone.
+///
+/// # Arguments
+/// * `file` - The archived file to read
+///
+/// # Returns
+/// A [`FetchOutput`] with the courses in schedule order and diagnostics for lines that could
+/// not be parsed
+pub fn read_archived_file(file: &ArchivedFile) -> IoResult<FetchOutput> {
+    let text = decode(fs::read(&file.path)?);
+    let Semester { season, year } = file.semester;
+
+    if let Some(header_year) = extract_year(&text)
+        && header_year != year
+    {
+        eprintln!(
+            "{} says it is for {}, using {} from its name",
+            file.path.display(),
+            *header_year,
+            file.semester
+        );
+    }
+    if !Year::all().contains(&year) {
+        eprintln!(
+            "{} is for {}, outside the years the fetcher covers",
+            file.path.display(),
+            file.semester
+        );
+    }
+
+    let (course_entries, diagnostics) = parse_soc(&text, season, year);
+    println!(
+        "Parsed {} courses for {} from {}",
+        course_entries.len(),
+        file.semester,
+        file.path.display()
+    );
+
+    Ok(FetchOutput {
+        course_objs: course_entries
+            .into_iter()
+            .map(|course| CourseObject {
+                course,
+                metadata: None,
+            })
+            .collect(),
+        diagnostics: diagnostics.into_iter().collect::<Diagnostics>(),
+        failed: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::syllabus_data::Season;
+    use uuid::Uuid;
+
+    // Fall 2018 as it was exported: a shorter header, dashed numbers, CRLF line endings, and
+    // Latin-1 text
+    const F18: &[u8] = b"Carnegie Mellon University\r\n\
+Semester: Fall 2018\r\n\
+\r\n\
+\tModern Languages\r\n\
+\t82-101\tElementary French I\r\n\
+\t\t\t12.0\tA\tMTWR\t09:30AM\t10:20AM\tBH 235A\tPittsburgh, Pennsylvania\tL\xe9vy\r\n\
+\t82-103\tElementary French I Online\r\n\
+???\r\n";
+
+    #[test]
+    fn test_semester_of() {
+        let f18 = Semester::from_str("F18").unwrap();
+        assert_eq!(semester_of(Path::new("archive/F18.dat")), Some(f18));
+        assert_eq!(
+            semester_of(Path::new("archive/sched_layout_F2018.dat")),
+            Some(f18)
+        );
+        assert_eq!(semester_of(Path::new("archive/README.md")), None);
+    }
+
+    #[test]
+    fn test_read_archive() {
+        let dir = std::env::temp_dir().join(format!("backfill-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("F18.dat"), F18).unwrap();
+        fs::write(dir.join("S19.dat"), b"").unwrap();
+        fs::write(dir.join("notes.txt"), b"").unwrap();
+
+        assert_eq!(decode(b"L\xe9vy".to_vec()), "L\u{e9}vy");
+
+        let files = find_archived_files(&dir).unwrap();
+        assert_eq!(
+            files
+                .iter()
+                .map(|file| file.semester.to_string())
+                .collect::<Vec<_>>(),
+            vec!["F18", "S19"]
+        );
+
+        let output = read_archived_file(&files[0]).unwrap();
+        assert_eq!(output.course_objs.len(), 2);
+
+        let course = &output.course_objs[0];
+        assert_eq!(course.course.number.to_string(), "82101");
+        assert_eq!(course.course.season, Season::Fall);
+        assert_eq!(*course.course.year, 2018);
+        assert!(course.metadata.is_none());
+
+        // Lines that still can't be parsed are reported
+        assert_eq!(output.diagnostics.len(), 1);
+        assert_eq!(output.diagnostics.diagnostics[0].raw, "???");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
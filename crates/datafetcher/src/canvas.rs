@@ -0,0 +1,386 @@
+use crate::source::{FetchOptions, MAX_BACKOFF, RateLimiter, backoff_delay, is_transient_status};
+use models::syllabus_data::{Department, Season, Year};
+use reqwest::{
+    Client, StatusCode, Url,
+    header::{AUTHORIZATION, LINK, RETRY_AFTER},
+};
+use serde::{Deserialize, de::DeserializeOwned};
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// Base URL of CMU's Canvas instance
+pub const DEFAULT_CANVAS_URL: &str = "https://canvas.cmu.edu";
+
+/// Name of the module that lists a syllabus registry's files
+const SYLLABI_MODULE: &str = "Available Syllabi";
+
+/// Largest page size Canvas allows
+const PER_PAGE: u32 = 100;
+
+/// Error returned when Canvas cannot be queried
+#[derive(Debug)]
+pub enum CanvasError {
+    /// `CANVAS_ACCESS_TOKEN` is not set
+    MissingToken,
+    /// Canvas rejected the access token
+    Unauthorized { url: String },
+    /// The requested resource doesn't exist
+    NotFound { url: String },
+    /// The HTTP request failed or returned a non-success status
+    Http {
+        url: String,
+        message: String,
+        /// Whether retrying the request might succeed
+        transient: bool,
+    },
+    /// The response wasn't the JSON we expected
+    InvalidResponse { url: String, message: String },
+}
+
+impl Display for CanvasError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingToken => write!(f, "CANVAS_ACCESS_TOKEN is not set"),
+            Self::Unauthorized { url } => {
+                write!(f, "Canvas rejected the access token for {url}")
+            }
+            Self::NotFound { url } => write!(f, "{url} was not found"),
+            Self::Http { url, message, .. } => write!(f, "Request to {url} failed: {message}"),
+            Self::InvalidResponse { url, message } => {
+                write!(f, "Unexpected response from {url}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CanvasError {}
+
+/// A module of a Canvas course
+#[derive(Debug, Clone, Deserialize)]
+pub struct Module {
+    pub name: String,
+    /// API URL listing the module's items
+    pub items_url: String,
+}
+
+/// An item of a Canvas module
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModuleItem {
+    pub title: String,
+    /// Kind of item (e.g., "File" or "Page")
+    #[serde(rename = "type")]
+    pub item_type: String,
+    /// API URL of the item's content, if it has any
+    pub url: Option<String>,
+}
+
+/// A Canvas file
+#[derive(Debug, Clone, Deserialize)]
+pub struct CanvasFile {
+    pub filename: String,
+    /// URL the file can be downloaded from
+    pub url: String,
+}
+
+/// A syllabus file listed in a syllabus registry
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyllabusFile {
+    /// Title of the module item, which names the course and section
+    pub title: String,
+    /// URL the syllabus can be downloaded from
+    pub url: String,
+}
+
+/// Builds the SIS ID of a department's syllabus registry course for a semester
+pub fn registry_sis_id(season: Season, year: Year, department: Department) -> String {
+    format!("syllabus-registry-{}{year}-{department}", season.as_str())
+}
+
+/// Finds the URL of the next page in a Canvas `Link` header
+///
+/// # Arguments
+/// * `link` - The value of the header
+///
+/// # Returns
+/// The URL with `rel="next"`, if there is one
+pub fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>'))
+            .map(str::to_owned)
+    })
+}
+
+/// Client for the parts of the Canvas API used by the syllabus registry, with a rate limit,
+/// a timeout, and exponential-backoff retries for transient failures
+#[derive(Debug)]
+pub struct CanvasClient {
+    client: Client,
+    base_url: String,
+    token: String,
+    rate_limiter: RateLimiter,
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+impl CanvasClient {
+    /// Creates a client for the Canvas instance at `base_url`
+    pub fn new(base_url: &str, token: &str, options: &FetchOptions) -> Self {
+        let client = Client::builder()
+            .timeout(options.timeout)
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            token: token.to_owned(),
+            rate_limiter: RateLimiter::new(options.requests_per_second),
+            max_retries: options.max_retries,
+            initial_backoff: options.initial_backoff,
+        }
+    }
+
+    /// Creates a client from the `CANVAS_ACCESS_TOKEN` and optional `CANVAS_URL` environment
+    /// variables
+    pub fn from_env(options: &FetchOptions) -> Result<Self, CanvasError> {
+        dotenvy::dotenv().ok();
+
+        let token = std::env::var("CANVAS_ACCESS_TOKEN")
+            .ok()
+            .filter(|token| !token.trim().is_empty())
+            .ok_or(CanvasError::MissingToken)?;
+        let base_url =
+            std::env::var("CANVAS_URL").unwrap_or_else(|_| DEFAULT_CANVAS_URL.to_owned());
+
+        Ok(Self::new(&base_url, token.trim(), options))
+    }
+
+    /// Sends a single authorized request, returning the body, the next page's URL, and how
+    /// long the server asked us to wait before retrying, if it did
+    async fn get_once(
+        &self,
+        url: &str,
+    ) -> Result<(Vec<u8>, Option<String>), (CanvasError, Option<Duration>)> {
+        let http_error = |e: reqwest::Error| {
+            let transient = e.is_timeout() || e.is_connect() || e.is_request() || e.is_body();
+            let error = CanvasError::Http {
+                url: url.to_owned(),
+                message: e.to_string(),
+                transient,
+            };
+            (error, None)
+        };
+
+        if let Some(host) = Url::parse(url).ok().as_ref().and_then(Url::host_str) {
+            self.rate_limiter.wait(host).await;
+        }
+
+        let response = self
+            .client
+            .get(url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .send()
+            .await
+            .map_err(http_error)?;
+        let status = response.status();
+
+        match status {
+            StatusCode::UNAUTHORIZED => {
+                let error = CanvasError::Unauthorized {
+                    url: url.to_owned(),
+                };
+                return Err((error, None));
+            }
+            StatusCode::NOT_FOUND => {
+                let error = CanvasError::NotFound {
+                    url: url.to_owned(),
+                };
+                return Err((error, None));
+            }
+            _ => {}
+        }
+
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs);
+
+            let error = CanvasError::Http {
+                url: url.to_owned(),
+                message: format!("HTTP status {status}"),
+                transient: is_transient_status(status),
+            };
+            return Err((error, retry_after));
+        }
+
+        let next = response
+            .headers()
+            .get(LINK)
+            .and_then(|value| value.to_str().ok())
+            .and_then(next_page_url);
+
+        let body = response.bytes().await.map_err(http_error)?;
+        Ok((body.to_vec(), next))
+    }
+
+    /// Sends an authorized request, retrying transient failures
+    async fn get(&self, url: &str) -> Result<(Vec<u8>, Option<String>), CanvasError> {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match self.get_once(url).await {
+                Ok(response) => return Ok(response),
+                Err((error, retry_after)) => {
+                    let transient = matches!(
+                        error,
+                        CanvasError::Http {
+                            transient: true,
+                            ..
+                        }
+                    );
+                    if !transient || attempt > self.max_retries {
+                        return Err(error);
+                    }
+
+                    let delay = backoff_delay(self.initial_backoff, attempt)
+                        .max(retry_after.unwrap_or_default())
+                        .min(MAX_BACKOFF);
+
+                    eprintln!("{error}, retrying in {delay:?} (attempt {attempt})");
+                    sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// Fetches and deserializes a single JSON response
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, CanvasError> {
+        let (body, _) = self.get(url).await?;
+        serde_json::from_slice(&body).map_err(|e| CanvasError::InvalidResponse {
+            url: url.to_owned(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Fetches every page of a paginated JSON list, following the `Link` headers
+    async fn get_all<T: DeserializeOwned>(&self, url: &str) -> Result<Vec<T>, CanvasError> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let mut next = Some(format!("{url}{separator}per_page={PER_PAGE}"));
+        let mut items = Vec::new();
+
+        while let Some(url) = next {
+            let (body, next_url) = self.get(&url).await?;
+            let page: Vec<T> =
+                serde_json::from_slice(&body).map_err(|e| CanvasError::InvalidResponse {
+                    url: url.clone(),
+                    message: e.to_string(),
+                })?;
+
+            items.extend(page);
+            next = next_url;
+        }
+
+        Ok(items)
+    }
+
+    /// Downloads a file, sending the access token since Canvas file URLs require it
+    ///
+    /// # Arguments
+    /// * `url` - The download URL of the file
+    ///
+    /// # Returns
+    /// The contents of the file
+    pub async fn download(&self, url: &str) -> Result<Vec<u8>, CanvasError> {
+        let (body, _) = self.get(url).await?;
+        Ok(body)
+    }
+
+    /// Lists the syllabus files in a department's syllabus registry for a semester
+    ///
+    /// # Arguments
+    /// * `season` - The season of the semester
+    /// * `year` - The year of the semester
+    /// * `department` - The department whose registry to read
+    ///
+    /// # Returns
+    /// The syllabus files, or an empty list if the department has no registry that semester
+    pub async fn syllabus_files(
+        &self,
+        season: Season,
+        year: Year,
+        department: Department,
+    ) -> Result<Vec<SyllabusFile>, CanvasError> {
+        let modules_url = format!(
+            "{}/api/v1/courses/sis_course_id:{}/modules",
+            self.base_url,
+            registry_sis_id(season, year, department)
+        );
+
+        let modules = match self.get_all::<Module>(&modules_url).await {
+            Ok(modules) => modules,
+            // Not every department publishes a registry every semester
+            Err(CanvasError::NotFound { .. }) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let Some(module) = modules.iter().find(|m| m.name == SYLLABI_MODULE) else {
+            return Ok(Vec::new());
+        };
+
+        let mut files = Vec::new();
+        for item in self.get_all::<ModuleItem>(&module.items_url).await? {
+            let Some(file_url) = item.url.filter(|_| item.item_type == "File") else {
+                continue;
+            };
+
+            let file: CanvasFile = self.get_json(&file_url).await?;
+            files.push(SyllabusFile {
+                title: item.title,
+                url: file.url,
+            });
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_sis_id() {
+        assert_eq!(
+            registry_sis_id(Season::Fall, Year(2025), Department::CS),
+            "syllabus-registry-F25-CS"
+        );
+    }
+
+    #[test]
+    fn test_next_page_url() {
+        let link = "<https://canvas.cmu.edu/api/v1/x?page=1>; rel=\"current\",\
+                    <https://canvas.cmu.edu/api/v1/x?page=2>; rel=\"next\",\
+                    <https://canvas.cmu.edu/api/v1/x?page=5>; rel=\"last\"";
+        assert_eq!(
+            next_page_url(link).as_deref(),
+            Some("https://canvas.cmu.edu/api/v1/x?page=2")
+        );
+
+        assert_eq!(
+            next_page_url("<https://canvas.cmu.edu/api/v1/x?page=5>; rel=\"last\""),
+            None
+        );
+    }
+}
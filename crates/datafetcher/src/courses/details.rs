@@ -0,0 +1,661 @@
+use crate::{
+    diagnostics::{Diagnostic, DiagnosticCategory, DiagnosticSource},
+    util::clean_text,
+};
+use lazy_static::lazy_static;
+use models::{
+    course_data::{CourseEntry, CourseMetadata, CourseObject, TimeRange, parse_instructors},
+    days::Days,
+    requisite::Prerequisites,
+    reservation::{Reservation, Restriction},
+};
+use scraper::{ElementRef, Html, Selector};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+lazy_static! {
+    static ref DT: Selector = Selector::parse("dt").unwrap();
+    static ref HEADINGS: Selector = Selector::parse("h1, h2, h3, h4, h5, h6, dt, strong").unwrap();
+    static ref RELATED_URLS: Selector = Selector::parse("#course-detail-related-urls a").unwrap();
+    static ref TABLE: Selector = Selector::parse("table").unwrap();
+    static ref TH: Selector = Selector::parse("th").unwrap();
+    static ref TR: Selector = Selector::parse("tr").unwrap();
+    static ref TD: Selector = Selector::parse("td").unwrap();
+}
+
+/// A field on the courseDetails page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailField {
+    SpecialPermission,
+    Description,
+    Prerequisites,
+    Corequisites,
+    Crosslisted,
+    Notes,
+    Schedule,
+}
+
+impl DetailField {
+    /// The label the field is introduced by on the page, in lowercase without punctuation
+    fn label(self) -> &'static str {
+        match self {
+            Self::SpecialPermission => "special permission",
+            Self::Description => "description",
+            Self::Prerequisites => "prerequisites",
+            Self::Corequisites => "corequisites",
+            Self::Crosslisted => "cross-listed courses",
+            Self::Notes => "notes",
+            Self::Schedule => "schedule",
+        }
+    }
+
+    /// The diagnostic category used when the field's text cannot be parsed, or `None` for
+    /// free-text fields, which accept any text
+    fn invalid_category(self) -> Option<DiagnosticCategory> {
+        match self {
+            Self::SpecialPermission => Some(DiagnosticCategory::InvalidSpecialPermission),
+            Self::Prerequisites => Some(DiagnosticCategory::InvalidPrerequisites),
+            Self::Corequisites => Some(DiagnosticCategory::InvalidCorequisites),
+            Self::Crosslisted => Some(DiagnosticCategory::InvalidCrosslisted),
+            Self::Schedule => Some(DiagnosticCategory::InvalidSchedule),
+            Self::Description | Self::Notes => None,
+        }
+    }
+}
+
+impl Display for DetailField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Error returned when a field on the courseDetails page cannot be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum DetailsError {
+    /// No element with the field's label was found
+    MissingField(DetailField),
+    /// The field was found, but its text could not be parsed
+    InvalidField { field: DetailField, raw: String },
+}
+
+impl Display for DetailsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingField(field) => write!(f, "Missing field: {field}"),
+            Self::InvalidField { field, raw } => write!(f, "Invalid {field}: {raw}"),
+        }
+    }
+}
+
+impl std::error::Error for DetailsError {}
+
+impl DetailsError {
+    /// Converts the error into a [`Diagnostic`] for the given course, or `None` for a
+    /// free-text field, which is never invalid
+    pub fn into_diagnostic(self, source: &DiagnosticSource) -> Option<Diagnostic> {
+        let (category, raw) = match self {
+            Self::MissingField(field) => (DiagnosticCategory::MissingField, field.to_string()),
+            Self::InvalidField { field, raw } => (field.invalid_category()?, raw),
+        };
+
+        Some(Diagnostic {
+            source: source.clone(),
+            category,
+            raw,
+        })
+    }
+}
+
+/// A row of the schedule table on the courseDetails page
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleRow {
+    /// Lecture/section code (e.g., "Lec 1", "A")
+    pub section: String,
+    /// Days the meeting occurs
+    pub days: Days,
+    /// Time range for the meeting
+    pub time: Option<TimeRange>,
+    /// Building and room (e.g., "GHC 4401")
+    pub bldg_room: String,
+    /// Campus of the meeting
+    pub location: String,
+    /// Names of the instructors
+    pub instructors: Vec<String>,
+}
+
+/// Everything parsed from a courseDetails page
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseDetails {
+    /// The course's metadata, with defaults for fields that could not be parsed
+    pub metadata: CourseMetadata,
+    /// The meetings listed in the schedule table
+    pub schedule: Vec<ScheduleRow>,
+    /// Errors for fields that could not be parsed
+    pub errors: Vec<DetailsError>,
+}
+
+/// Normalizes a field label for comparison
+///
+/// # Arguments
+/// * `text` - The label text as it appears on the page
+///
+/// # Returns
+/// The label in lowercase, with whitespace collapsed and trailing colons removed
+fn normalize_label(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(':')
+        .trim()
+        .to_lowercase()
+}
+
+/// Gets the trimmed text content of an element
+fn element_text(element: ElementRef) -> String {
+    element.text().collect::<String>().trim().to_owned()
+}
+
+/// Finds the element following a label, e.g. the `dd` after a `dt` or the `p` after a heading
+///
+/// # Arguments
+/// * `document` - The [`Html`] document to search
+/// * `field` - The field whose label to look for
+///
+/// # Returns
+/// The first element sibling after the label, or a [`DetailsError::MissingField`]
+fn find_field(document: &Html, field: DetailField) -> Result<ElementRef<'_>, DetailsError> {
+    let selector: &Selector = if field == DetailField::Description {
+        &HEADINGS
+    } else {
+        &DT
+    };
+
+    document
+        .select(selector)
+        .filter(|label| normalize_label(&element_text(*label)).starts_with(field.label()))
+        .find_map(|label| label.next_siblings().find_map(ElementRef::wrap))
+        .ok_or(DetailsError::MissingField(field))
+}
+
+/// Gets the text of an optional free-text field, where "None" means no value
+fn parse_text_field(document: &Html, field: DetailField) -> Result<Option<String>, DetailsError> {
+    find_field(document, field).map(|element| clean_text(&element_text(element)))
+}
+
+/// Parses a field whose text is converted with [`FromStr`]
+fn parse_struct_field<T: FromStr>(document: &Html, field: DetailField) -> Result<T, DetailsError> {
+    let raw = element_text(find_field(document, field)?);
+
+    raw.parse()
+        .map_err(|_| DetailsError::InvalidField { field, raw })
+}
+
+/// Parses the prerequisites field, keeping text that doesn't match the grammar as
+/// [`Expr::Text`](models::requisite::Expr::Text) so it can still be shown
+///
+/// # Arguments
+/// * `document` - The [`Html`] document to parse
+/// * `errors` - Collects the field if it is missing or could not be fully parsed
+///
+/// # Returns
+/// The parsed [`Prerequisites`]
+fn parse_prerequisites(document: &Html, errors: &mut Vec<DetailsError>) -> Prerequisites {
+    let field = DetailField::Prerequisites;
+    let raw = match find_field(document, field) {
+        Ok(element) => element_text(element),
+        Err(e) => {
+            errors.push(e);
+            return Prerequisites::default();
+        }
+    };
+
+    let (prerequisites, error) = Prerequisites::parse_or_raw(&raw);
+    if error.is_some() {
+        errors.push(DetailsError::InvalidField { field, raw });
+    }
+    prerequisites
+}
+
+/// Parses whether special permission is required to take the course
+///
+/// # Arguments
+/// * `document` - The [`Html`] document to parse
+///
+/// # Returns
+/// `true` for "Yes" and `false` for "No", or a [`DetailsError`]
+pub fn parse_special_permission(document: &Html) -> Result<bool, DetailsError> {
+    let field = DetailField::SpecialPermission;
+    let raw = element_text(find_field(document, field)?);
+
+    match raw.to_lowercase().as_str() {
+        "yes" => Ok(true),
+        "no" => Ok(false),
+        _ => Err(DetailsError::InvalidField { field, raw }),
+    }
+}
+
+/// Parses the related URLs from the full HTML document
+///
+/// # Arguments
+/// * `document` - The [`Html`] document to parse
+///
+/// # Returns
+/// A vector of related URLs as strings
+pub fn parse_related_urls(document: &Html) -> Vec<String> {
+    document
+        .select(&RELATED_URLS)
+        .filter_map(|link| link.value().attr("href"))
+        .map(|href| href.to_owned())
+        .collect()
+}
+
+/// Finds the first table whose headers include all of the given labels
+fn find_table<'a>(document: &'a Html, headers: &[&str]) -> Option<ElementRef<'a>> {
+    document.select(&TABLE).find(|table| {
+        let table_headers: Vec<String> = table
+            .select(&TH)
+            .map(|th| normalize_label(&element_text(th)))
+            .collect();
+
+        headers
+            .iter()
+            .all(|header| table_headers.iter().any(|h| h.starts_with(header)))
+    })
+}
+
+/// Parses the schedule table from the full HTML document.
+///
+/// Rows for additional meetings leave the section cell empty, so they inherit the section of
+/// the row above them. A row whose days or times can't be parsed is skipped and recorded as a
+/// diagnostic, keeping the rest of the schedule.
+///
+/// # Arguments
+/// * `document` - The [`Html`] document to parse
+/// * `source` - The course the document belongs to, for diagnostics
+/// * `diagnostics` - Collects rows that could not be parsed
+///
+/// # Returns
+/// A vector of [`ScheduleRow`]s, or a [`DetailsError`] if the table is missing
+pub fn parse_schedule(
+    document: &Html,
+    source: &DiagnosticSource,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Vec<ScheduleRow>, DetailsError> {
+    let field = DetailField::Schedule;
+    let table =
+        find_table(document, &["days", "begin", "end"]).ok_or(DetailsError::MissingField(field))?;
+
+    // Map each column to its position, since the table's column order isn't guaranteed
+    let columns: HashMap<String, usize> = table
+        .select(&TH)
+        .enumerate()
+        .map(|(i, th)| (normalize_label(&element_text(th)), i))
+        .collect();
+    let column = |prefix: &str| {
+        columns
+            .iter()
+            .find(|(label, _)| label.starts_with(prefix))
+            .map(|(_, &i)| i)
+    };
+
+    let section_col = column("lec").or_else(|| column("sec"));
+    let days_col = column("days");
+    let begin_col = column("begin");
+    let end_col = column("end");
+    let room_col = column("bldg");
+    let location_col = column("location");
+    let instructors_col = column("instructor");
+
+    let mut rows = Vec::new();
+    let mut section = String::new();
+
+    for row in table.select(&TR) {
+        let cells: Vec<String> = row.select(&TD).map(element_text).collect();
+        if cells.is_empty() {
+            continue;
+        }
+
+        let cell = |col: Option<usize>| col.and_then(|i| cells.get(i)).cloned().unwrap_or_default();
+
+        let row_section = cell(section_col);
+        if !row_section.is_empty() {
+            section = row_section;
+        }
+
+        let days = cell(days_col);
+        let (begin, end) = (cell(begin_col), cell(end_col));
+
+        let parsed = Days::from_str(&days).ok().and_then(|days| match days {
+            Days::TBA => Some((days, None)),
+            Days::Days(_) => TimeRange::from_strings(&begin, &end).map(|time| (days, Some(time))),
+        });
+        let Some((days, time)) = parsed else {
+            diagnostics.push(Diagnostic {
+                source: source.clone(),
+                category: DiagnosticCategory::InvalidSchedule,
+                raw: cells.join(" | "),
+            });
+            continue;
+        };
+
+        rows.push(ScheduleRow {
+            section: section.clone(),
+            days,
+            time,
+            bldg_room: cell(room_col),
+            location: cell(location_col),
+            instructors: parse_instructors(&cell(instructors_col)),
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Parses the reservations from the full HTML document
+///
+/// # Arguments
+/// * `document` - The [`Html`] document to parse
+/// * `source` - The course the document belongs to, for diagnostics
+/// * `diagnostics` - Collects restrictions that could not be parsed
+///
+/// # Returns
+/// A vector of [`Reservation`] objects, each containing a section and its associated [`Restriction`]s
+pub fn parse_reservations(
+    document: &Html,
+    source: &DiagnosticSource,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<Reservation> {
+    let mut reservations_map: HashMap<String, Vec<Restriction>> = HashMap::new();
+
+    // The reservations table is the one with "Section" and "Restriction" headers
+    if let Some(table) = find_table(document, &["section", "restriction"]) {
+        for row in table.select(&TR) {
+            let cells: Vec<_> = row.select(&TD).collect();
+
+            if cells.len() >= 2 {
+                let section = element_text(cells[0]);
+                let restriction_text = element_text(cells[1]);
+
+                if !section.is_empty() && !restriction_text.is_empty() {
+                    let restriction =
+                        Restriction::from_str(&restriction_text).unwrap_or_else(|_| {
+                            diagnostics.push(Diagnostic {
+                                source: source.clone(),
+                                category: DiagnosticCategory::InvalidRestriction,
+                                raw: restriction_text.clone(),
+                            });
+                            Restriction {
+                                student_type: None,
+                                restriction_type: None,
+                            }
+                        });
+
+                    reservations_map
+                        .entry(section)
+                        .or_default()
+                        .push(restriction);
+                }
+            }
+        }
+    }
+
+    reservations_map
+        .into_iter()
+        .map(|(section, restrictions)| Reservation {
+            section,
+            restrictions,
+        })
+        .collect()
+}
+
+/// Unwraps a parsed field, falling back to its default and recording the error
+fn or_default<T: Default>(result: Result<T, DetailsError>, errors: &mut Vec<DetailsError>) -> T {
+    result.unwrap_or_else(|e| {
+        errors.push(e);
+        T::default()
+    })
+}
+
+/// Parses every field of a courseDetails page.
+///
+/// Fields are found by their labels rather than their positions on the page. A field that is
+/// missing or can't be parsed falls back to its default and is reported in `errors`.
+///
+/// # Arguments
+/// * `html` - The raw HTML of the courseDetails page
+/// * `source` - The course the page belongs to, for diagnostics
+/// * `diagnostics` - Collects schedule rows and restrictions that could not be parsed
+///
+/// # Returns
+/// The parsed [`CourseDetails`]
+pub fn parse_details_page(
+    html: &str,
+    source: &DiagnosticSource,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> CourseDetails {
+    let document = Html::parse_document(html);
+    let mut errors = Vec::new();
+
+    let special_permission = or_default(parse_special_permission(&document), &mut errors);
+    let description = or_default(
+        parse_text_field(&document, DetailField::Description),
+        &mut errors,
+    );
+    let prerequisites = parse_prerequisites(&document, &mut errors);
+    let corequisites = or_default(
+        parse_struct_field(&document, DetailField::Corequisites),
+        &mut errors,
+    );
+    let crosslisted = or_default(
+        parse_struct_field(&document, DetailField::Crosslisted),
+        &mut errors,
+    );
+    let notes = or_default(parse_text_field(&document, DetailField::Notes), &mut errors);
+    let schedule = or_default(parse_schedule(&document, source, diagnostics), &mut errors);
+
+    let metadata = CourseMetadata {
+        related_urls: parse_related_urls(&document),
+        special_permission,
+        description,
+        prerequisites,
+        corequisites,
+        crosslisted,
+        notes,
+        reservations: parse_reservations(&document, source, diagnostics),
+    };
+
+    CourseDetails {
+        metadata,
+        schedule,
+        errors,
+    }
+}
+
+/// Parses a courseDetails page into the metadata for a course
+///
+/// # Arguments
+/// * `html` - The raw HTML of the courseDetails page
+/// * `course` - The course the page belongs to
+///
+/// # Returns
+/// The [`CourseObject`] with its `metadata` set, along with any [`Diagnostic`]s for fields
+/// that could not be parsed
+pub fn parse_course_details(html: &str, course: CourseEntry) -> (CourseObject, Vec<Diagnostic>) {
+    let source = DiagnosticSource::Course {
+        number: course.number.clone(),
+        season: course.season,
+        year: course.year,
+    };
+    let mut diagnostics = Vec::new();
+
+    let details = parse_details_page(html, &source, &mut diagnostics);
+    diagnostics.extend(
+        details
+            .errors
+            .into_iter()
+            .filter_map(|e| e.into_diagnostic(&source)),
+    );
+
+    // Sections on the details page should match the ones in the schedule of classes
+    for row in &details.schedule {
+        if !course
+            .components
+            .iter()
+            .any(|component| component.code == row.section)
+        {
+            diagnostics.push(Diagnostic {
+                source: source.clone(),
+                category: DiagnosticCategory::UnknownSection,
+                raw: row.section.clone(),
+            });
+        }
+    }
+
+    let course_obj = CourseObject {
+        course,
+        metadata: Some(details.metadata),
+    };
+    (course_obj, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::{days::DaySet, requisite::Expr};
+
+    const DETAILS: &str = r#"<html><body><div>
+<div><table>
+<thead><tr><th>Lec/Sec</th><th>Days</th><th>Begin</th><th>End</th><th>Bldg/Room</th><th>Location</th><th>Instructor(s)</th></tr></thead>
+<tbody>
+<tr><td>Lec 1</td><td>TR</td><td>09:30AM</td><td>10:50AM</td><td>GHC 4401</td><td>Pittsburgh, Pennsylvania</td><td>Cervesato, Kaynar</td></tr>
+<tr><td></td><td>F</td><td>09:00AM</td><td>09:50AM</td><td>WEH 5403</td><td>Pittsburgh, Pennsylvania</td><td>Cervesato</td></tr>
+<tr><td>A</td><td>TBA</td><td></td><td></td><td>TBA</td><td>Pittsburgh, Pennsylvania</td><td>Instructor TBA</td></tr>
+</tbody>
+</table></div>
+<div><dl><dt>Notes</dt><dd>None</dd></dl></div>
+<div><dl><dt>Special Permission
+    Required:</dt><dd> Yes </dd></dl></div>
+<div><h4>Description:</h4><p>Write  correct programs.</p></div>
+<div><dl><dt>Prerequisites</dt><dd>15112 or 15110</dd></dl></div>
+<div><dl><dt>Corequisites</dt><dd>21127</dd></dl></div>
+<div><dl><dt>Cross-Listed Courses</dt><dd>None</dd></dl></div>
+<div><table>
+<tr><th>Section</th><th>Restriction</th></tr>
+<tr><td>A</td><td>Reserved for Undergraduate Students</td></tr>
+</table></div>
+</div></body></html>"#;
+
+    fn source() -> DiagnosticSource {
+        DiagnosticSource::Course {
+            number: "15122".into(),
+            season: models::syllabus_data::Season::Fall,
+            year: models::syllabus_data::Year(2025),
+        }
+    }
+
+    #[test]
+    fn test_parse_details_by_label() {
+        let details = parse_details_page(DETAILS, &source(), &mut Vec::new());
+        let metadata = &details.metadata;
+
+        assert!(details.errors.is_empty(), "{:?}", details.errors);
+        assert!(metadata.special_permission);
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("Write correct programs.")
+        );
+        assert!(matches!(
+            metadata.prerequisites.clone().into_inner(),
+            Some(Expr::Or(_, _))
+        ));
+        assert_eq!(metadata.corequisites.to_vec(), vec!["21127"]);
+        assert!(metadata.crosslisted.is_empty());
+        assert_eq!(metadata.notes, None);
+        assert_eq!(metadata.reservations.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        let document = Html::parse_document(DETAILS);
+        let schedule = parse_schedule(&document, &source(), &mut Vec::new()).unwrap();
+
+        assert_eq!(schedule.len(), 3);
+        assert_eq!(schedule[0].section, "Lec 1");
+        assert_eq!(schedule[0].instructors, vec!["Cervesato", "Kaynar"]);
+        assert_eq!(
+            schedule[0].days,
+            Days::Days(DaySet::TUESDAY | DaySet::THURSDAY)
+        );
+
+        // Additional meetings inherit the section above them
+        assert_eq!(schedule[1].section, "Lec 1");
+        assert_eq!(schedule[1].bldg_room, "WEH 5403");
+        assert_eq!(
+            schedule[1].time,
+            TimeRange::from_strings("09:00AM", "09:50AM")
+        );
+
+        assert_eq!(schedule[2].days, Days::TBA);
+        assert_eq!(schedule[2].time, None);
+    }
+
+    #[test]
+    fn test_parse_schedule_skips_bad_rows() {
+        let html = r#"<html><body><table>
+<tr><th>Lec/Sec</th><th>Days</th><th>Begin</th><th>End</th></tr>
+<tr><td>Lec 1</td><td>TR</td><td>09:30AM</td><td>10:50AM</td></tr>
+<tr><td>A</td><td>F</td><td>noon</td><td>09:50AM</td></tr>
+<tr><td>B</td><td>MW</td><td>09:00AM</td><td></td></tr>
+<tr><td>C</td><td>TBA</td><td></td><td></td></tr>
+</table></body></html>"#;
+        let mut diagnostics = Vec::new();
+
+        let document = Html::parse_document(html);
+        let schedule = parse_schedule(&document, &source(), &mut diagnostics).unwrap();
+
+        let sections: Vec<_> = schedule.iter().map(|row| row.section.as_str()).collect();
+        assert_eq!(sections, vec!["Lec 1", "C"]);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].category, DiagnosticCategory::InvalidSchedule);
+        assert_eq!(diagnostics[0].raw, "A | F | noon | 09:50AM");
+    }
+
+    #[test]
+    fn test_parse_details_errors() {
+        let html = r#"<html><body>
+<dl><dt>Special Permission Required:</dt><dd>Maybe</dd></dl>
+<dl><dt>Prerequisites</dt><dd>15112 or</dd></dl>
+</body></html>"#;
+
+        let details = parse_details_page(html, &source(), &mut Vec::new());
+
+        assert!(!details.metadata.special_permission);
+
+        // Unparseable prerequisites are kept as written rather than dropped
+        assert_eq!(
+            details.metadata.prerequisites.clone().into_inner(),
+            Some(Expr::Text("15112 or".to_owned()))
+        );
+        assert!(details.errors.contains(&DetailsError::InvalidField {
+            field: DetailField::Prerequisites,
+            raw: "15112 or".to_owned(),
+        }));
+        assert!(details.errors.contains(&DetailsError::InvalidField {
+            field: DetailField::SpecialPermission,
+            raw: "Maybe".to_owned(),
+        }));
+        assert!(
+            details
+                .errors
+                .contains(&DetailsError::MissingField(DetailField::Schedule))
+        );
+        assert!(
+            details
+                .errors
+                .contains(&DetailsError::MissingField(DetailField::Notes))
+        );
+    }
+}
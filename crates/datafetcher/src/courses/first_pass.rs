@@ -2,6 +2,27 @@ use crate::courses::line::Line;
 use models::units::Units;
 use std::str::FromStr;
 
+/// Number of header rows at the top of current SOC files
+pub const SOC_HEADER_ROWS: usize = 11;
+
+/// Counts the header rows at the top of a SOC file
+///
+/// Every row of the schedule starts with a tab while header rows don't, so the header ends at
+/// the first row starting with a tab. Archived files from past semesters have more or fewer
+/// header rows than current ones.
+///
+/// # Arguments
+/// * `input` - A raw string slice containing the full input file contents
+///
+/// # Returns
+/// The number of header rows, or [`SOC_HEADER_ROWS`] if the file has no schedule rows
+pub fn header_rows(input: &str) -> usize {
+    input
+        .lines()
+        .position(|line| line.starts_with('\t'))
+        .unwrap_or(SOC_HEADER_ROWS)
+}
+
 /// Discard the header rows in the SOC file before parsing
 ///
 /// # Arguments
@@ -10,7 +31,7 @@ use std::str::FromStr;
 /// # Returns
 /// An iterator over the cleaned, line-based content of the SOC input
 fn preprocess_lines(input: &str) -> impl Iterator<Item = &str> {
-    input.lines().skip(11).map(str::trim_end)
+    input.lines().skip(header_rows(input)).map(str::trim_end)
 }
 
 /// Determines whether a string is a valid course number.
@@ -19,9 +40,15 @@ fn preprocess_lines(input: &str) -> impl Iterator<Item = &str> {
 /// * `s` - A string slice to check.
 ///
 /// # Returns
-/// `true` if the input is a 5-digit numeric string (e.g., `"15122"`), `false` otherwise.
+/// `true` if the input is a 5-digit numeric string (e.g., `"15122"`), or one with a dash as
+/// older files write them (e.g., `"15-122"`), `false` otherwise.
 fn is_course_number(s: &str) -> bool {
-    s.len() == 5 && s.chars().all(|c| c.is_ascii_digit())
+    let digits = match s.split_once('-') {
+        Some((department, number)) if department.len() == 2 => format!("{department}{number}"),
+        Some(_) => return false,
+        None => s.to_owned(),
+    };
+    digits.len() == 5 && digits.chars().all(|c| c.is_ascii_digit())
 }
 
 /// Determines whether a string looks like a valid section code.
@@ -60,7 +87,7 @@ pub fn parse_line(line: &str) -> Line {
 
         // CourseHeader: number + title only
         [number, title] if is_course_number(number) => Line::CourseHeader {
-            number: number.to_string(),
+            number: number.replace('-', ""),
             title: title.trim().to_string(),
         },
 
@@ -69,13 +96,14 @@ pub fn parse_line(line: &str) -> Line {
             if is_course_number(number) && Units::from_str(units).is_ok() =>
         {
             Line::SecondaryCourseHeader {
-                number: number.to_string(),
+                number: number.replace('-', ""),
                 title: title.trim_end_matches(':').trim().to_string(),
                 units: units.to_string(),
             }
         }
 
-        // PrimaryCourseComponent: starts with units
+        // PrimaryCourseComponent: starts with units. Older files leave out the instructors of
+        // components that don't have any yet.
         [
             units,
             section,
@@ -84,15 +112,16 @@ pub fn parse_line(line: &str) -> Line {
             time_end,
             building_room,
             campus,
-            instructors,
-            ..,
+            rest @ ..,
         ] if Units::from_str(units).is_ok() => Line::PrimaryCourseComponent {
             units: units.to_string(),
             section: section.to_string(),
             days: days.to_string(),
             time_start: time_start.to_string(),
             time_end: time_end.to_string(),
+            bldg_room: building_room.to_string(),
             campus: campus.to_string(),
+            instructors: rest.first().copied().unwrap_or_default().to_string(),
         },
 
         // SecondaryCourseComponent: starts with section
@@ -110,15 +139,19 @@ pub fn parse_line(line: &str) -> Line {
             days: days.to_string(),
             time_start: time_start.to_string(),
             time_end: time_end.to_string(),
+            bldg_room: building_room.to_string(),
             campus: campus.to_string(),
+            instructors: instructors.to_string(),
         },
 
         // AdditionalMeeting: days + times + building + campus
-        [days, time_start, time_end, building_room, campus, ..] => Line::AdditionalMeeting {
+        [days, time_start, time_end, building_room, campus, rest @ ..] => Line::AdditionalMeeting {
             days: days.to_string(),
             time_start: time_start.to_string(),
             time_end: time_end.to_string(),
+            bldg_room: building_room.to_string(),
             campus: campus.to_string(),
+            instructors: rest.first().copied().unwrap_or_default().to_string(),
         },
 
         // AdditionalMeeting with missing campus
@@ -126,17 +159,16 @@ pub fn parse_line(line: &str) -> Line {
             days: days.to_string(),
             time_start: time_start.to_string(),
             time_end: time_end.to_string(),
+            bldg_room: building_room.to_string(),
             campus: "Unknown Location".to_owned(),
+            instructors: String::new(),
         },
 
         // ComponentTitle: short string that doesn't match other formats
         [title] if leading_tabs == 2 => Line::ComponentTitle(title.to_string()),
 
-        // Unknown: matches none of the above, log for diagnostics
-        _ => {
-            println!("Unknown line format: {line}");
-            Line::Unknown(line.to_string())
-        }
+        // Unknown: matches none of the above, reported in the diagnostics
+        _ => Line::Unknown(line.to_string()),
     }
 }
 
@@ -157,7 +189,7 @@ pub fn first_pass(input: &str) -> Vec<Line> {
 
 #[cfg(test)]
 mod test {
-    use crate::courses::first_pass::parse_line;
+    use crate::courses::first_pass::{SOC_HEADER_ROWS, first_pass, header_rows, parse_line};
     use crate::courses::line::Line;
 
     #[test]
@@ -194,7 +226,9 @@ mod test {
                 days: "R".into(),
                 time_start: "12:30PM".into(),
                 time_end: "01:50PM".into(),
+                bldg_room: "MM A14".into(),
                 campus: "Pittsburgh, Pennsylvania".into(),
+                instructors: "Workinger".into(),
             },
             Line::CourseHeader {
                 number: "48104".into(),
@@ -206,14 +240,18 @@ mod test {
                 days: "MW".into(),
                 time_start: "10:00AM".into(),
                 time_end: "10:50AM".into(),
+                bldg_room: "CFA A9".into(),
                 campus: "Pittsburgh, Pennsylvania".into(),
+                instructors: "Holmes".into(),
             },
             Line::SecondaryCourseComponent {
                 section: "A2".into(),
                 days: "MW".into(),
                 time_start: "10:00AM".into(),
                 time_end: "10:50AM".into(),
+                bldg_room: "CFA A9".into(),
                 campus: "Pittsburgh, Pennsylvania".into(),
+                instructors: "Holmes".into(),
             },
             Line::CourseHeader {
                 number: "48214".into(),
@@ -225,14 +263,18 @@ mod test {
                 days: "TBA".into(),
                 time_start: "".into(),
                 time_end: "".into(),
+                bldg_room: "DNM DNM".into(),
                 campus: "Pittsburgh, Pennsylvania".into(),
+                instructors: "Bard".into(),
             },
             Line::SecondaryCourseComponent {
                 section: "A".into(),
                 days: "M".into(),
                 time_start: "10:00AM".into(),
                 time_end: "10:50AM".into(),
+                bldg_room: "MM 303".into(),
                 campus: "Pittsburgh, Pennsylvania".into(),
+                instructors: "Bard".into(),
             },
             Line::SecondaryCourseHeader {
                 number: "48313".into(),
@@ -246,7 +288,9 @@ mod test {
                 days: "TR".into(),
                 time_start: "11:00AM".into(),
                 time_end: "12:20PM".into(),
+                bldg_room: "TBD TBD".into(),
                 campus: "Pittsburgh, Pennsylvania".into(),
+                instructors: "Sindi".into(),
             },
             Line::ComponentTitle("New Pedogogies".into()),
             Line::ComponentTitle("Storycraft".into()),
@@ -255,7 +299,9 @@ mod test {
                 days: "MW".into(),
                 time_start: "11:00AM".into(),
                 time_end: "12:20PM".into(),
+                bldg_room: "TBA".into(),
                 campus: "Pittsburgh, Pennsylvania".into(),
+                instructors: "Stone".into(),
             },
         ];
 
@@ -273,4 +319,39 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_archived_format_drift() {
+        // Older files have fewer header rows, dashed course numbers, CRLF line endings, and no
+        // instructors column for components without instructors
+        let input = "Carnegie Mellon University\r\n\
+                     Semester: Fall 2018\r\n\
+                     \r\n\
+                     \tComputer Science\r\n\
+                     \t15-122\tPrinciples of Imperative Computation\r\n\
+                     \t\t\t10.0\tLec 1\tTR\t09:30AM\t10:50AM\tGHC 4401\tPittsburgh, Pennsylvania\r\n";
+
+        assert_eq!(header_rows(input), 3);
+        assert_eq!(header_rows("no schedule rows"), SOC_HEADER_ROWS);
+        assert_eq!(
+            first_pass(input),
+            vec![
+                Line::Department("Computer Science".into()),
+                Line::CourseHeader {
+                    number: "15122".into(),
+                    title: "Principles of Imperative Computation".into(),
+                },
+                Line::PrimaryCourseComponent {
+                    units: "10.0".into(),
+                    section: "Lec 1".into(),
+                    days: "TR".into(),
+                    time_start: "09:30AM".into(),
+                    time_end: "10:50AM".into(),
+                    bldg_room: "GHC 4401".into(),
+                    campus: "Pittsburgh, Pennsylvania".into(),
+                    instructors: "".into(),
+                },
+            ]
+        );
+    }
 }
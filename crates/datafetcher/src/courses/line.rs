@@ -29,7 +29,10 @@ pub enum Line {
         days: String,       // To become `Days`
         time_start: String, // To become `TimeRange.begin`
         time_end: String,   // To become `TimeRange.end`
+        bldg_room: String,
         campus: String,
+        /// Names of the instructors, separated by commas
+        instructors: String,
     },
     /// Any additional components of a course. These will always share the same number of units
     /// as the `PrimaryCourseComponent` they are associated with.
@@ -38,7 +41,10 @@ pub enum Line {
         days: String,
         time_start: String,
         time_end: String,
+        bldg_room: String,
         campus: String,
+        /// Names of the instructors, separated by commas
+        instructors: String,
     },
     /// An additional meeting time for a `*CourseComponent`. These will always share the same title,
     /// units, and professors as the `*CourseComponent` they are associated with.
@@ -46,7 +52,10 @@ pub enum Line {
         days: String,
         time_start: String,
         time_end: String,
+        bldg_room: String,
         campus: String,
+        /// Names of the instructors, separated by commas
+        instructors: String,
     },
     /// A blank line or whitespace-only
     Empty,
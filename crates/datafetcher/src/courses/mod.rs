@@ -1,3 +1,4 @@
+pub mod details;
 pub mod first_pass;
 pub mod line;
 pub mod second_pass;
@@ -1,21 +1,27 @@
 use crate::courses::line::Line;
 use models::{
-    course_data::{ComponentType, CourseComponent, CourseEntry, Meeting, TimeRange},
+    course_data::{
+        ComponentType, CourseComponent, CourseEntry, Meeting, TimeRange, parse_instructors,
+    },
     syllabus_data::{Season, Year},
     units::Units,
 };
 
-fn parse_meetings(
-    lines: &[Line],
+fn parse_meetings<'a>(
+    lines: &'a [Line],
     days: String,
     time_start: String,
     time_end: String,
+    bldg_room: String,
     campus: String,
-) -> (Vec<Meeting>, &[Line]) {
+    instructors: &str,
+) -> (Vec<Meeting>, &'a [Line]) {
     let mut meetings = vec![Meeting {
         days: days.into(),
         time: TimeRange::from_strings(&time_start, &time_end),
+        bldg_room,
         campus,
+        instructors: parse_instructors(instructors),
     }];
 
     let mut remaining = lines;
@@ -25,7 +31,9 @@ fn parse_meetings(
             days,
             time_start,
             time_end,
+            bldg_room,
             campus,
+            instructors,
         },
         rest @ ..,
     ] = remaining
@@ -33,7 +41,9 @@ fn parse_meetings(
         meetings.push(Meeting {
             days: days.clone().into(),
             time: TimeRange::from_strings(time_start, time_end),
+            bldg_room: bldg_room.to_owned(),
             campus: campus.to_owned(),
+            instructors: parse_instructors(instructors),
         });
 
         remaining = rest;
@@ -54,7 +64,9 @@ fn parse_component(
                 days,
                 time_start,
                 time_end,
+                bldg_room,
                 campus,
+                instructors,
             },
             rest @ ..,
         ] => {
@@ -63,7 +75,9 @@ fn parse_component(
                 days.clone(),
                 time_start.clone(),
                 time_end.clone(),
+                bldg_room.clone(),
                 campus.clone(),
+                instructors,
             );
 
             (
@@ -84,7 +98,9 @@ fn parse_component(
                 days,
                 time_start,
                 time_end,
+                bldg_room,
                 campus,
+                instructors,
             },
             rest @ ..,
         ] => {
@@ -93,7 +109,9 @@ fn parse_component(
                 days.clone(),
                 time_start.clone(),
                 time_end.clone(),
+                bldg_room.clone(),
                 campus.clone(),
+                instructors,
             );
 
             (
@@ -175,7 +193,12 @@ fn parse_components(
     (components, first_units, remaining)
 }
 
-fn parse_course(lines: &[Line], season: Season, year: Year) -> Option<(CourseEntry, &[Line])> {
+fn parse_course<'a>(
+    lines: &'a [Line],
+    season: Season,
+    year: Year,
+    department: Option<&str>,
+) -> Option<(CourseEntry, &'a [Line])> {
     let (number, title, header_units, rest) = match lines {
         [Line::CourseHeader { number, title }, rest @ ..] => {
             (number.clone(), title.clone(), None, rest)
@@ -209,6 +232,7 @@ fn parse_course(lines: &[Line], season: Season, year: Year) -> Option<(CourseEnt
             components,
             season,
             year,
+            department: department.map(str::to_owned),
         },
         remaining,
     ))
@@ -217,9 +241,14 @@ fn parse_course(lines: &[Line], season: Season, year: Year) -> Option<(CourseEnt
 fn parse_courses(lines: &[Line], season: Season, year: Year) -> Vec<CourseEntry> {
     let mut entries = Vec::new();
     let mut remaining = lines;
+    // Courses are listed under the department that offers them
+    let mut department = None;
 
     while !remaining.is_empty() {
-        if let Some((course, rest)) = parse_course(remaining, season, year) {
+        if let [Line::Department(name), rest @ ..] = remaining {
+            department = Some(name.as_str());
+            remaining = rest;
+        } else if let Some((course, rest)) = parse_course(remaining, season, year, department) {
             entries.push(course);
             remaining = rest;
         } else {
@@ -289,6 +318,7 @@ mod test {
                 units: Units::new(3.0),
                 season: Season::Fall,
                 year: Year(2025),
+                department: Some("Architecture".to_owned()),
                 components: vec![CourseComponent {
                     title: "First Year Seminar: Architecture Edition".to_string(),
                     component_type: ComponentType::Section,
@@ -296,7 +326,9 @@ mod test {
                     meetings: vec![Meeting {
                         days: Days::Days(DaySet::THURSDAY),
                         time: Some(TimeRange::from_strings("12:30PM", "01:50PM").unwrap()),
+                        bldg_room: "MM A14".to_owned(),
                         campus: "Pittsburgh, Pennsylvania".to_owned(),
+                        instructors: vec!["Workinger".to_owned()],
                     }],
                 }],
             },
@@ -305,6 +337,7 @@ mod test {
                 units: Units::VAR,
                 season: Season::Fall,
                 year: Year(2025),
+                department: Some("Architecture".to_owned()),
                 components: vec![
                     CourseComponent {
                         title: "Shop Skills".to_string(),
@@ -313,7 +346,9 @@ mod test {
                         meetings: vec![Meeting {
                             days: Days::Days(DaySet::MONDAY | DaySet::WEDNESDAY),
                             time: Some(TimeRange::from_strings("10:00AM", "10:50AM").unwrap()),
+                            bldg_room: "CFA A9".to_owned(),
                             campus: "Pittsburgh, Pennsylvania".to_owned(),
+                            instructors: vec!["Holmes".to_owned()],
                         }],
                     },
                     CourseComponent {
@@ -323,7 +358,9 @@ mod test {
                         meetings: vec![Meeting {
                             days: Days::Days(DaySet::MONDAY | DaySet::WEDNESDAY),
                             time: Some(TimeRange::from_strings("10:00AM", "10:50AM").unwrap()),
+                            bldg_room: "CFA A9".to_owned(),
                             campus: "Pittsburgh, Pennsylvania".to_owned(),
+                            instructors: vec!["Holmes".to_owned()],
                         }],
                     },
                 ],
@@ -333,6 +370,7 @@ mod test {
                 units: Units::new(9.0),
                 season: Season::Fall,
                 year: Year(2025),
+                department: Some("Architecture".to_owned()),
                 components: vec![
                     CourseComponent {
                         title: "Generative Modeling".to_string(),
@@ -341,7 +379,9 @@ mod test {
                         meetings: vec![Meeting {
                             days: Days::TBA,
                             time: None,
+                            bldg_room: "DNM DNM".to_owned(),
                             campus: "Pittsburgh, Pennsylvania".to_owned(),
+                            instructors: vec!["Bard".to_owned()],
                         }],
                     },
                     CourseComponent {
@@ -351,7 +391,9 @@ mod test {
                         meetings: vec![Meeting {
                             days: Days::Days(DaySet::MONDAY),
                             time: Some(TimeRange::from_strings("10:00AM", "10:50AM").unwrap()),
+                            bldg_room: "MM 303".to_owned(),
                             campus: "Pittsburgh, Pennsylvania".to_owned(),
+                            instructors: vec!["Bard".to_owned()],
                         }],
                     },
                 ],
@@ -361,6 +403,7 @@ mod test {
                 units: Units::new(9.0),
                 season: Season::Fall,
                 year: Year(2025),
+                department: Some("Architecture".to_owned()),
                 components: vec![
                     CourseComponent {
                         title: "New Pedogogies: Unreasonable Architecture".to_string(),
@@ -369,7 +412,9 @@ mod test {
                         meetings: vec![Meeting {
                             days: Days::Days(DaySet::TUESDAY | DaySet::THURSDAY),
                             time: Some(TimeRange::from_strings("11:00AM", "12:20PM").unwrap()),
+                            bldg_room: "TBD TBD".to_owned(),
                             campus: "Pittsburgh, Pennsylvania".to_owned(),
+                            instructors: vec!["Sindi".to_owned()],
                         }],
                     },
                     CourseComponent {
@@ -379,7 +424,9 @@ mod test {
                         meetings: vec![Meeting {
                             days: Days::Days(DaySet::MONDAY | DaySet::WEDNESDAY),
                             time: Some(TimeRange::from_strings("11:00AM", "12:20PM").unwrap()),
+                            bldg_room: "TBA".to_owned(),
                             campus: "Pittsburgh, Pennsylvania".to_owned(),
+                            instructors: vec!["Stone".to_owned()],
                         }],
                     },
                 ],
@@ -0,0 +1,217 @@
+use crate::courses::line::Line;
+use models::{
+    course_data::CourseNumber,
+    syllabus_data::{Season, Year},
+};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufWriter, Result as IoResult},
+    path::Path,
+};
+
+/// Kind of problem found while parsing scraped data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticCategory {
+    /// A SOC line that matches none of the known line formats
+    UnknownLine,
+    /// A reservation restriction that could not be parsed
+    InvalidRestriction,
+    /// A prerequisites string that could not be parsed
+    InvalidPrerequisites,
+    /// A corequisites string that could not be parsed
+    InvalidCorequisites,
+    /// A cross-listed courses string that could not be parsed
+    InvalidCrosslisted,
+    /// A special permission value other than "Yes" or "No"
+    InvalidSpecialPermission,
+    /// A schedule table row that could not be parsed
+    InvalidSchedule,
+    /// A labeled field that is missing from a course's details page
+    MissingField,
+    /// A section on a course's details page that isn't in the schedule of classes
+    UnknownSection,
+}
+
+/// Where a diagnostic was found
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DiagnosticSource {
+    /// A line in a semester's SOC file, numbered from 1
+    SocLine {
+        season: Season,
+        year: Year,
+        line_number: usize,
+    },
+    /// A course's details page
+    Course {
+        number: CourseNumber,
+        season: Season,
+        year: Year,
+    },
+}
+
+/// A single problem found while parsing scraped data
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    /// Where the problem was found
+    pub source: DiagnosticSource,
+    /// What kind of problem it is
+    pub category: DiagnosticCategory,
+    /// The raw text that could not be parsed
+    pub raw: String,
+}
+
+/// All problems found during a scrape
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Diagnostics {
+    /// Number of diagnostics in each category
+    pub counts: BTreeMap<DiagnosticCategory, usize>,
+    /// The diagnostics themselves, in the order they were found
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a diagnostic
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        *self.counts.entry(diagnostic.category).or_default() += 1;
+        self.diagnostics.push(diagnostic);
+    }
+
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Writes the diagnostics as a pretty-printed JSON report
+    ///
+    /// # Arguments
+    /// * `path` - The file to write the report to
+    pub fn write_report(&self, path: impl AsRef<Path>) -> IoResult<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+impl Extend<Diagnostic> for Diagnostics {
+    fn extend<I: IntoIterator<Item = Diagnostic>>(&mut self, iter: I) {
+        for diagnostic in iter {
+            self.push(diagnostic);
+        }
+    }
+}
+
+impl FromIterator<Diagnostic> for Diagnostics {
+    fn from_iter<I: IntoIterator<Item = Diagnostic>>(iter: I) -> Self {
+        let mut diagnostics = Self::new();
+        diagnostics.extend(iter);
+        diagnostics
+    }
+}
+
+/// Collects a diagnostic for every line the first pass could not classify
+///
+/// # Arguments
+/// * `lines` - The output of [`first_pass`](crate::courses::first_pass::first_pass)
+/// * `header_rows` - The number of header rows the first pass skipped, from
+///   [`header_rows`](crate::courses::first_pass::header_rows)
+/// * `season` - The season of the SOC file
+/// * `year` - The year of the SOC file
+///
+/// # Returns
+/// A vector of [`Diagnostic`]s with the line numbers of the original SOC file
+pub fn unknown_line_diagnostics(
+    lines: &[Line],
+    header_rows: usize,
+    season: Season,
+    year: Year,
+) -> Vec<Diagnostic> {
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| match line {
+            Line::Unknown(raw) => Some(Diagnostic {
+                source: DiagnosticSource::SocLine {
+                    season,
+                    year,
+                    line_number: header_rows + i + 1,
+                },
+                category: DiagnosticCategory::UnknownLine,
+                raw: raw.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::courses::first_pass::{SOC_HEADER_ROWS, first_pass};
+
+    #[test]
+    fn test_unknown_line_diagnostics() {
+        let header = "\n".repeat(SOC_HEADER_ROWS);
+        let input = format!("{header}\t48025\tFirst Year Seminar\n???\n");
+
+        let diagnostics = unknown_line_diagnostics(
+            &first_pass(&input),
+            SOC_HEADER_ROWS,
+            Season::Fall,
+            Year(2025),
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].raw, "???");
+        assert_eq!(diagnostics[0].category, DiagnosticCategory::UnknownLine);
+        assert_eq!(
+            diagnostics[0].source,
+            DiagnosticSource::SocLine {
+                season: Season::Fall,
+                year: Year(2025),
+                line_number: 13,
+            }
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_counts() {
+        let diagnostic = |category| Diagnostic {
+            source: DiagnosticSource::Course {
+                number: "15122".into(),
+                season: Season::Fall,
+                year: Year(2025),
+            },
+            category,
+            raw: String::new(),
+        };
+
+        let diagnostics: Diagnostics = [
+            diagnostic(DiagnosticCategory::InvalidPrerequisites),
+            diagnostic(DiagnosticCategory::InvalidRestriction),
+            diagnostic(DiagnosticCategory::InvalidPrerequisites),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            diagnostics.counts[&DiagnosticCategory::InvalidPrerequisites],
+            2
+        );
+        assert_eq!(
+            diagnostics.counts[&DiagnosticCategory::InvalidRestriction],
+            1
+        );
+    }
+}
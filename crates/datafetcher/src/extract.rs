@@ -0,0 +1,253 @@
+use quick_xml::{Reader, events::Event};
+use scraper::{Html, Node};
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    io::{Cursor, Read},
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+use zip::ZipArchive;
+
+/// File formats syllabi are published in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyllabusFormat {
+    Pdf,
+    Docx,
+    Html,
+    Text,
+    /// Anything we can't extract text from, like images or legacy Word documents
+    Unknown,
+}
+
+impl SyllabusFormat {
+    /// Detects the format of a file from its contents, since Canvas download URLs don't carry
+    /// a reliable file extension
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(b"%PDF") {
+            return Self::Pdf;
+        }
+
+        if bytes.starts_with(b"PK\x03\x04") {
+            let is_docx = ZipArchive::new(Cursor::new(bytes))
+                .is_ok_and(|mut archive| archive.by_name("word/document.xml").is_ok());
+            return if is_docx { Self::Docx } else { Self::Unknown };
+        }
+
+        let Ok(text) = std::str::from_utf8(bytes) else {
+            return Self::Unknown;
+        };
+        if text.contains('\0') {
+            return Self::Unknown;
+        }
+
+        let head = text
+            .trim_start_matches('\u{feff}')
+            .trim_start()
+            .chars()
+            .take(1024)
+            .collect::<String>()
+            .to_lowercase();
+        if head.starts_with("<!doctype html") || head.contains("<html") {
+            Self::Html
+        } else {
+            Self::Text
+        }
+    }
+
+    /// MIME type the format is served with
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Pdf => "application/pdf",
+            Self::Docx => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            Self::Html => "text/html; charset=utf-8",
+            Self::Text => "text/plain; charset=utf-8",
+            Self::Unknown => "application/octet-stream",
+        }
+    }
+}
+
+/// Error returned when text cannot be extracted from a syllabus
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractError {
+    pub format: SyllabusFormat,
+    pub message: String,
+}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Failed to extract {:?} text: {}",
+            self.format, self.message
+        )
+    }
+}
+
+impl std::error::Error for ExtractError {}
+
+/// Collapses runs of whitespace, so text extracted from different formats indexes the same way
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Extracts the text of a PDF
+fn pdf_text(bytes: &[u8]) -> Result<String, String> {
+    // pdf-extract panics on some malformed files instead of returning an error
+    catch_unwind(AssertUnwindSafe(|| {
+        pdf_extract::extract_text_from_mem(bytes)
+    }))
+    .map_err(|_| "PDF parser panicked".to_owned())?
+    .map_err(|e| e.to_string())
+}
+
+/// Extracts the text runs of a DOCX document, one paragraph per line
+fn docx_text(bytes: &[u8]) -> Result<String, String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|e| e.to_string())?
+        .read_to_string(&mut xml)
+        .map_err(|e| e.to_string())?;
+
+    let mut reader = Reader::from_str(&xml);
+    let mut text = String::new();
+    let mut in_text_run = false;
+
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) if e.name().as_ref() == b"w:t" => in_text_run = true,
+            Event::End(e) if e.name().as_ref() == b"w:t" => in_text_run = false,
+            Event::End(e) if e.name().as_ref() == b"w:p" => text.push('\n'),
+            Event::Empty(e) if matches!(e.name().as_ref(), b"w:tab" | b"w:br") => text.push(' '),
+            Event::Text(t) if in_text_run => {
+                text.push_str(&t.unescape().map_err(|e| e.to_string())?);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(text)
+}
+
+/// Extracts the visible text of an HTML page, skipping scripts and styles
+fn html_text(html: &str) -> String {
+    let document = Html::parse_document(html);
+
+    document
+        .tree
+        .nodes()
+        .filter_map(|node| {
+            let Node::Text(text) = node.value() else {
+                return None;
+            };
+            let hidden = node
+                .parent()
+                .and_then(|parent| parent.value().as_element())
+                .is_some_and(|element| matches!(element.name(), "script" | "style"));
+            (!hidden).then_some(&**text)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Extracts the plain text of a syllabus so it can be indexed for search
+///
+/// # Arguments
+/// * `bytes` - The contents of the syllabus file
+///
+/// # Returns
+/// The detected [`SyllabusFormat`] and the text with whitespace collapsed, which is empty for
+/// [`SyllabusFormat::Unknown`], or an [`ExtractError`] if the file is corrupt
+pub fn extract_text(bytes: &[u8]) -> Result<(SyllabusFormat, String), ExtractError> {
+    let format = SyllabusFormat::detect(bytes);
+    let error = |message| ExtractError { format, message };
+
+    let text = match format {
+        SyllabusFormat::Pdf => pdf_text(bytes).map_err(error)?,
+        SyllabusFormat::Docx => docx_text(bytes).map_err(error)?,
+        SyllabusFormat::Html => html_text(&String::from_utf8_lossy(bytes)),
+        SyllabusFormat::Text => String::from_utf8_lossy(bytes)
+            .trim_start_matches('\u{feff}')
+            .to_owned(),
+        SyllabusFormat::Unknown => String::new(),
+    };
+
+    Ok((format, normalize_whitespace(&text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    fn zip_file(name: &str, contents: &str) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(contents.as_bytes()).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_extract_docx() {
+        let docx = zip_file(
+            "word/document.xml",
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+              <w:body>
+                <w:p><w:r><w:t>15-122 Principles of</w:t></w:r><w:r><w:t xml:space="preserve"> Imperative Computation</w:t></w:r></w:p>
+                <w:p><w:r><w:t>Grading: labs &amp; exams</w:t></w:r></w:p>
+              </w:body>
+            </w:document>"#,
+        );
+
+        assert_eq!(
+            extract_text(&docx).unwrap(),
+            (
+                SyllabusFormat::Docx,
+                "15-122 Principles of Imperative Computation Grading: labs & exams".to_owned()
+            )
+        );
+
+        // Other zip files aren't documents
+        let other = zip_file("data.csv", "a,b");
+        assert_eq!(SyllabusFormat::detect(&other), SyllabusFormat::Unknown);
+        assert_eq!(extract_text(&other).unwrap().1, "");
+    }
+
+    #[test]
+    fn test_extract_html_and_text() {
+        let html = b"<!DOCTYPE html><html><head><style>p { color: red; }</style>\
+                     <script>alert(1)</script></head>\
+                     <body><h1>Syllabus</h1><p>Office hours:\n  Tuesdays</p></body></html>";
+        assert_eq!(
+            extract_text(html).unwrap(),
+            (
+                SyllabusFormat::Html,
+                "Syllabus Office hours: Tuesdays".to_owned()
+            )
+        );
+
+        assert_eq!(
+            extract_text("\u{feff}Week 1:\tIntro".as_bytes()).unwrap(),
+            (SyllabusFormat::Text, "Week 1: Intro".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_extract_pdf_errors() {
+        assert_eq!(SyllabusFormat::detect(b"%PDF-1.4\n"), SyllabusFormat::Pdf);
+
+        // A truncated PDF is an error rather than a panic
+        let error = extract_text(b"%PDF-1.4\n1 0 obj\n<<").unwrap_err();
+        assert_eq!(error.format, SyllabusFormat::Pdf);
+
+        assert_eq!(
+            extract_text(&[0xff, 0xd8, 0xff, 0xe0]).unwrap(),
+            (SyllabusFormat::Unknown, String::new())
+        );
+    }
+}
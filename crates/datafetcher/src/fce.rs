@@ -0,0 +1,152 @@
+use database::services::evaluation::EvaluationRecord;
+use models::syllabus_data::{Season, Year};
+use sea_orm::prelude::Decimal;
+use serde::Deserialize;
+use std::{io::Read, str::FromStr};
+
+/// A row of a Faculty Course Evaluation CSV export, with the export's column names
+#[derive(Debug, Deserialize)]
+struct FceRow {
+    #[serde(rename = "Year")]
+    year: u16,
+    #[serde(rename = "Semester")]
+    semester: String,
+    #[serde(rename = "Course ID")]
+    course_id: String,
+    #[serde(rename = "Section")]
+    section: String,
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Course Name")]
+    course_name: String,
+    #[serde(rename = "Level")]
+    level: String,
+    #[serde(rename = "Possible Respondents")]
+    possible_respondents: i16,
+    #[serde(rename = "Num Respondents")]
+    num_respondents: i16,
+    #[serde(rename = "Hrs Per Week", default)]
+    hours_per_week: String,
+    #[serde(rename = "Interest in student learning", default)]
+    interest_in_student_learning: String,
+    #[serde(rename = "Clearly explain course requirements", default)]
+    clearly_explain_requirements: String,
+    #[serde(rename = "Clear learning objectives & goals", default)]
+    clear_learning_objectives: String,
+    #[serde(
+        rename = "Instructor provides feedback to students to improve",
+        default
+    )]
+    instructor_provides_feedback: String,
+    #[serde(rename = "Demonstrate importance of subject matter", default)]
+    demonstrate_importance: String,
+    #[serde(rename = "Explains subject matter of course", default)]
+    explains_subject_matter: String,
+    #[serde(rename = "Show respect for all students", default)]
+    show_respect_for_students: String,
+    #[serde(rename = "Overall teaching rate", default)]
+    overall_teaching_rate: String,
+    #[serde(rename = "Overall course rate", default)]
+    overall_course_rate: String,
+}
+
+/// Parses a rating cell, which is empty when an evaluation didn't ask the question
+fn rating(cell: &str) -> Option<Decimal> {
+    Decimal::from_str(cell.trim()).ok()
+}
+
+/// Maps the semester column of an FCE export to the seasons it may refer to
+fn seasons(semester: &str) -> Option<Vec<Season>> {
+    match semester.trim().to_lowercase().as_str() {
+        "fall" => Some(vec![Season::Fall]),
+        "spring" => Some(vec![Season::Spring]),
+        "summer" => Some(vec![Season::Summer1, Season::Summer2]),
+        _ => None,
+    }
+}
+
+impl FceRow {
+    fn into_record(self) -> Option<EvaluationRecord> {
+        Some(EvaluationRecord {
+            number: self.course_id.trim().replace('-', ""),
+            seasons: seasons(&self.semester)?,
+            year: Year(self.year),
+            section: self.section.trim().to_owned(),
+            instructor: self.name,
+            course_short_name: self.course_name,
+            course_level: self.level,
+            total_students: self.possible_respondents,
+            num_responses: self.num_respondents,
+            hours_per_week: rating(&self.hours_per_week),
+            interest_in_student_learning: rating(&self.interest_in_student_learning),
+            clearly_explain_requirements: rating(&self.clearly_explain_requirements),
+            clear_learning_objectives: rating(&self.clear_learning_objectives),
+            instructor_provides_feedback: rating(&self.instructor_provides_feedback),
+            demonstrate_importance: rating(&self.demonstrate_importance),
+            explains_subject_matter: rating(&self.explains_subject_matter),
+            show_respect_for_students: rating(&self.show_respect_for_students),
+            overall_teaching_rate: rating(&self.overall_teaching_rate),
+            overall_course_rate: rating(&self.overall_course_rate),
+        })
+    }
+}
+
+/// Reads the evaluations from a Faculty Course Evaluation CSV export
+///
+/// # Arguments
+/// * `reader` - The CSV data
+///
+/// # Returns
+/// The [`EvaluationRecord`]s and the number of rows that were skipped because their semester
+/// isn't a known season, or a [`csv::Error`] if the file is malformed
+pub fn read_evaluations(reader: impl Read) -> Result<(Vec<EvaluationRecord>, usize), csv::Error> {
+    let mut records = Vec::new();
+    let mut skipped = 0;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    for row in reader.deserialize::<FceRow>() {
+        match row?.into_record() {
+            Some(record) => records.push(record),
+            None => skipped += 1,
+        }
+    }
+
+    Ok((records, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+Year,Semester,College,Dept,Course ID,Section,Name,Course Name,Level,Possible Respondents,Num Respondents,Response Rate,Hrs Per Week,Interest in student learning,Clearly explain course requirements,Clear learning objectives & goals,Instructor provides feedback to students to improve,Demonstrate importance of subject matter,Explains subject matter of course,Show respect for all students,Overall teaching rate,Overall course rate
+2024,Fall,SCS,CS,15-122,Lec 1,\"CERVESATO, ILIANO\",PRIN IMPERATIVE COMP,Undergraduate,300,150,50%,11.5,4.5,4.4,4.3,4.1,4.6,4.5,4.8,4.4,4.2
+2024,Summer,SCS,CS,15-122,A,\"DOE, JANE\",PRIN IMPERATIVE COMP,Undergraduate,20,10,50%,,,,,,,,,4.0,3.9
+2024,Winter,SCS,CS,15-122,A,\"DOE, JANE\",PRIN IMPERATIVE COMP,Undergraduate,20,10,50%,,,,,,,,,,
+";
+
+    #[test]
+    fn test_read_evaluations() {
+        let (records, skipped) = read_evaluations(CSV.as_bytes()).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(skipped, 1);
+
+        let fall = &records[0];
+        assert_eq!(fall.number, "15122");
+        assert_eq!(fall.seasons, vec![Season::Fall]);
+        assert_eq!(*fall.year, 2024);
+        assert_eq!(fall.section, "Lec 1");
+        assert_eq!(fall.instructor, "CERVESATO, ILIANO");
+        assert_eq!(fall.num_responses, 150);
+        assert_eq!(fall.hours_per_week, Decimal::from_str("11.5").ok());
+
+        let summer = &records[1];
+        assert_eq!(summer.seasons, vec![Season::Summer1, Season::Summer2]);
+        assert_eq!(summer.hours_per_week, None);
+        assert_eq!(summer.overall_course_rate, Decimal::from_str("3.9").ok());
+    }
+}
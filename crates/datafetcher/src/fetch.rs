@@ -0,0 +1,513 @@
+use crate::{
+    courses::{
+        details::parse_course_details,
+        first_pass::{first_pass, header_rows},
+        second_pass::second_pass,
+    },
+    diagnostics::{Diagnostic, Diagnostics, unknown_line_diagnostics},
+    source::{FetchOptions, Source, SourceError, course_details_url, soc_url},
+};
+use futures::{StreamExt, future::try_join_all, stream};
+use models::{
+    course_data::{CourseEntry, CourseObject},
+    syllabus_data::{Season, Semester, Year},
+};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::BufReader, path::Path, str::FromStr, time::Instant};
+
+/// A course whose details could not be fetched, even after retrying
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailedCourse {
+    /// Course number (e.g., "15122")
+    pub number: String,
+    /// Season code (e.g., "F")
+    pub season: String,
+    /// Full year (e.g., 2025)
+    pub year: u16,
+    /// Why the last attempt failed
+    pub error: String,
+}
+
+impl FailedCourse {
+    fn new(course: &CourseEntry, error: &SourceError) -> Self {
+        Self {
+            number: course.number.to_string(),
+            season: course.season.as_str().to_owned(),
+            year: *course.year,
+            error: error.to_string(),
+        }
+    }
+
+    /// Whether this failure is for the given course offering
+    pub fn matches(&self, course: &CourseEntry) -> bool {
+        self.number == course.number.to_string()
+            && Season::from_str(&self.season).is_ok_and(|season| season == course.season)
+            && self.year == *course.year
+    }
+
+    /// Reads a list of failed courses written by a previous run
+    pub fn read_list(path: impl AsRef<Path>) -> std::io::Result<Vec<Self>> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+/// Which semesters to fetch
+///
+/// CMU only publishes the current schedule of classes for each season, so a [`Semester`] selects
+/// its season and then keeps only the courses from the requested year.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    /// Seasons to fetch in full
+    pub seasons: Vec<Season>,
+    /// Specific semesters to fetch
+    pub semesters: Vec<Semester>,
+}
+
+impl Selection {
+    /// Whether nothing was selected, which means everything is fetched
+    pub fn is_all(&self) -> bool {
+        self.seasons.is_empty() && self.semesters.is_empty()
+    }
+
+    /// The seasons whose schedule of classes has to be downloaded
+    pub fn seasons(&self) -> Vec<Season> {
+        if self.is_all() {
+            return Season::all();
+        }
+
+        let mut seasons = self.seasons.clone();
+        for semester in &self.semesters {
+            if !seasons.contains(&semester.season) {
+                seasons.push(semester.season);
+            }
+        }
+        seasons
+    }
+
+    /// Whether a course belongs to the selection
+    pub fn includes(&self, course: &CourseEntry) -> bool {
+        self.is_all()
+            || self.seasons.contains(&course.season)
+            || self
+                .semesters
+                .iter()
+                .any(|s| s.season == course.season && s.year == course.year)
+    }
+}
+
+/// Everything produced by a run of the fetch pipeline
+#[derive(Debug, Clone, Default)]
+pub struct FetchOutput {
+    /// The courses, in schedule order
+    pub course_objs: Vec<CourseObject>,
+    /// Problems found while parsing
+    pub diagnostics: Diagnostics,
+    /// Courses whose details could not be fetched; these have no metadata
+    pub failed: Vec<FailedCourse>,
+}
+
+/// Retrieves the year from the course data text
+///
+/// The year ends the `Semester:` row of the header, whose position has moved between years.
+///
+/// # Arguments
+/// * `text` - A string slice containing the course data
+///
+/// # Returns
+/// * An `Option<Year>` which is the year extracted from the text
+pub fn extract_year(text: &str) -> Option<Year> {
+    let line = text
+        .lines()
+        .take(header_rows(text))
+        .find(|line| line.trim_start().starts_with("Semester"))?;
+    let year_str = line.split_whitespace().last()?;
+
+    year_str.parse::<u16>().ok().map(Year)
+}
+
+/// Parses the text of a SOC file into courses
+///
+/// # Arguments
+/// * `text` - The full contents of the SOC file
+/// * `season` - The season the file is for
+/// * `year` - The year the file is for
+///
+/// # Returns
+/// The parsed [`CourseEntry`]s along with diagnostics for lines that could not be parsed
+pub fn parse_soc(text: &str, season: Season, year: Year) -> (Vec<CourseEntry>, Vec<Diagnostic>) {
+    let lines = first_pass(text);
+    let unknown = unknown_line_diagnostics(&lines, header_rows(text), season, year);
+
+    (second_pass(lines, season, year), unknown)
+}
+
+/// Downloads and parses the schedule of classes for the given seasons
+///
+/// # Arguments
+/// * `source` - Where to get the SOC files from
+/// * `seasons` - The seasons to fetch
+///
+/// # Returns
+/// The parsed [`CourseEntry`]s along with diagnostics for lines that could not be parsed
+pub async fn fetch_course_entries<S: Source>(
+    source: &S,
+    seasons: &[Season],
+) -> Result<(Vec<CourseEntry>, Diagnostics), SourceError> {
+    let start = Instant::now();
+
+    // Download each season's data concurrently
+    let raw_data = try_join_all(seasons.iter().map(|&season| async move {
+        println!("Downloading data for season: {season:?}");
+        let text = source.fetch(&soc_url(season)).await?;
+
+        println!("Downloaded {} bytes for season {season:?}", text.len());
+        Ok::<_, SourceError>((season, text))
+    }))
+    .await?;
+
+    println!("All season data downloaded in {:?}", start.elapsed());
+
+    println!("Parsing course data...");
+    let parse_start = Instant::now();
+
+    let (course_entries, line_diagnostics): (Vec<_>, Vec<_>) = raw_data
+        .into_par_iter()
+        .map(|(season, text)| {
+            let year = extract_year(&text)
+                .unwrap_or_else(|| panic!("Failed to extract year for {season:?}"));
+            let (courses, unknown) = parse_soc(&text, season, year);
+
+            println!(
+                "Parsed {} courses for {} {}",
+                courses.len(),
+                season.as_str(),
+                year
+            );
+            (courses, unknown)
+        })
+        .unzip();
+
+    let course_entries = course_entries.into_iter().flatten().collect::<Vec<_>>();
+    let diagnostics = line_diagnostics
+        .into_iter()
+        .flatten()
+        .collect::<Diagnostics>();
+
+    println!(
+        "Parsed {} total courses in {:?}",
+        course_entries.len(),
+        parse_start.elapsed()
+    );
+
+    Ok((course_entries, diagnostics))
+}
+
+/// Fetches a course's details page to get additional metadata for that course.
+///
+/// # Arguments
+/// * `source` - Where to get the courseDetails page from
+/// * `course` - The course to process
+///
+/// # Returns
+/// The [`CourseObject`] containing the full course object, with the `metadata` field
+/// set to `Some(CourseMetadata)` along with any [`Diagnostic`]s for fields that could not be
+/// parsed, or the course and the error if the page could not be fetched.
+pub async fn fetch_course_details<S: Source>(
+    source: &S,
+    course: CourseEntry,
+) -> Result<(CourseObject, Vec<Diagnostic>), (CourseEntry, SourceError)> {
+    match source.fetch(&course_details_url(&course)).await {
+        Ok(html) => Ok(parse_course_details(&html, course)),
+        Err(e) => {
+            eprintln!(
+                "Failed to fetch course details for course {} ({}{}): {e}",
+                course.number.as_full_string(),
+                course.season.as_str(),
+                course.year
+            );
+            Err((course, e))
+        }
+    }
+}
+
+/// Fetches the details of every course, at most `concurrency` at a time
+///
+/// # Arguments
+/// * `source` - Where to get the courseDetails pages from
+/// * `course_entries` - The courses to process
+/// * `concurrency` - Maximum number of pages fetched at the same time
+///
+/// # Returns
+/// A [`FetchOutput`] with the courses in their original order
+pub async fn fetch_all_course_details<S: Source>(
+    source: &S,
+    course_entries: Vec<CourseEntry>,
+    concurrency: usize,
+) -> FetchOutput {
+    let details_start = Instant::now();
+    let total_courses = course_entries.len();
+
+    let results: Vec<_> = stream::iter(course_entries)
+        .map(|course| fetch_course_details(source, course))
+        .buffered(concurrency.max(1))
+        .enumerate()
+        .map(|(i, result)| {
+            let done = i + 1;
+            if done.is_multiple_of(100) || done == total_courses {
+                println!("Processed {done}/{total_courses} courses...");
+            }
+            result
+        })
+        .collect()
+        .await;
+
+    let mut output = FetchOutput::default();
+
+    for result in results {
+        match result {
+            Ok((course_obj, diagnostics)) => {
+                output.course_objs.push(course_obj);
+                output.diagnostics.extend(diagnostics);
+            }
+            Err((course, e)) => {
+                output.failed.push(FailedCourse::new(&course, &e));
+                output.course_objs.push(CourseObject {
+                    course,
+                    metadata: None,
+                });
+            }
+        }
+    }
+
+    println!("Processed course details in {:?}", details_start.elapsed());
+    output
+}
+
+/// Runs the full pipeline from downloading the schedule of classes to [`CourseObject`]s
+///
+/// # Arguments
+/// * `source` - Where to get every response from
+/// * `selection` - The semesters to fetch
+/// * `options` - The [`FetchOptions`] to use
+///
+/// # Returns
+/// A [`FetchOutput`] with the courses in schedule order, or a [`SourceError`] if a schedule of
+/// classes file could not be downloaded
+pub async fn fetch_course_objects<S: Source>(
+    source: &S,
+    selection: &Selection,
+    options: &FetchOptions,
+) -> Result<FetchOutput, SourceError> {
+    let start = Instant::now();
+    let (mut course_entries, diagnostics) =
+        fetch_course_entries(source, &selection.seasons()).await?;
+
+    let total = course_entries.len();
+    course_entries.retain(|course| selection.includes(course));
+    if course_entries.len() < total {
+        println!(
+            "Selected {} of {total} courses for the requested semesters",
+            course_entries.len()
+        );
+    }
+
+    println!("Processing course details...");
+    let mut output = fetch_all_course_details(source, course_entries, options.concurrency).await;
+
+    let mut all_diagnostics = diagnostics;
+    all_diagnostics.extend(output.diagnostics.diagnostics);
+    output.diagnostics = all_diagnostics;
+
+    println!(
+        "Total course data fetching completed in {:?}",
+        start.elapsed()
+    );
+
+    Ok(output)
+}
+
+/// Fetches the details again for courses that failed in a previous run
+///
+/// # Arguments
+/// * `source` - Where to get the courseDetails pages from
+/// * `course_objs` - The courses from the previous run, or only the failed ones
+/// * `failed` - The courses whose details could not be fetched
+/// * `options` - The [`FetchOptions`] to use
+///
+/// # Returns
+/// A [`FetchOutput`] with the same courses in the same order, where each retried course has
+/// metadata if the retry succeeded
+pub async fn retry_failed_courses<S: Source>(
+    source: &S,
+    course_objs: Vec<CourseObject>,
+    failed: &[FailedCourse],
+    options: &FetchOptions,
+) -> FetchOutput {
+    let (retry, keep): (Vec<_>, Vec<_>) = course_objs
+        .into_iter()
+        .enumerate()
+        .partition(|(_, course_obj)| failed.iter().any(|f| f.matches(&course_obj.course)));
+
+    println!("Retrying {} failed courses...", retry.len());
+
+    let (retry_indices, retry_entries): (Vec<_>, Vec<_>) = retry
+        .into_iter()
+        .map(|(i, course_obj)| (i, course_obj.course))
+        .unzip();
+
+    let mut output = fetch_all_course_details(source, retry_entries, options.concurrency).await;
+
+    // Put the retried courses back in their original positions
+    let mut course_objs: Vec<_> = keep
+        .into_iter()
+        .chain(retry_indices.into_iter().zip(output.course_objs))
+        .collect();
+    course_objs.sort_by_key(|(i, _)| *i);
+
+    output.course_objs = course_objs
+        .into_iter()
+        .map(|(_, course_obj)| course_obj)
+        .collect();
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::{ReplaySource, course_details_url_for, recording_path};
+    use models::requisite::Expr;
+    use std::path::Path;
+    use uuid::Uuid;
+
+    const SOC: &str = "\
+Carnegie Mellon University
+Schedule of Classes
+
+Semester: Fall 2025
+
+
+
+
+
+
+
+\tComputer Science\t\t\t\t\t\t\t\t
+\t15122\tPrinciples of Imperative Computation
+\t\t\t12.0\tLec 1\tTR\t09:30AM\t10:50AM\tGHC 4401\tPittsburgh, Pennsylvania\tCervesato
+\t\t\t\tA\tF\t09:00AM\t09:50AM\tWEH 5403\tPittsburgh, Pennsylvania\tCervesato
+\t15150\tPrinciples of Functional Programming
+\t\t\t12.0\tLec\tMW\t11:00AM\t12:20PM\tGHC 4401\tPittsburgh, Pennsylvania\tErdmann
+";
+
+    const DETAILS: &str = r#"<html><body><div>
+<table>
+<tr><th>Lec/Sec</th><th>Days</th><th>Begin</th><th>End</th><th>Bldg/Room</th><th>Location</th><th>Instructor(s)</th></tr>
+<tr><td>Lec 1</td><td>TR</td><td>09:30AM</td><td>10:50AM</td><td>GHC 4401</td><td>Pittsburgh, Pennsylvania</td><td>Cervesato</td></tr>
+<tr><td>A</td><td>F</td><td>09:00AM</td><td>09:50AM</td><td>WEH 5403</td><td>Pittsburgh, Pennsylvania</td><td>Cervesato</td></tr>
+</table>
+<dl><dt>Special Permission Required:</dt><dd>No</dd></dl>
+<h4>Description:</h4><p>  Write  correct programs. </p>
+<dl><dt>Prerequisites</dt><dd>15112 or 15110</dd></dl>
+<dl><dt>Corequisites</dt><dd>None</dd></dl>
+<dl><dt>Cross-Listed Courses</dt><dd>None</dd></dl>
+<dl><dt>Notes</dt><dd>None</dd></dl>
+</div></body></html>"#;
+
+    fn record(dir: &Path, url: &str, body: &str) {
+        std::fs::write(recording_path(dir, url), body).unwrap();
+    }
+
+    #[test]
+    fn test_semester_selection() {
+        let f25 = Semester::from_str("F25").unwrap();
+        let selection = Selection {
+            seasons: vec![Season::Spring],
+            semesters: vec![f25],
+        };
+        assert_eq!(selection.seasons(), vec![Season::Spring, Season::Fall]);
+        assert_eq!(Selection::default().seasons(), Season::all());
+    }
+
+    #[tokio::test]
+    async fn test_replay_pipeline() {
+        let dir = std::env::temp_dir().join(format!("datafetcher-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        record(&dir, &soc_url(Season::Fall), SOC);
+        record(
+            &dir,
+            &course_details_url_for("15122", Season::Fall, Year(2025)),
+            DETAILS,
+        );
+
+        let source = ReplaySource::new(&dir);
+        let options = FetchOptions::default();
+        let output = fetch_course_objects(
+            &source,
+            &Selection {
+                seasons: vec![Season::Fall],
+                ..Default::default()
+            },
+            &options,
+        )
+        .await
+        .unwrap();
+        let course_objs = &output.course_objs;
+
+        assert!(output.diagnostics.is_empty());
+        assert_eq!(course_objs.len(), 2);
+
+        let course = &course_objs[0];
+        assert_eq!(course.course.number.to_string(), "15122");
+        assert_eq!(*course.course.year, 2025);
+        assert_eq!(course.course.components.len(), 2);
+
+        let metadata = course.metadata.as_ref().unwrap();
+        assert!(!metadata.special_permission);
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("Write correct programs.")
+        );
+        assert!(matches!(
+            metadata.prerequisites.clone().into_inner(),
+            Some(Expr::Or(_, _))
+        ));
+        assert!(metadata.corequisites.is_empty());
+
+        // 15150's details were never recorded
+        assert!(course_objs[1].metadata.is_none());
+        assert_eq!(output.failed.len(), 1);
+        assert_eq!(output.failed[0].number, "15150");
+
+        // Once they are, retrying only fetches the failed course
+        record(
+            &dir,
+            &course_details_url_for("15150", Season::Fall, Year(2025)),
+            DETAILS,
+        );
+        let retried =
+            retry_failed_courses(&source, output.course_objs, &output.failed, &options).await;
+
+        assert!(retried.failed.is_empty());
+        assert_eq!(retried.course_objs[1].course.number.to_string(), "15150");
+        assert!(retried.course_objs[1].metadata.is_some());
+
+        // The SOC file itself must be recorded
+        assert!(
+            fetch_course_objects(
+                &source,
+                &Selection {
+                    seasons: vec![Season::Spring],
+                    ..Default::default()
+                },
+                &options,
+            )
+            .await
+            .is_err()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
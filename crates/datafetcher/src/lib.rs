@@ -1,2 +1,11 @@
+pub mod backfill;
+pub mod canvas;
 pub mod courses;
+pub mod diagnostics;
+pub mod extract;
+pub mod fce;
+pub mod fetch;
+pub mod snapshot;
+pub mod source;
+pub mod syllabi;
 pub mod util;
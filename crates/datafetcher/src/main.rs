@@ -1,331 +1,840 @@
-//// use database::db::create_connection;
-//use database::services::save_course::SaveCourseService;
-use datafetcher::{
-    courses::{first_pass::first_pass, second_pass::second_pass},
-    util::{
-        execute_hurl, get_capture_value, get_captures, get_optional_string_value,
-        get_parsed_struct_value, insert_variable, parse_from_raw_html,
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use database::{
+    archive::SyllabusStore,
+    db::create_connection,
+    services::{
+        evaluation::EvaluationService,
+        query_course::QueryCourseService,
+        save_course::SaveCourseService,
+        scrape_run::{ScrapeRunService, ScrapeRunSummary, SemesterSummary},
+        syllabus::SyllabusService,
     },
 };
-use futures::future::join_all;
-use hurl::runner::VariableSet;
+use datafetcher::{
+    backfill::{find_archived_files, read_archived_file},
+    canvas::{CanvasClient, CanvasError},
+    diagnostics::Diagnostics,
+    fce::read_evaluations,
+    fetch::{FailedCourse, FetchOutput, Selection, fetch_course_objects, retry_failed_courses},
+    snapshot::{read_snapshot, write_json, write_ndjson},
+    source::{FetchOptions, LiveSource, RecordingSource, ReplaySource, Source},
+    syllabi::{archive_syllabi, fetch_syllabus_map, read_syllabus_csv},
+};
 use models::{
-    course_data::{CourseEntry, CourseMetadata, CourseObject},
-    reservation::{Reservation, Restriction},
-    syllabus_data::{Season, Year},
+    course_change::{CourseChange, diff_courses},
+    course_data::CourseObject,
+    syllabus_data::{Department, Season, Semester, SyllabusMap, Year},
 };
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use reqwest::Client;
-use scraper::{Html, Selector};
-use std::{collections::HashMap, str::FromStr, time::Instant};
-
+use sea_orm::{DatabaseConnection, DbErr};
 use serde_json::to_writer_pretty;
-use std::fs::File;
-use std::io::BufWriter;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+/// Scrapes CMU course data and loads it into the database
+#[derive(Parser)]
+#[command(name = "datafetcher")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
 
-/// Hurl script for retrieving course details
-const COURSE_DETAILS_SCRIPT: &str = include_str!("../scripts/course_details.hurl");
+#[derive(Subcommand)]
+enum Command {
+    /// Fetch courses and write them to JSON, NDJSON, or the database
+    Fetch(FetchArgs),
+    /// Import Faculty Course Evaluations from a CSV export
+    ImportFce {
+        /// The CSV export to import
+        file: PathBuf,
+    },
+    /// Import syllabus URLs from the Canvas syllabus registries, or from a CSV file
+    ImportSyllabi {
+        /// A CSV file with season,year,number,section,url columns to import instead of reading
+        /// Canvas
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// Semester whose registries to read (e.g., F25); can be repeated
+        #[arg(
+            long = "semester",
+            value_name = "SEMESTER",
+            required_unless_present = "file"
+        )]
+        semesters: Vec<Semester>,
+        /// Department whose registry to read (e.g., CS); can be repeated [default: all]
+        #[arg(long = "department", value_name = "DEPARTMENT")]
+        departments: Vec<Department>,
+        /// Maximum number of registries read or syllabi downloaded at the same time
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+        /// Don't download the syllabi into the archive
+        #[arg(long)]
+        skip_archive: bool,
+    },
+    /// Load a JSON or NDJSON snapshot written by `fetch` into the database, updating the
+    /// stored courses of every semester in it
+    Load {
+        /// The snapshot to load
+        file: PathBuf,
+    },
+    /// Load archived SOC files of past semesters from a directory into the database
+    ///
+    /// Each file is named after its semester (e.g., F18.dat or sched_layout_F18.dat).
+    Backfill {
+        /// The directory holding the archived files
+        dir: PathBuf,
+        /// Update semesters that already have stored courses instead of skipping them
+        #[arg(long)]
+        overwrite: bool,
+        /// File to write the diagnostics report to
+        #[arg(long, value_name = "FILE", default_value = "diagnostics.json")]
+        report: PathBuf,
+    },
+    /// Fetch courses and report how they differ from the database, without saving them
+    Diff {
+        #[command(flatten)]
+        source: SourceArgs,
+        /// File to write the changes to
+        #[arg(long, value_name = "FILE", default_value = "changes.json")]
+        out: PathBuf,
+    },
+    /// Fetch courses and report parse diagnostics, failing if there are any
+    Validate {
+        #[command(flatten)]
+        source: SourceArgs,
+        /// File to write the diagnostics report to
+        #[arg(long, value_name = "FILE", default_value = "diagnostics.json")]
+        report: PathBuf,
+    },
+}
 
-/// Retrieves the year from the course data text
-///
-/// # Arguments
-/// * `text` - A string slice containing the course data
-///
-/// # Returns
-/// * An `Option<Year>` which is the year extracted from the text
-fn extract_year(text: &str) -> Option<Year> {
-    let line = text.lines().nth(3)?;
-    let year_str = line.split_whitespace().last()?;
+/// Where fetched courses are written
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Output {
+    /// A pretty-printed JSON array
+    Json,
+    /// One JSON course per line
+    Ndjson,
+    /// The database, recording the changes and the scrape run
+    Db,
+}
 
-    year_str.parse::<u16>().ok().map(Year)
+#[derive(Args)]
+struct FetchArgs {
+    #[command(flatten)]
+    source: SourceArgs,
+    /// Where to write the fetched courses
+    #[arg(long, value_enum, default_value_t = Output::Db)]
+    output: Output,
+    /// File to write JSON or NDJSON output to [default: course_objs.json or course_objs.ndjson]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+    /// A failed courses list from a previous run, to retry instead of fetching everything;
+    /// only the retried courses are written or saved
+    #[arg(long, value_name = "FILE")]
+    retry_failed: Option<PathBuf>,
+    /// File to write the courses whose details couldn't be fetched to
+    #[arg(long, value_name = "FILE", default_value = "failed_courses.json")]
+    failed_report: PathBuf,
+    /// File to write the diagnostics report to
+    #[arg(long, value_name = "FILE", default_value = "diagnostics.json")]
+    report: PathBuf,
+    /// Don't read the Canvas syllabus registries when saving to the database
+    #[arg(long)]
+    skip_syllabi: bool,
+    /// Don't download the syllabi into the archive when saving to the database
+    #[arg(long)]
+    skip_archive: bool,
 }
 
-/// Parses the related URLs from the full HTML document
-///
-/// # Arguments
-/// * `document` - The [`Html`] document to parse
-///
-/// # Returns
-/// A vector of related URLs as strings
-fn parse_related_urls(document: &Html) -> Vec<String> {
-    let selector = Selector::parse("#course-detail-related-urls a").unwrap();
-
-    document
-        .select(&selector)
-        .filter_map(|link| link.value().attr("href"))
-        .map(|href| href.to_owned())
-        .collect()
+/// Which semesters to fetch, where responses come from, and how fast to request them
+#[derive(Args)]
+struct SourceArgs {
+    /// Season to fetch in full (F, S, M, or N); can be repeated [default: all]
+    #[arg(long = "season", value_name = "SEASON")]
+    seasons: Vec<Season>,
+    /// Semester to fetch (e.g., F25); can be repeated
+    #[arg(long = "semester", value_name = "SEMESTER")]
+    semesters: Vec<Semester>,
+    /// Fetch from CMU's servers and save every response to a directory
+    #[arg(long, value_name = "DIR", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+    /// Serve previously recorded responses from a directory
+    #[arg(long, value_name = "DIR")]
+    replay: Option<PathBuf>,
+    /// Maximum number of courseDetails pages, syllabus registries, or syllabi fetched at the
+    /// same time
+    #[arg(long, default_value_t = FetchOptions::default().concurrency)]
+    concurrency: usize,
+    /// Maximum number of requests per second sent to each host
+    #[arg(long, value_name = "REQUESTS_PER_SECOND", value_parser = parse_rate, default_value_t = FetchOptions::default().requests_per_second)]
+    rate: f64,
+    /// Number of times a transient failure is retried
+    #[arg(long, default_value_t = FetchOptions::default().max_retries)]
+    retries: u32,
+    /// Time limit for a single request
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout, default_value_t = FetchOptions::default().timeout.as_secs_f64())]
+    timeout: f64,
 }
 
-/// Parses the reservations from the full HTML document
-///
-/// # Arguments
-/// * `document` - The [`Html`] document to parse
-///
-/// # Returns
-/// A vector of [`Reservation`] objects, each containing a section and its associated [`Restriction`]s
-fn parse_reservations(document: &Html) -> Vec<Reservation> {
-    let mut reservations_map: HashMap<String, Vec<Restriction>> = HashMap::new();
+/// Parses `--rate`, which must be positive so the rate limit can't be turned off by accident
+fn parse_rate(raw: &str) -> Result<f64, String> {
+    let rate = raw.parse::<f64>().map_err(|e| e.to_string())?;
+    if rate > 0.0 && Duration::try_from_secs_f64(1.0 / rate).is_ok() {
+        Ok(rate)
+    } else {
+        Err(format!(
+            "{raw} is not a positive number of requests per second"
+        ))
+    }
+}
 
-    let table_selector = Selector::parse("table").unwrap();
-    let header_selector = Selector::parse("th").unwrap();
+/// Parses `--timeout`, which must be a positive number of seconds that fits in a [`Duration`]
+fn parse_timeout(raw: &str) -> Result<f64, String> {
+    let seconds = raw.parse::<f64>().map_err(|e| e.to_string())?;
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(timeout) if !timeout.is_zero() => Ok(seconds),
+        _ => Err(format!("{raw} is not a positive number of seconds")),
+    }
+}
 
-    let row_selector = Selector::parse("tr").unwrap();
-    let cell_selector = Selector::parse("td").unwrap();
+impl SourceArgs {
+    fn options(&self) -> FetchOptions {
+        FetchOptions {
+            concurrency: self.concurrency,
+            requests_per_second: self.rate,
+            max_retries: self.retries,
+            timeout: Duration::from_secs_f64(self.timeout),
+            ..FetchOptions::default()
+        }
+    }
 
-    // Find the table with the "Section" and "Restriction" headers
-    for table in document.select(&table_selector) {
-        let headers: Vec<String> = table
-            .select(&header_selector)
-            .map(|th| th.text().collect::<String>().trim().to_lowercase())
-            .collect();
+    fn selection(&self) -> Selection {
+        Selection {
+            seasons: self.seasons.clone(),
+            semesters: self.semesters.clone(),
+        }
+    }
 
-        if headers.contains(&"section".to_string()) && headers.contains(&"restriction".to_string())
-        {
-            // This is the reservations table, get the data rows
-            for row in table.select(&row_selector) {
-                let cells: Vec<_> = row.select(&cell_selector).collect();
-
-                if cells.len() >= 2 {
-                    let section = cells[0].text().collect::<String>().trim().to_owned();
-                    let restriction_text = cells[1].text().collect::<String>().trim().to_owned();
-
-                    if !section.is_empty() && !restriction_text.is_empty() {
-                        let restriction =
-                            Restriction::from_str(&restriction_text).unwrap_or_else(|e| {
-                                eprintln!("Failed to parse restriction '{restriction_text}': {e}");
-                                Restriction {
-                                    student_type: None,
-                                    restriction_type: None,
-                                }
-                            });
-
-                        reservations_map
-                            .entry(section)
-                            .or_default()
-                            .push(restriction);
-                    }
-                }
+    /// Runs the fetch pipeline against the chosen source, or only retries the failed courses
+    /// of a previous run when `retry` holds that run's courses and failures
+    async fn fetch(
+        &self,
+        retry: Option<(Vec<CourseObject>, Vec<FailedCourse>)>,
+    ) -> Result<FetchOutput, Box<dyn Error>> {
+        let options = self.options();
+        match (&self.record, &self.replay) {
+            (_, Some(dir)) => {
+                self.fetch_from(&ReplaySource::new(dir), &options, retry)
+                    .await
+            }
+            (Some(dir), None) => {
+                let source = RecordingSource::new(LiveSource::new(&options), dir)?;
+                self.fetch_from(&source, &options, retry).await
             }
+            (None, None) => {
+                self.fetch_from(&LiveSource::new(&options), &options, retry)
+                    .await
+            }
+        }
+    }
 
-            // This is the right table, stop looking
-            break;
+    async fn fetch_from<S: Source>(
+        &self,
+        source: &S,
+        options: &FetchOptions,
+        retry: Option<(Vec<CourseObject>, Vec<FailedCourse>)>,
+    ) -> Result<FetchOutput, Box<dyn Error>> {
+        match retry {
+            Some((course_objs, failed)) => {
+                Ok(retry_failed_courses(source, course_objs, &failed, options).await)
+            }
+            None => Ok(fetch_course_objects(source, &self.selection(), options).await?),
         }
     }
+}
+
+/// Loads a failed courses list along with the stored courses it lists
+async fn load_failed_courses(
+    db: &DatabaseConnection,
+    path: &Path,
+) -> Result<(Vec<CourseObject>, Vec<FailedCourse>), Box<dyn Error>> {
+    let failed = FailedCourse::read_list(path)?;
+
+    let mut semesters = failed
+        .iter()
+        .filter_map(|f| Some((Season::from_str(&f.season).ok()?, Year(f.year))))
+        .collect::<Vec<_>>();
+    semesters.sort_by_key(|(season, year)| (**year, season.as_str().to_owned()));
+    semesters.dedup();
+
+    let mut course_objs = Vec::new();
+    for (season, year) in semesters {
+        course_objs.extend(
+            QueryCourseService::get_semester_course_objects(db, season, year)
+                .await?
+                .into_iter()
+                .filter(|course_obj| failed.iter().any(|f| f.matches(&course_obj.course))),
+        );
+    }
 
-    reservations_map
-        .into_iter()
-        .map(|(section, restrictions)| Reservation {
-            section,
-            restrictions,
-        })
-        .collect()
+    Ok((course_objs, failed))
 }
 
-/// Processes a course entry to get additional metadata for that course.
-///
-/// Makes an HTTP request to the courseDetails endpoint using the course number, season,
-/// and year. Parses the response HTML with `xpath` to extract additional data.
+/// Groups courses by the semester they are offered in
+fn group_by_semester(course_objs: Vec<CourseObject>) -> HashMap<(Season, Year), Vec<CourseObject>> {
+    let mut semesters: HashMap<(Season, Year), Vec<CourseObject>> = HashMap::new();
+    for course_obj in course_objs {
+        semesters
+            .entry((course_obj.course.season, course_obj.course.year))
+            .or_default()
+            .push(course_obj);
+    }
+    semesters
+}
+
+/// Saves each scraped semester in place of its stored courses, recording how the courses
+/// changed in the same transaction
 ///
 /// # Arguments
-/// * `course` - The course to process
+/// * `db` - The database connection
+/// * `scrape_run_id` - The ID of the current scrape run
+/// * `course_objs` - The scraped courses
+/// * `syllabus_map` - Syllabus URLs for the scraped components
+/// * `retried` - Whether the courses are only the retried courses of a previous run, so the
+///   semesters' other stored courses are left as they are
 ///
 /// # Returns
-/// The [`CourseObject`] containing the full course object, with the `metadata` field
-/// set to `Some(CourseMetadata)` if successful and `None` otherwise.
-fn process_course_details(course: CourseEntry) -> CourseObject {
-    // Create a new variable set with course, season, and year variables
-    let mut vars = VariableSet::new();
-    insert_variable(&mut vars, "course", &course.number.to_string());
-    insert_variable(&mut vars, "season", course.season.as_str());
-    insert_variable(&mut vars, "year", &course.year.to_string());
-
-    // Run the Hurl script to get course details
-    let result = match execute_hurl(COURSE_DETAILS_SCRIPT, &vars) {
-        Ok(result) if result.success => result,
-        Ok(_) => {
-            return CourseObject {
-                course,
-                metadata: None,
-            };
-        }
-        Err(e) => {
-            eprintln!(
-                "Failed to run course details script for course {} ({}{}): {e}",
-                course.number.as_full_string(),
-                course.season.as_str(),
-                course.year
+/// The number of changes recorded and a summary of each saved semester
+async fn save_semesters(
+    db: &DatabaseConnection,
+    scrape_run_id: Uuid,
+    course_objs: Vec<CourseObject>,
+    syllabus_map: &SyllabusMap,
+    retried: bool,
+) -> Result<(usize, Vec<SemesterSummary>), DbErr> {
+    let mut total_changes = 0;
+    let mut summaries = Vec::new();
+
+    for ((season, year), semester_objs) in group_by_semester(course_objs) {
+        summaries.push(SemesterSummary {
+            season: season.as_str().to_owned(),
+            year: *year as i16,
+            course_count: semester_objs.len(),
+        });
+
+        let semester_syllabi = syllabus_map
+            .iter()
+            .filter(|((y, s, _, _), _)| *y == year && *s == season)
+            .map(|(key, url)| (key.clone(), url.clone()))
+            .collect();
+
+        // The changes are recorded in the transaction saving the courses. Retried courses
+        // leave the rest of their semester as it is.
+        let run = Some(scrape_run_id);
+        let summary = if retried {
+            SaveCourseService::update_courses(
+                db,
+                season,
+                year,
+                semester_objs,
+                semester_syllabi,
+                run,
+            )
+            .await?
+        } else {
+            SaveCourseService::save_semester(db, season, year, semester_objs, semester_syllabi, run)
+                .await?
+        };
+        println!(
+            "Recorded {} changes for {}{}",
+            summary.changes,
+            season.as_str(),
+            year
+        );
+        total_changes += summary.changes;
+    }
+
+    Ok((total_changes, summaries))
+}
+
+/// Reads the syllabus registries of the given departments and semesters, reporting the
+/// registries and titles that could not be read
+async fn fetch_syllabi(
+    client: &CanvasClient,
+    semesters: &[(Season, Year)],
+    departments: &[Department],
+    concurrency: usize,
+) -> Result<SyllabusMap, CanvasError> {
+    println!("Fetching syllabi...");
+    let start = Instant::now();
+    let report = fetch_syllabus_map(client, semesters, departments, concurrency).await?;
+
+    println!(
+        "Found {} syllabi in {:?}",
+        report.syllabus_map.len(),
+        start.elapsed()
+    );
+    if !report.errors.is_empty() {
+        println!(
+            "Failed to read {} syllabus registries:",
+            report.errors.len()
+        );
+        for e in &report.errors {
+            println!(
+                "  {} {}{}: {}",
+                e.department,
+                e.season.as_str(),
+                e.year,
+                e.error
             );
-            return CourseObject {
-                course,
-                metadata: None,
-            };
         }
-    };
+    }
+    if !report.unparsed_titles.is_empty() {
+        println!(
+            "{} syllabi don't name a course section:",
+            report.unparsed_titles.len()
+        );
+        for title in &report.unparsed_titles {
+            println!("  {title}");
+        }
+    }
 
-    // Extract individual fields from result using get_capture_value
-    let special_permission = get_capture_value(&result, "special_permission")
-        .map(|v| matches!(v.to_string().trim().to_lowercase().as_str(), "yes"))
-        .unwrap_or_default();
-
-    // Create CourseMetadata with extracted information
-    let captures = get_captures(&result);
-    let raw_html = parse_from_raw_html(captures);
-
-    let metadata = CourseMetadata {
-        related_urls: parse_related_urls(&raw_html),
-        special_permission,
-        description: get_optional_string_value(&result, "description"),
-        prerequisites: get_parsed_struct_value(&result, "prerequisites"),
-        corequisites: get_parsed_struct_value(&result, "corequisites"),
-        crosslisted: get_parsed_struct_value(&result, "crosslisted"),
-        notes: get_optional_string_value(&result, "notes"),
-        reservations: parse_reservations(&raw_html),
-    };
+    Ok(report.syllabus_map)
+}
 
-    CourseObject {
-        course,
-        metadata: Some(metadata),
+/// Downloads syllabi into the archive, extracts their text, and records them in the database
+async fn archive(
+    db: &DatabaseConnection,
+    client: &CanvasClient,
+    syllabus_map: &SyllabusMap,
+    concurrency: usize,
+) -> Result<(), Box<dyn Error>> {
+    println!("Archiving syllabi...");
+    let start = Instant::now();
+    let store = SyllabusStore::from_env();
+    let archived_urls = SyllabusService::get_archived_urls(db).await?;
+
+    let report = archive_syllabi(client, &store, syllabus_map, &archived_urls, concurrency).await?;
+    println!(
+        "Downloaded {} syllabi to {} in {:?}",
+        report.files.len(),
+        store.root().display(),
+        start.elapsed()
+    );
+    if !report.errors.is_empty() {
+        println!("Failed to archive {} syllabi:", report.errors.len());
+        for e in &report.errors {
+            println!("  {}: {}", e.url, e.message);
+        }
+    }
+    if !report.unextracted.is_empty() {
+        println!(
+            "Failed to extract the text of {} syllabi:",
+            report.unextracted.len()
+        );
+        for url in &report.unextracted {
+            println!("  {url}");
+        }
     }
+
+    let saved = SyllabusService::save_archived(db, report.files, report.syllabi).await?;
+    println!("Recorded {saved} archived syllabi");
+    Ok(())
 }
 
-/// Orchestrates the scraping of course details
-#[tokio::main]
-async fn main() {
-    let overall_start = Instant::now();
+/// Writes the failed courses and diagnostics of a fetch to their report files
+fn write_reports(
+    output: &FetchOutput,
+    failed_report: &Path,
+    report: &Path,
+) -> Result<(), Box<dyn Error>> {
+    println!(
+        "Failed to fetch details for {} courses",
+        output.failed.len()
+    );
+    let file = File::create(failed_report)?;
+    to_writer_pretty(BufWriter::new(file), &output.failed)?;
+
+    println!("Found {} parse diagnostics", output.diagnostics.len());
+    for (category, count) in &output.diagnostics.counts {
+        println!("  {category:?}: {count}");
+    }
+    output.diagnostics.write_report(report)?;
 
-    // println!("Creating database connection...");
-    // let db = create_connection()
-    //     .await
-    //     .expect("Failed to connect to database");
-
-    println!("Starting data fetching...");
-
-    // let syllabus_future = task::spawn_blocking(|| {
-    //     println!("Fetching syllabus data...");
-    //     let start = Instant::now();
-    //     let result = create_syllabus_map();
-    //     println!("Syllabus data fetched in {:?}", start.elapsed());
-    //     result
-    // });
-
-    let course_objs_future = async {
-        println!("Fetching course data...");
-
-        let start = Instant::now();
-        let client = Client::new();
-
-        // Build futures for downloading each season's data
-        let futures = Season::all().into_iter().map(|season| {
-            let client = client.clone();
-            async move {
-                println!("Downloading data for season: {season:?}");
-                let url = format!(
-                    "https://enr-apps.as.cmu.edu/assets/SOC/sched_layout_{}.dat",
-                    season.as_full_str()
-                );
-
-                let text = client
-                    .get(&url)
-                    .send()
-                    .await
-                    .expect("Request failed")
-                    .text()
+    Ok(())
+}
+
+/// Fetches courses and writes them to the chosen output
+async fn run_fetch(args: FetchArgs) -> Result<(), Box<dyn Error>> {
+    // Only the database output and retries need a connection
+    let db = if args.output == Output::Db || args.retry_failed.is_some() {
+        println!("Creating database connection...");
+        Some(create_connection().await?)
+    } else {
+        None
+    };
+
+    let scrape_run_id = match (&db, args.output) {
+        (Some(db), Output::Db) => Some(ScrapeRunService::start_run(db).await?),
+        _ => None,
+    };
+
+    let retry = match (&db, &args.retry_failed) {
+        (Some(db), Some(path)) => Some(load_failed_courses(db, path).await?),
+        _ => None,
+    };
+
+    println!("Fetching course data...");
+    let output = args.source.fetch(retry).await?;
+    write_reports(&output, &args.failed_report, &args.report)?;
+
+    match args.output {
+        Output::Json => {
+            let path = args.out.unwrap_or_else(|| "course_objs.json".into());
+            write_json(&path, &output.course_objs)?;
+            println!(
+                "Wrote {} courses to {}",
+                output.course_objs.len(),
+                path.display()
+            );
+        }
+        Output::Ndjson => {
+            let path = args.out.unwrap_or_else(|| "course_objs.ndjson".into());
+            write_ndjson(&path, &output.course_objs)?;
+            println!(
+                "Wrote {} courses to {}",
+                output.course_objs.len(),
+                path.display()
+            );
+        }
+        Output::Db => {
+            let (Some(db), Some(scrape_run_id)) = (&db, scrape_run_id) else {
+                unreachable!("the database output always connects and starts a run");
+            };
+
+            // Courses are still worth saving without their syllabi
+            let client = if args.skip_syllabi {
+                None
+            } else {
+                CanvasClient::from_env(&args.source.options())
+                    .inspect_err(|e| eprintln!("Skipping syllabi: {e}"))
+                    .ok()
+            };
+
+            let syllabus_map = match &client {
+                Some(client) => {
+                    let mut semesters = group_by_semester(output.course_objs.clone())
+                        .into_keys()
+                        .collect::<Vec<_>>();
+                    semesters.sort_by_key(|(season, year)| (**year, season.as_str().to_owned()));
+
+                    fetch_syllabi(
+                        client,
+                        &semesters,
+                        &Department::all(),
+                        args.source.concurrency,
+                    )
                     .await
-                    .expect("Failed to read body");
+                    .unwrap_or_else(|e| {
+                        eprintln!("Skipping syllabi: {e}");
+                        SyllabusMap::new()
+                    })
+                }
+                None => SyllabusMap::new(),
+            };
 
-                println!("Downloaded {} bytes for season {:?}", text.len(), season);
-                (season, text)
+            println!("Starting database save operation...");
+            let save_start = Instant::now();
+
+            // Record what changed since the last run, then save courses to the database
+            let (change_count, semesters) = save_semesters(
+                db,
+                scrape_run_id,
+                output.course_objs,
+                &syllabus_map,
+                args.retry_failed.is_some(),
+            )
+            .await?;
+            println!(
+                "Successfully saved courses and {change_count} changes to database in {:?}",
+                save_start.elapsed()
+            );
+
+            if let Some(client) = client.filter(|_| !args.skip_archive)
+                && let Err(e) = archive(db, &client, &syllabus_map, args.source.concurrency).await
+            {
+                eprintln!("Skipping syllabus archive: {e}");
             }
-        });
 
-        // Download all and parse in parallel
-        let raw_data = join_all(futures).await;
-        println!("All season data downloaded in {:?}", start.elapsed());
-
-        println!("Parsing course data...");
-        let parse_start = Instant::now();
-
-        let course_entries = raw_data
-            .into_par_iter()
-            .map(|(season, text)| {
-                let year = extract_year(&text)
-                    .unwrap_or_else(|| panic!("Failed to extract year for {season:?}"));
-                let lines = first_pass(&text);
-                let courses = second_pass(lines, season, year);
-
-                println!(
-                    "Parsed {} courses for {} {}",
-                    courses.len(),
-                    season.as_str(),
-                    year
-                );
-                courses
-            })
-            .flatten()
-            .collect::<Vec<_>>();
+            let summary = ScrapeRunSummary {
+                semesters,
+                detail_failures: output
+                    .failed
+                    .iter()
+                    .map(|f| format!("{} {}{:02}", f.number, f.season, f.year % 100))
+                    .collect(),
+                diagnostics: serde_json::to_value(&output.diagnostics)?,
+            };
+            ScrapeRunService::finish_run(db, scrape_run_id, summary).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Imports Faculty Course Evaluations into the database
+async fn run_import_fce(file: &Path) -> Result<(), Box<dyn Error>> {
+    let (records, skipped) = read_evaluations(File::open(file)?)?;
+    println!(
+        "Read {} evaluations, skipped {skipped} with an unknown semester",
+        records.len()
+    );
+
+    let db = create_connection().await?;
+    let summary = EvaluationService::import_evaluations(&db, records).await?;
 
+    println!("Imported {} evaluations", summary.imported);
+    if !summary.unmatched.is_empty() {
         println!(
-            "Parsed {} total courses in {:?}",
-            course_entries.len(),
-            parse_start.elapsed()
+            "{} evaluations have no matching component:",
+            summary.unmatched.len()
         );
+        for unmatched in &summary.unmatched {
+            println!("  {unmatched}");
+        }
+    }
 
-        println!("Processing course details...");
-        let details_start = Instant::now();
+    Ok(())
+}
 
-        let total_courses = course_entries.len();
-        let progress = AtomicUsize::new(0);
+/// Imports syllabus URLs for the stored components, from a CSV file if one is given and from
+/// the Canvas syllabus registries otherwise, then archives the syllabi
+async fn run_import_syllabi(
+    file: Option<&Path>,
+    semesters: &[Semester],
+    departments: &[Department],
+    concurrency: usize,
+    skip_archive: bool,
+) -> Result<(), Box<dyn Error>> {
+    // A CSV import can still be archived if its URLs point at Canvas
+    let client = match file {
+        Some(_) => CanvasClient::from_env(&FetchOptions::default()).ok(),
+        None => Some(CanvasClient::from_env(&FetchOptions::default())?),
+    };
 
-        let course_objs = course_entries
-            .into_par_iter()
-            .map(|course| {
-                let obj = process_course_details(course);
-                let done = progress.fetch_add(1, Ordering::Relaxed) + 1;
-                if done % 100 == 0 || done == total_courses {
-                    println!("Processed {done}/{total_courses} courses...");
-                }
-                obj
-            })
-            .collect::<Vec<_>>();
+    let syllabus_map = match (file, &client) {
+        (Some(file), _) => {
+            let (syllabus_map, skipped) = read_syllabus_csv(File::open(file)?)?;
+            println!(
+                "Read {} syllabi, skipped {skipped} with an unknown season",
+                syllabus_map.len()
+            );
+            syllabus_map
+        }
+        (None, client) => {
+            let client = client.as_ref().expect("Reading Canvas requires a client");
+            let semesters = semesters
+                .iter()
+                .map(|semester| (semester.season, semester.year))
+                .collect::<Vec<_>>();
+            let departments = if departments.is_empty() {
+                Department::all()
+            } else {
+                departments.to_vec()
+            };
 
-        println!("Processed course details in {:?}", details_start.elapsed());
+            fetch_syllabi(client, &semesters, &departments, concurrency).await?
+        }
+    };
+
+    let db = create_connection().await?;
+    let updated = SaveCourseService::set_syllabus_urls(&db, &syllabus_map).await?;
+
+    println!("Set the syllabus of {updated} components");
+
+    match client {
+        _ if skip_archive => {}
+        Some(client) => archive(&db, &client, &syllabus_map, concurrency).await?,
+        None => println!("Not archiving syllabi: {}", CanvasError::MissingToken),
+    }
+
+    Ok(())
+}
+
+/// Saves a snapshot of courses to the database
+async fn run_load(file: &Path) -> Result<(), Box<dyn Error>> {
+    let course_objs = read_snapshot(file)?;
+    println!("Read {} courses from {}", course_objs.len(), file.display());
+
+    let db = create_connection().await?;
+    for ((season, year), semester_objs) in group_by_semester(course_objs) {
+        SaveCourseService::save_semester(
+            &db,
+            season,
+            year,
+            semester_objs,
+            SyllabusMap::new(),
+            None,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Saves the archived SOC files in a directory as past semesters, skipping the semesters that
+/// are already stored unless `overwrite` is set
+async fn run_backfill(dir: &Path, overwrite: bool, report: &Path) -> Result<(), Box<dyn Error>> {
+    let files = find_archived_files(dir)?;
+    println!("Found {} archived files in {}", files.len(), dir.display());
+
+    let db = create_connection().await?;
+    let stored = QueryCourseService::get_semesters(&db).await?;
+
+    let mut diagnostics = Diagnostics::new();
+    for file in files {
+        let semester = file.semester;
+        if !overwrite && stored.contains(&(semester.season, semester.year)) {
+            println!("Skipping {semester}: already stored");
+            continue;
+        }
+
+        let output = read_archived_file(&file)?;
+        diagnostics.extend(output.diagnostics.diagnostics);
+
+        SaveCourseService::save_semester(
+            &db,
+            semester.season,
+            semester.year,
+            output.course_objs,
+            SyllabusMap::new(),
+            None,
+        )
+        .await?;
+    }
+
+    println!("Found {} parse diagnostics", diagnostics.len());
+    for (category, count) in &diagnostics.counts {
+        println!("  {category:?}: {count}");
+    }
+    diagnostics.write_report(report)?;
+    println!("Wrote diagnostics report to {}", report.display());
+
+    Ok(())
+}
+
+/// Fetches courses and writes how they differ from the stored courses
+async fn run_diff(source: SourceArgs, out: &Path) -> Result<(), Box<dyn Error>> {
+    let db = create_connection().await?;
+    let output = source.fetch(None).await?;
+
+    let mut changes: Vec<CourseChange> = Vec::new();
+    for ((season, year), semester_objs) in group_by_semester(output.course_objs) {
+        let stored = QueryCourseService::get_semester_course_objects(&db, season, year).await?;
+        let semester_changes = diff_courses(&stored, &semester_objs);
+
+        println!(
+            "{} changes for {}{}",
+            semester_changes.len(),
+            season.as_str(),
+            year
+        );
+        changes.extend(semester_changes);
+    }
+
+    for change in &changes {
         println!(
-            "Total course data fetching completed in {:?}",
-            start.elapsed()
+            "  {} {}{} {}{}: {} -> {}",
+            change.number,
+            change.season.as_str(),
+            change.year,
+            change.kind.as_str(),
+            change
+                .section
+                .as_ref()
+                .map(|section| format!(" ({section})"))
+                .unwrap_or_default(),
+            change.old_value.as_deref().unwrap_or("-"),
+            change.new_value.as_deref().unwrap_or("-"),
         );
+    }
+
+    to_writer_pretty(BufWriter::new(File::create(out)?), &changes)?;
+    println!("Wrote {} changes to {}", changes.len(), out.display());
+    Ok(())
+}
+
+/// Fetches courses and reports every parse diagnostic
+async fn run_validate(source: SourceArgs, report: &Path) -> Result<(), Box<dyn Error>> {
+    let output = source.fetch(None).await?;
+
+    println!("Found {} parse diagnostics", output.diagnostics.len());
+    for (category, count) in &output.diagnostics.counts {
+        println!("  {category:?}: {count}");
+    }
+    output.diagnostics.write_report(report)?;
+    println!("Wrote diagnostics report to {}", report.display());
+
+    if !output.failed.is_empty() {
+        return Err(format!(
+            "Failed to fetch details for {} courses",
+            output.failed.len()
+        )
+        .into());
+    }
+    if !output.diagnostics.is_empty() {
+        return Err(format!("Found {} parse diagnostics", output.diagnostics.len()).into());
+    }
+
+    Ok(())
+}
 
-        course_objs
+#[tokio::main]
+async fn main() {
+    let overall_start = Instant::now();
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Fetch(args) => run_fetch(args).await,
+        Command::ImportFce { file } => run_import_fce(&file).await,
+        Command::ImportSyllabi {
+            file,
+            semesters,
+            departments,
+            concurrency,
+            skip_archive,
+        } => {
+            run_import_syllabi(
+                file.as_deref(),
+                &semesters,
+                &departments,
+                concurrency,
+                skip_archive,
+            )
+            .await
+        }
+        Command::Load { file } => run_load(&file).await,
+        Command::Backfill {
+            dir,
+            overwrite,
+            report,
+        } => run_backfill(&dir, overwrite, &report).await,
+        Command::Diff { source, out } => run_diff(source, &out).await,
+        Command::Validate { source, report } => run_validate(source, &report).await,
     };
 
-    // Fetch syllabi and course objects concurrently
-    // println!("Waiting for syllabus and course data...");
-    // let (syllabus_result, course_objs) = join!(syllabus_future, course_objs_future);
-    // let syllabus_map = syllabus_result.expect("create_syllabus_map panicked");
-
-    let course_objs = course_objs_future.await;
-    // println!("Starting database save operation...");
-    let save_start = Instant::now();
-
-    // Save courses to the database
-    // match SaveCourseService::save_courses(&db, course_objs, syllabus_map).await {
-    //     Ok(course_ids) => {
-    //         println!(
-    //             "Successfully saved {} courses to database in {:?}",
-    //             course_ids.len(),
-    //             save_start.elapsed()
-    //         );
-    //     }
-    //     Err(e) => {
-    //         eprintln!("Failed to save courses to database: {e}");
-    //         std::process::exit(1);
-    //     }
-    // }
-
-    let file = File::create("course_objs.json").expect("create json");
-    let writer = BufWriter::new(file);
-    to_writer_pretty(writer, &course_objs).expect("write json");
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
 
     println!("Total operation completed in {:?}", overall_start.elapsed());
 }
@@ -0,0 +1,101 @@
+use models::course_data::CourseObject;
+use serde_json::to_writer_pretty;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Result as IoResult, Write},
+    path::Path,
+};
+
+/// Writes courses as a pretty-printed JSON array
+///
+/// # Arguments
+/// * `path` - The file to write the courses to
+/// * `course_objs` - The courses to write
+pub fn write_json(path: impl AsRef<Path>, course_objs: &[CourseObject]) -> IoResult<()> {
+    let writer = BufWriter::new(File::create(path)?);
+    to_writer_pretty(writer, course_objs)?;
+    Ok(())
+}
+
+/// Writes courses as newline-delimited JSON, one course per line
+///
+/// # Arguments
+/// * `path` - The file to write the courses to
+/// * `course_objs` - The courses to write
+pub fn write_ndjson(path: impl AsRef<Path>, course_objs: &[CourseObject]) -> IoResult<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for course_obj in course_objs {
+        serde_json::to_writer(&mut writer, course_obj)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+/// Reads a snapshot written by [`write_json`] or [`write_ndjson`]
+///
+/// Files ending in `.ndjson` or `.jsonl` are read as newline-delimited JSON and everything else
+/// as a JSON array.
+///
+/// # Arguments
+/// * `path` - The snapshot to read
+///
+/// # Returns
+/// The courses in the order they were written
+pub fn read_snapshot(path: impl AsRef<Path>) -> IoResult<Vec<CourseObject>> {
+    let path = path.as_ref();
+    let reader = BufReader::new(File::open(path)?);
+
+    let is_ndjson = path
+        .extension()
+        .is_some_and(|ext| ext == "ndjson" || ext == "jsonl");
+    if !is_ndjson {
+        return Ok(serde_json::from_reader(reader)?);
+    }
+
+    let mut course_objs = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            course_objs.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(course_objs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::courses::{first_pass::first_pass, second_pass::second_pass};
+    use models::syllabus_data::{Season, Year};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let soc = format!(
+            "{}\t15122\tPrinciples of Imperative Computation\n\
+             \t\t\t12.0\tLec 1\tTR\t09:30AM\t10:50AM\tGHC 4401\tPittsburgh, Pennsylvania\tCervesato\n",
+            "\n".repeat(11)
+        );
+        let course_objs = second_pass(first_pass(&soc), Season::Fall, Year(2025))
+            .into_iter()
+            .map(|course| CourseObject {
+                course,
+                metadata: None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(course_objs.len(), 1);
+
+        let dir = std::env::temp_dir().join(format!("snapshot-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let json = dir.join("course_objs.json");
+        write_json(&json, &course_objs).unwrap();
+        assert_eq!(read_snapshot(&json).unwrap(), course_objs);
+
+        let ndjson = dir.join("course_objs.ndjson");
+        write_ndjson(&ndjson, &course_objs).unwrap();
+        assert_eq!(read_snapshot(&ndjson).unwrap(), course_objs);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,527 @@
+use models::{
+    course_data::CourseEntry,
+    syllabus_data::{Season, Year},
+};
+use reqwest::{Client, StatusCode, Url, header::RETRY_AFTER};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{
+    sync::Mutex,
+    time::{Instant, sleep, sleep_until},
+};
+
+/// Base URL of the schedule of classes data files
+const SOC_BASE_URL: &str = "https://enr-apps.as.cmu.edu/assets/SOC";
+
+/// Base URL of the course details endpoint
+const COURSE_DETAILS_URL: &str =
+    "https://enr-apps.andrew.cmu.edu/open/SOC/SOCServlet/courseDetails";
+
+/// Builds the URL of a season's schedule of classes data file
+pub fn soc_url(season: Season) -> String {
+    format!("{SOC_BASE_URL}/sched_layout_{}.dat", season.as_full_str())
+}
+
+/// Builds the URL of a course's details page
+pub fn course_details_url(course: &CourseEntry) -> String {
+    course_details_url_for(&course.number.to_string(), course.season, course.year)
+}
+
+/// Builds the URL of a course's details page from its number and semester
+pub fn course_details_url_for(number: &str, season: Season, year: Year) -> String {
+    format!(
+        "{COURSE_DETAILS_URL}?COURSE={number}&SEMESTER={}{year}",
+        season.as_str()
+    )
+}
+
+/// Error returned when a [`Source`] cannot produce a response
+#[derive(Debug)]
+pub enum SourceError {
+    /// The HTTP request failed or returned a non-success status
+    Http {
+        url: String,
+        message: String,
+        /// Whether retrying the request might succeed
+        transient: bool,
+    },
+    /// A recorded response could not be read or written
+    Io { path: PathBuf, error: io::Error },
+    /// Replay mode was asked for a response that was never recorded
+    NotRecorded { url: String, path: PathBuf },
+}
+
+impl Display for SourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Http { url, message, .. } => write!(f, "Request to {url} failed: {message}"),
+            Self::Io { path, error } => write!(f, "Failed to access {}: {error}", path.display()),
+            Self::NotRecorded { url, path } => {
+                write!(f, "No recorded response for {url} at {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SourceError {}
+
+/// Somewhere the datafetcher can get raw responses from
+pub trait Source: Sync {
+    /// Fetches the body of the response at `url`
+    ///
+    /// # Arguments
+    /// * `url` - The URL to fetch
+    ///
+    /// # Returns
+    /// The response body as text, or a [`SourceError`]
+    fn fetch(&self, url: &str) -> impl Future<Output = Result<String, SourceError>> + Send;
+}
+
+/// Options controlling how the datafetcher talks to CMU's servers
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchOptions {
+    /// Maximum number of courseDetails pages fetched at the same time
+    pub concurrency: usize,
+    /// Maximum number of requests per second sent to each host
+    pub requests_per_second: f64,
+    /// Number of times a transient failure is retried before giving up
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled after every attempt
+    pub initial_backoff: Duration,
+    /// Time limit for a single request
+    pub timeout: Duration,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 16,
+            requests_per_second: 10.0,
+            max_retries: 4,
+            initial_backoff: Duration::from_millis(500),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Longest time to wait between two attempts of the same request
+pub(crate) const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Computes how long to wait before retrying a request
+///
+/// # Arguments
+/// * `initial` - The delay before the first retry
+/// * `attempt` - The number of attempts made so far, starting at 1
+///
+/// # Returns
+/// `initial * 2^(attempt - 1)`, capped at [`MAX_BACKOFF`]
+pub fn backoff_delay(initial: Duration, attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    initial.saturating_mul(factor).min(MAX_BACKOFF)
+}
+
+/// Whether a response status is worth retrying (rate limiting or a server error)
+pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Spaces out requests to each host so none receives more than a fixed rate
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter allowing `requests_per_second` requests to each host
+    ///
+    /// A rate of zero or less disables the limit, which the CLI never allows.
+    pub fn new(requests_per_second: f64) -> Self {
+        let interval = if requests_per_second > 0.0 {
+            Duration::from_secs_f64(1.0 / requests_per_second)
+        } else {
+            Duration::ZERO
+        };
+
+        Self {
+            interval,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Waits until a request to `host` is allowed
+    pub async fn wait(&self, host: &str) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let now = Instant::now();
+
+            let slot = next_slot.get(host).map_or(now, |&next| next.max(now));
+            next_slot.insert(host.to_owned(), slot + self.interval);
+            slot
+        };
+
+        sleep_until(slot).await;
+    }
+}
+
+/// Fetches responses from the live CMU servers, with a per-host rate limit, a timeout, and
+/// exponential-backoff retries for transient failures
+#[derive(Debug)]
+pub struct LiveSource {
+    client: Client,
+    rate_limiter: RateLimiter,
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+impl LiveSource {
+    pub fn new(options: &FetchOptions) -> Self {
+        let client = Client::builder()
+            .timeout(options.timeout)
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            client,
+            rate_limiter: RateLimiter::new(options.requests_per_second),
+            max_retries: options.max_retries,
+            initial_backoff: options.initial_backoff,
+        }
+    }
+
+    /// Sends a single request, returning the body and how long the server asked us to wait
+    /// before retrying, if it did
+    async fn fetch_once(&self, url: &str) -> Result<String, (SourceError, Option<Duration>)> {
+        let http_error = |e: reqwest::Error| {
+            let transient = e.is_timeout() || e.is_connect() || e.is_request() || e.is_body();
+            let error = SourceError::Http {
+                url: url.to_owned(),
+                message: e.to_string(),
+                transient,
+            };
+            (error, None)
+        };
+
+        if let Some(host) = Url::parse(url).ok().as_ref().and_then(Url::host_str) {
+            self.rate_limiter.wait(host).await;
+        }
+
+        let response = self.client.get(url).send().await.map_err(http_error)?;
+        let status = response.status();
+
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs);
+
+            let error = SourceError::Http {
+                url: url.to_owned(),
+                message: format!("HTTP status {status}"),
+                transient: is_transient_status(status),
+            };
+            return Err((error, retry_after));
+        }
+
+        response.text().await.map_err(http_error)
+    }
+}
+
+impl Source for LiveSource {
+    async fn fetch(&self, url: &str) -> Result<String, SourceError> {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match self.fetch_once(url).await {
+                Ok(body) => return Ok(body),
+                Err((error, retry_after)) => {
+                    let transient = matches!(
+                        error,
+                        SourceError::Http {
+                            transient: true,
+                            ..
+                        }
+                    );
+                    if !transient || attempt > self.max_retries {
+                        return Err(error);
+                    }
+
+                    // Honor the server's Retry-After if it asks for longer than our backoff
+                    let delay = backoff_delay(self.initial_backoff, attempt)
+                        .max(retry_after.unwrap_or_default())
+                        .min(MAX_BACKOFF);
+
+                    eprintln!("{error}, retrying in {delay:?} (attempt {attempt})");
+                    sleep(delay).await;
+                }
+            }
+        }
+    }
+}
+
+/// Maps a URL to the file its response is recorded in
+///
+/// # Arguments
+/// * `dir` - The recording directory
+/// * `url` - The URL of the response
+///
+/// # Returns
+/// The path of the recording, named after the URL without its scheme
+pub fn recording_path(dir: &Path, url: &str) -> PathBuf {
+    let name: String = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    dir.join(name)
+}
+
+/// Wraps another source and saves every response it returns to a directory
+#[derive(Debug, Clone)]
+pub struct RecordingSource<S> {
+    inner: S,
+    dir: PathBuf,
+}
+
+impl<S: Source> RecordingSource<S> {
+    /// Creates a recording source, creating `dir` if it doesn't exist
+    pub fn new(inner: S, dir: impl Into<PathBuf>) -> Result<Self, SourceError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|error| SourceError::Io {
+            path: dir.clone(),
+            error,
+        })?;
+
+        Ok(Self { inner, dir })
+    }
+}
+
+impl<S: Source> Source for RecordingSource<S> {
+    async fn fetch(&self, url: &str) -> Result<String, SourceError> {
+        let body = self.inner.fetch(url).await?;
+
+        let path = recording_path(&self.dir, url);
+        tokio::fs::write(&path, &body)
+            .await
+            .map_err(|error| SourceError::Io { path, error })?;
+
+        Ok(body)
+    }
+}
+
+/// Serves responses previously saved by a [`RecordingSource`], without touching the network
+#[derive(Debug, Clone)]
+pub struct ReplaySource {
+    dir: PathBuf,
+}
+
+impl ReplaySource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl Source for ReplaySource {
+    async fn fetch(&self, url: &str) -> Result<String, SourceError> {
+        let path = recording_path(&self.dir, url);
+
+        match tokio::fs::read_to_string(&path).await {
+            Ok(body) => Ok(body),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                Err(SourceError::NotRecorded {
+                    url: url.to_owned(),
+                    path,
+                })
+            }
+            Err(error) => Err(SourceError::Io { path, error }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    /// Serves a fixed body for every URL
+    struct FixedSource(&'static str);
+
+    impl Source for FixedSource {
+        async fn fetch(&self, _url: &str) -> Result<String, SourceError> {
+            Ok(self.0.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let initial = Duration::from_millis(500);
+
+        assert_eq!(backoff_delay(initial, 1), Duration::from_millis(500));
+        assert_eq!(backoff_delay(initial, 2), Duration::from_secs(1));
+        assert_eq!(backoff_delay(initial, 4), Duration::from_secs(4));
+        assert_eq!(backoff_delay(initial, 30), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_transient_status() {
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_transient_status(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter() {
+        let limiter = RateLimiter::new(20.0);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.wait("enr-apps.as.cmu.edu").await;
+        }
+
+        // Three requests to one host need two 50ms gaps
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        // Another host has its own budget
+        let start = Instant::now();
+        limiter.wait("example.com").await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    /// A live source that retries quickly, so tests don't wait on the default backoff
+    fn quick_live_source() -> LiveSource {
+        LiveSource::new(&FetchOptions {
+            requests_per_second: 1000.0,
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(10),
+            ..FetchOptions::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_live_source_retries_transient_status() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/soc"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/soc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("schedule"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let source = quick_live_source();
+        let body = source.fetch(&format!("{}/soc", server.uri())).await;
+        assert_eq!(body.unwrap(), "schedule");
+    }
+
+    #[tokio::test]
+    async fn test_live_source_does_not_retry_not_found() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let source = quick_live_source();
+        let result = source.fetch(&format!("{}/missing", server.uri())).await;
+        assert!(matches!(
+            result,
+            Err(SourceError::Http {
+                transient: false,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_live_source_honors_retry_after() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/soc"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/soc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("schedule"))
+            .mount(&server)
+            .await;
+
+        let source = quick_live_source();
+        let start = Instant::now();
+        let body = source.fetch(&format!("{}/soc", server.uri())).await;
+
+        // The server's one second wins over the 10ms backoff
+        assert_eq!(body.unwrap(), "schedule");
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_recording_path() {
+        let path = recording_path(Path::new("rec"), &soc_url(Season::Fall));
+        assert_eq!(
+            path,
+            Path::new("rec").join("enr-apps.as.cmu.edu_assets_SOC_sched_layout_fall.dat")
+        );
+
+        let path = recording_path(
+            Path::new("rec"),
+            &course_details_url_for("15122", Season::Spring, Year(2025)),
+        );
+        assert_eq!(
+            path.file_name().unwrap(),
+            "enr-apps.andrew.cmu.edu_open_SOC_SOCServlet_courseDetails_COURSE_15122_SEMESTER_S25"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let dir = std::env::temp_dir().join(format!("datafetcher-{}", Uuid::new_v4()));
+        let url = soc_url(Season::Fall);
+
+        let recorder = RecordingSource::new(FixedSource("recorded body"), &dir).unwrap();
+        assert_eq!(recorder.fetch(&url).await.unwrap(), "recorded body");
+
+        let replay = ReplaySource::new(&dir);
+        assert_eq!(replay.fetch(&url).await.unwrap(), "recorded body");
+        assert!(matches!(
+            replay.fetch(&soc_url(Season::Spring)).await,
+            Err(SourceError::NotRecorded { .. })
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,554 @@
+use crate::{
+    canvas::{CanvasClient, CanvasError},
+    extract::extract_text,
+};
+use database::{
+    archive::SyllabusStore,
+    services::syllabus::{ArchivedFile, ArchivedSyllabus},
+};
+use futures::{StreamExt, stream};
+use lazy_static::lazy_static;
+use models::syllabus_data::{Department, Season, SyllabusMap, Year};
+use regex::Regex;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    str::FromStr,
+};
+
+lazy_static! {
+    /// A course number followed by a section or lecture code, as in "15122 A", "15-122_Lec 1",
+    /// or "F25-15122-A2.pdf"
+    static ref SYLLABUS_TITLE: Regex = Regex::new(
+        r"(?:^|\D)(\d{2})-?(\d{3})[\s_:-]+(?:(?i:lec(?:ture)?)\s*(\d+)|([A-Z][A-Z0-9]?))(?:[^A-Za-z0-9]|$)"
+    )
+    .unwrap();
+}
+
+/// A row of a syllabus CSV file
+#[derive(Debug, Deserialize)]
+struct SyllabusRow {
+    /// Season code (e.g., "F")
+    season: String,
+    /// Full year (e.g., 2025)
+    year: u16,
+    /// Course number without a dash (e.g., "15122")
+    number: String,
+    /// Component code (e.g., "Lec 1")
+    section: String,
+    url: String,
+}
+
+/// Reads syllabus URLs from a CSV file with `season,year,number,section,url` columns
+///
+/// # Arguments
+/// * `reader` - The CSV data
+///
+/// # Returns
+/// The [`SyllabusMap`] and the number of rows skipped because of an unknown season, or a
+/// [`csv::Error`] if the file is malformed
+pub fn read_syllabus_csv(reader: impl Read) -> Result<(SyllabusMap, usize), csv::Error> {
+    let mut syllabus_map = SyllabusMap::new();
+    let mut skipped = 0;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    for row in reader.deserialize::<SyllabusRow>() {
+        let row = row?;
+        let Ok(season) = Season::from_str(&row.season.to_uppercase()) else {
+            skipped += 1;
+            continue;
+        };
+
+        syllabus_map.insert(
+            (
+                Year(row.year),
+                season,
+                row.number.replace('-', ""),
+                row.section,
+            ),
+            row.url,
+        );
+    }
+
+    Ok((syllabus_map, skipped))
+}
+
+/// Extracts the course number and component code from the title of a syllabus
+///
+/// # Arguments
+/// * `title` - The title of the syllabus registry item
+///
+/// # Returns
+/// The course number without a dash and the component code (e.g., "Lec 1" or "A"), or `None`
+/// if the title doesn't name a section
+pub fn parse_syllabus_title(title: &str) -> Option<(String, String)> {
+    let captures = SYLLABUS_TITLE.captures(title)?;
+    let number = format!("{}{}", &captures[1], &captures[2]);
+
+    let code = match (captures.get(3), captures.get(4)) {
+        (Some(lecture), _) => format!("Lec {}", lecture.as_str()),
+        (None, Some(section)) => section.as_str().to_owned(),
+        (None, None) => return None,
+    };
+
+    Some((number, code))
+}
+
+/// A syllabus registry that could not be read
+#[derive(Debug)]
+pub struct RegistryError {
+    pub department: Department,
+    pub season: Season,
+    pub year: Year,
+    pub error: CanvasError,
+}
+
+/// Everything produced by reading the syllabus registries
+#[derive(Debug, Default)]
+pub struct SyllabusReport {
+    /// Syllabus URLs keyed by year, season, course number, and component code
+    pub syllabus_map: SyllabusMap,
+    /// Registries that could not be read
+    pub errors: Vec<RegistryError>,
+    /// Titles that don't name a course section
+    pub unparsed_titles: Vec<String>,
+}
+
+/// Reads the syllabus registry of every department for the given semesters
+///
+/// A registry that fails is reported in [`SyllabusReport::errors`] without stopping the others,
+/// except when Canvas rejects the access token, which would fail every request.
+///
+/// # Arguments
+/// * `client` - The Canvas client to use
+/// * `semesters` - The semesters to read
+/// * `departments` - The departments whose registries to read
+/// * `concurrency` - Maximum number of registries read at the same time
+///
+/// # Returns
+/// A [`SyllabusReport`], or a [`CanvasError`] if the access token was rejected
+pub async fn fetch_syllabus_map(
+    client: &CanvasClient,
+    semesters: &[(Season, Year)],
+    departments: &[Department],
+    concurrency: usize,
+) -> Result<SyllabusReport, CanvasError> {
+    let registries = semesters.iter().flat_map(|&(season, year)| {
+        departments
+            .iter()
+            .map(move |&department| (season, year, department))
+    });
+
+    let results: Vec<_> = stream::iter(registries)
+        .map(|(season, year, department)| async move {
+            let result = client.syllabus_files(season, year, department).await;
+            (season, year, department, result)
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut report = SyllabusReport::default();
+
+    for (season, year, department, result) in results {
+        let files = match result {
+            Ok(files) => files,
+            Err(error @ CanvasError::Unauthorized { .. }) => return Err(error),
+            Err(error) => {
+                report.errors.push(RegistryError {
+                    department,
+                    season,
+                    year,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        for file in files {
+            match parse_syllabus_title(&file.title) {
+                Some((number, code)) => {
+                    report
+                        .syllabus_map
+                        .insert((year, season, number, code), file.url);
+                }
+                None => report.unparsed_titles.push(file.title),
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// A syllabus that could not be archived
+#[derive(Debug)]
+pub struct ArchiveError {
+    pub url: String,
+    pub message: String,
+}
+
+/// Everything produced by archiving syllabi
+#[derive(Debug, Default)]
+pub struct ArchiveReport {
+    /// Files that were downloaded and stored
+    pub files: Vec<ArchivedFile>,
+    /// Syllabi whose file is archived, including ones archived by earlier runs
+    pub syllabi: Vec<ArchivedSyllabus>,
+    /// Syllabi that could not be downloaded or stored
+    pub errors: Vec<ArchiveError>,
+    /// URLs of files that were stored but whose text could not be extracted
+    pub unextracted: Vec<String>,
+}
+
+/// Downloads a syllabus, stores it, and extracts its text
+///
+/// # Returns
+/// The [`ArchivedFile`] and whether its text could be extracted, an error message if it could
+/// not be stored, or a [`CanvasError`] if it could not be downloaded
+async fn archive_file(
+    client: &CanvasClient,
+    store: &SyllabusStore,
+    url: &str,
+) -> Result<Result<(ArchivedFile, bool), String>, CanvasError> {
+    let bytes = client.download(url).await?;
+
+    // Hashing, writing, and parsing PDFs are too slow to run on the async runtime
+    let store = store.clone();
+    let stored = tokio::task::spawn_blocking(move || {
+        let hash = store.put(&bytes).map_err(|e| e.to_string())?;
+        let (format, text, extracted) = match extract_text(&bytes) {
+            Ok((format, text)) => (format, text, true),
+            Err(e) => (e.format, String::new(), false),
+        };
+
+        let file = ArchivedFile {
+            hash,
+            content_type: format.content_type().to_owned(),
+            size_bytes: bytes.len() as i64,
+            text,
+        };
+        Ok((file, extracted))
+    })
+    .await;
+
+    Ok(stored.unwrap_or_else(|e| Err(e.to_string())))
+}
+
+/// Downloads every syllabus in a [`SyllabusMap`] into the archive and extracts its text
+///
+/// Syllabi whose URL was archived before aren't downloaded again, and a URL shared by several
+/// components is only downloaded once.
+///
+/// # Arguments
+/// * `client` - The Canvas client to download with
+/// * `store` - The store to save the files in
+/// * `syllabus_map` - The syllabi to archive
+/// * `archived_urls` - Hashes of previously archived files, keyed by source URL
+/// * `concurrency` - Maximum number of files downloaded at the same time
+///
+/// # Returns
+/// An [`ArchiveReport`], or a [`CanvasError`] if the access token was rejected
+pub async fn archive_syllabi(
+    client: &CanvasClient,
+    store: &SyllabusStore,
+    syllabus_map: &SyllabusMap,
+    archived_urls: &HashMap<String, String>,
+    concurrency: usize,
+) -> Result<ArchiveReport, CanvasError> {
+    let mut report = ArchiveReport::default();
+
+    let mut keys_by_url: BTreeMap<&str, Vec<&(Year, Season, String, String)>> = BTreeMap::new();
+    for (key, url) in syllabus_map {
+        keys_by_url.entry(url).or_default().push(key);
+    }
+
+    let to_download = keys_by_url
+        .keys()
+        .copied()
+        .filter(|url| !archived_urls.contains_key(*url));
+    let results: Vec<_> = stream::iter(to_download)
+        .map(|url| async move { (url, archive_file(client, store, url).await) })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut hashes: HashMap<&str, String> = archived_urls
+        .iter()
+        .map(|(url, hash)| (url.as_str(), hash.clone()))
+        .collect();
+
+    for (url, result) in results {
+        let message = match result {
+            Ok(Ok((file, extracted))) => {
+                if !extracted {
+                    report.unextracted.push(url.to_owned());
+                }
+                hashes.insert(url, file.hash.clone());
+                report.files.push(file);
+                continue;
+            }
+            Ok(Err(message)) => message,
+            Err(error @ CanvasError::Unauthorized { .. }) => return Err(error),
+            Err(error) => error.to_string(),
+        };
+
+        report.errors.push(ArchiveError {
+            url: url.to_owned(),
+            message,
+        });
+    }
+
+    for (url, keys) in keys_by_url {
+        let Some(hash) = hashes.get(url) else {
+            continue;
+        };
+
+        for (year, season, number, code) in keys {
+            report.syllabi.push(ArchivedSyllabus {
+                number: number.clone(),
+                season: *season,
+                year: *year,
+                code: code.clone(),
+                source_url: url.to_owned(),
+                hash: hash.clone(),
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::FetchOptions;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path, query_param},
+    };
+
+    #[test]
+    fn test_parse_syllabus_title() {
+        let parsed = |number: &str, code: &str| Some((number.to_owned(), code.to_owned()));
+
+        assert_eq!(parse_syllabus_title("15122 A"), parsed("15122", "A"));
+        assert_eq!(
+            parse_syllabus_title("15-122_Lec 1.pdf"),
+            parsed("15122", "Lec 1")
+        );
+        assert_eq!(
+            parse_syllabus_title("F25-15122-A2.pdf"),
+            parsed("15122", "A2")
+        );
+        assert_eq!(
+            parse_syllabus_title("15-122 Lecture 2"),
+            parsed("15122", "Lec 2")
+        );
+        assert_eq!(parse_syllabus_title("Syllabus policy.pdf"), None);
+        assert_eq!(parse_syllabus_title("15122.pdf"), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_syllabus_map() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+
+        Mock::given(method("GET"))
+            .and(path(
+                "/api/v1/courses/sis_course_id:syllabus-registry-F25-CS/modules",
+            ))
+            .and(header("Authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "name": "Welcome", "items_url": format!("{base}/api/v1/courses/1/modules/1/items") },
+                { "name": "Available Syllabi", "items_url": format!("{base}/api/v1/courses/1/modules/2/items") },
+            ])))
+            .mount(&server)
+            .await;
+
+        // The items are split across two pages
+        Mock::given(method("GET"))
+            .and(path("/api/v1/courses/1/modules/2/items"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "title": "15-150 Lec 1", "type": "File", "url": format!("{base}/api/v1/files/2") },
+                { "title": "Read me first", "type": "Page", "url": format!("{base}/api/v1/pages/1") },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/courses/1/modules/2/items"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(
+                            "<{base}/api/v1/courses/1/modules/2/items?page=2&per_page=100>; rel=\"next\""
+                        )
+                        .as_str(),
+                    )
+                    .set_body_json(json!([
+                        { "title": "15122 A", "type": "File", "url": format!("{base}/api/v1/files/1") },
+                        { "title": "Course policies", "type": "File", "url": format!("{base}/api/v1/files/3") },
+                    ])),
+            )
+            .mount(&server)
+            .await;
+
+        for id in 1..=3 {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v1/files/{id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "filename": format!("{id}.pdf"),
+                    "url": format!("{base}/files/{id}/download"),
+                })))
+                .mount(&server)
+                .await;
+        }
+
+        // ECE has no registry this semester and MSC's is broken
+        Mock::given(method("GET"))
+            .and(path(
+                "/api/v1/courses/sis_course_id:syllabus-registry-F25-ECE/modules",
+            ))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/api/v1/courses/sis_course_id:syllabus-registry-F25-MSC/modules",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>"))
+            .mount(&server)
+            .await;
+
+        let options = FetchOptions {
+            requests_per_second: 0.0,
+            ..FetchOptions::default()
+        };
+        let client = CanvasClient::new(&base, "secret", &options);
+        let semesters = [(Season::Fall, Year(2025))];
+        let departments = [Department::CS, Department::ECE, Department::MSC];
+
+        let report = fetch_syllabus_map(&client, &semesters, &departments, 4)
+            .await
+            .unwrap();
+
+        assert_eq!(report.syllabus_map.len(), 2);
+        assert_eq!(
+            report.syllabus_map.get(&(
+                Year(2025),
+                Season::Fall,
+                "15122".to_owned(),
+                "A".to_owned()
+            )),
+            Some(&format!("{base}/files/1/download"))
+        );
+        assert_eq!(
+            report.syllabus_map.get(&(
+                Year(2025),
+                Season::Fall,
+                "15150".to_owned(),
+                "Lec 1".to_owned()
+            )),
+            Some(&format!("{base}/files/2/download"))
+        );
+        assert_eq!(report.unparsed_titles, vec!["Course policies"]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].department, Department::MSC);
+
+        // A rejected token stops everything
+        let client = CanvasClient::new(&base, "wrong", &options);
+        Mock::given(method("GET"))
+            .and(header("Authorization", "Bearer wrong"))
+            .respond_with(ResponseTemplate::new(401))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        assert!(matches!(
+            fetch_syllabus_map(&client, &semesters, &departments, 4).await,
+            Err(CanvasError::Unauthorized { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_archive_syllabi() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+
+        Mock::given(method("GET"))
+            .and(path("/files/1/download"))
+            .and(header("Authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Week 1:  Intro"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/2/download"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/3/download"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let key = |number: &str, code: &str| {
+            (Year(2025), Season::Fall, number.to_owned(), code.to_owned())
+        };
+        // Both lectures share a syllabus, and 15-150's was archived by an earlier run
+        let syllabus_map = SyllabusMap::from([
+            (key("15122", "Lec 1"), format!("{base}/files/1/download")),
+            (key("15122", "Lec 2"), format!("{base}/files/1/download")),
+            (key("15213", "A"), format!("{base}/files/2/download")),
+            (key("15150", "Lec 1"), format!("{base}/files/3/download")),
+        ]);
+        let archived_urls = HashMap::from([(format!("{base}/files/3/download"), "3".repeat(64))]);
+
+        let options = FetchOptions {
+            requests_per_second: 0.0,
+            ..FetchOptions::default()
+        };
+        let client = CanvasClient::new(&base, "secret", &options);
+        let root = std::env::temp_dir().join(format!("syllabi-{}", uuid::Uuid::new_v4()));
+        let store = SyllabusStore::new(&root);
+
+        let mut report = archive_syllabi(&client, &store, &syllabus_map, &archived_urls, 4)
+            .await
+            .unwrap();
+
+        assert_eq!(report.files.len(), 1);
+        let file = &report.files[0];
+        assert_eq!(file.text, "Week 1: Intro");
+        assert_eq!(file.content_type, "text/plain; charset=utf-8");
+        assert_eq!(store.get(&file.hash).unwrap().unwrap(), b"Week 1:  Intro");
+
+        report
+            .syllabi
+            .sort_by(|a, b| (&a.number, &a.code).cmp(&(&b.number, &b.code)));
+        let archived = report
+            .syllabi
+            .iter()
+            .map(|s| (s.number.as_str(), s.code.as_str(), s.hash.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            archived,
+            vec![
+                ("15122", "Lec 1", file.hash.as_str()),
+                ("15122", "Lec 2", file.hash.as_str()),
+                ("15150", "Lec 1", "3".repeat(64).as_str()),
+            ]
+        );
+
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].url, format!("{base}/files/2/download"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,12 +1,28 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20250710_create_all_tables;
+mod m20261018_add_course_department;
+mod m20261018_add_meeting_days_mask;
+mod m20261018_create_catalog;
+mod m20261018_create_course_changes;
+mod m20261018_create_fuzzy_search;
+mod m20261018_create_scrape_runs;
+mod m20261018_create_syllabus_archive;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20250710_create_all_tables::Migration)]
+        vec![
+            Box::new(m20250710_create_all_tables::Migration),
+            Box::new(m20261018_create_course_changes::Migration),
+            Box::new(m20261018_create_scrape_runs::Migration),
+            Box::new(m20261018_create_syllabus_archive::Migration),
+            Box::new(m20261018_create_catalog::Migration),
+            Box::new(m20261018_create_fuzzy_search::Migration),
+            Box::new(m20261018_add_meeting_days_mask::Migration),
+            Box::new(m20261018_add_course_department::Migration),
+        ]
     }
 }
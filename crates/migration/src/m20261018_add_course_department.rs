@@ -0,0 +1,37 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Name of the department the schedule of classes lists the course under, so
+        // departments can be searched by name. NULL until the course's semester is scraped again.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Courses::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Courses::Department).text())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Courses::Table)
+                    .drop_column(Courses::Department)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Courses {
+    Table,
+    Department,
+}
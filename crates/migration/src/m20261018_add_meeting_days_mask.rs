@@ -0,0 +1,55 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Bitmask of the days in days_pattern, in the bit order of `DaySet` (Monday first), so
+        // meetings can be filtered by day without parsing the pattern. NULL for TBA meetings.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Meetings::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Meetings::DaysMask).small_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Backfill the meetings saved before the column existed
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE meetings SET days_mask = \
+                 (CASE WHEN strpos(days_pattern, 'M') > 0 THEN 1 ELSE 0 END) \
+                 | (CASE WHEN strpos(days_pattern, 'T') > 0 THEN 2 ELSE 0 END) \
+                 | (CASE WHEN strpos(days_pattern, 'W') > 0 THEN 4 ELSE 0 END) \
+                 | (CASE WHEN strpos(days_pattern, 'R') > 0 THEN 8 ELSE 0 END) \
+                 | (CASE WHEN strpos(days_pattern, 'F') > 0 THEN 16 ELSE 0 END) \
+                 | (CASE WHEN strpos(days_pattern, 'S') > 0 THEN 32 ELSE 0 END) \
+                 | (CASE WHEN strpos(days_pattern, 'U') > 0 THEN 64 ELSE 0 END) \
+                 WHERE strpos(days_pattern, 'TBA') = 0",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Meetings::Table)
+                    .drop_column(Meetings::DaysMask)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Meetings {
+    Table,
+    DaysMask,
+}
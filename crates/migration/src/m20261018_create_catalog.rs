@@ -0,0 +1,170 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create catalog_courses table, one row per course number across every semester
+        manager
+            .create_table(
+                Table::create()
+                    .table(CatalogCourses::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CatalogCourses::Number)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    // Title and description of the most recent offering
+                    .col(ColumnDef::new(CatalogCourses::Title).string())
+                    .col(ColumnDef::new(CatalogCourses::Description).text())
+                    .col(
+                        ColumnDef::new(CatalogCourses::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create course_offerings table. Offerings outlive the courses rows they point to, which
+        // are replaced on every scrape, so they keep a snapshot of the title and description.
+        manager
+            .create_table(
+                Table::create()
+                    .table(CourseOfferings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CourseOfferings::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CourseOfferings::Number).string().not_null())
+                    .col(ColumnDef::new(CourseOfferings::Season).string().not_null())
+                    .col(
+                        ColumnDef::new(CourseOfferings::Year)
+                            .small_unsigned()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CourseOfferings::CourseId).uuid())
+                    .col(ColumnDef::new(CourseOfferings::Title).string())
+                    .col(ColumnDef::new(CourseOfferings::Description).text())
+                    .col(ColumnDef::new(CourseOfferings::Units).string().not_null())
+                    .col(
+                        ColumnDef::new(CourseOfferings::RecordedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-course_offerings-number")
+                            .from(CourseOfferings::Table, CourseOfferings::Number)
+                            .to(CatalogCourses::Table, CatalogCourses::Number)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-course_offerings-course_id")
+                            .from(CourseOfferings::Table, CourseOfferings::CourseId)
+                            .to(Courses::Table, Courses::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-course_offerings-offering")
+                    .table(CourseOfferings::Table)
+                    .col(CourseOfferings::Number)
+                    .col(CourseOfferings::Season)
+                    .col(CourseOfferings::Year)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Backfill from the courses already stored, titled by their first lecture
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "INSERT INTO catalog_courses (number) \
+             SELECT DISTINCT number FROM courses \
+             ON CONFLICT DO NOTHING",
+        )
+        .await?;
+        db.execute_unprepared(
+            "INSERT INTO course_offerings \
+                 (id, number, season, year, course_id, title, description, units) \
+             SELECT gen_random_uuid(), c.number, c.season, c.year, c.id, \
+                 (SELECT title FROM components \
+                  WHERE course_id = c.id \
+                  ORDER BY component_type = 'Lecture' DESC, code \
+                  LIMIT 1), \
+                 c.description, c.units \
+             FROM courses c \
+             ON CONFLICT DO NOTHING",
+        )
+        .await?;
+        db.execute_unprepared(
+            "UPDATE catalog_courses cc SET title = o.title, description = o.description \
+             FROM ( \
+                 SELECT DISTINCT ON (number) number, title, description FROM course_offerings \
+                 ORDER BY number, year DESC, \
+                     CASE season WHEN 'F' THEN 3 WHEN 'N' THEN 2 WHEN 'M' THEN 1 ELSE 0 END DESC \
+             ) o \
+             WHERE cc.number = o.number",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CourseOfferings::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CatalogCourses::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum CatalogCourses {
+    Table,
+    Number,
+    Title,
+    Description,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum CourseOfferings {
+    Table,
+    Id,
+    Number,
+    Season,
+    Year,
+    CourseId,
+    Title,
+    Description,
+    Units,
+    RecordedAt,
+}
+
+#[derive(Iden)]
+enum Courses {
+    Table,
+    Id,
+}
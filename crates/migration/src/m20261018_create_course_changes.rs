@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create course_changes table
+        manager
+            .create_table(
+                Table::create()
+                    .table(CourseChanges::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CourseChanges::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CourseChanges::ScrapeRunId).uuid().not_null())
+                    .col(ColumnDef::new(CourseChanges::Number).string().not_null())
+                    .col(ColumnDef::new(CourseChanges::Season).string().not_null())
+                    .col(
+                        ColumnDef::new(CourseChanges::Year)
+                            .small_unsigned()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CourseChanges::ChangeType).text().not_null())
+                    .col(ColumnDef::new(CourseChanges::Section).string())
+                    .col(ColumnDef::new(CourseChanges::OldValue).text())
+                    .col(ColumnDef::new(CourseChanges::NewValue).text())
+                    .col(
+                        ColumnDef::new(CourseChanges::DetectedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Clients sync incrementally by detection time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-course_changes-detected_at")
+                    .table(CourseChanges::Table)
+                    .col(CourseChanges::DetectedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CourseChanges::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum CourseChanges {
+    Table,
+    Id,
+    ScrapeRunId,
+    Number,
+    Season,
+    Year,
+    ChangeType,
+    Section,
+    OldValue,
+    NewValue,
+    DetectedAt,
+}
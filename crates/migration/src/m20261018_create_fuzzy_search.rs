@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("CREATE EXTENSION IF NOT EXISTS pg_trgm")
+            .await?;
+
+        // Trigram indexes for matching misspelled titles and instructor names
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS \"idx-components-title-trgm\" ON components \
+             USING GIN (title gin_trgm_ops)",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS \"idx-instructors-name-trgm\" ON instructors \
+             USING GIN (name gin_trgm_ops)",
+        )
+        .await?;
+
+        // Full-text index for searching titles
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS \"idx-components-title\" ON components \
+             USING GIN (to_tsvector('english', title))",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP INDEX IF EXISTS \"idx-components-title\"")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS \"idx-instructors-name-trgm\"")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS \"idx-components-title-trgm\"")
+            .await?;
+
+        Ok(())
+    }
+}
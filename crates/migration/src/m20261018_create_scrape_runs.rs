@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create scrape_runs table
+        manager
+            .create_table(
+                Table::create()
+                    .table(ScrapeRuns::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ScrapeRuns::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ScrapeRuns::StartedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    // Null while the run is still in progress or if it crashed
+                    .col(ColumnDef::new(ScrapeRuns::FinishedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ScrapeRuns::Semesters).json().not_null())
+                    .col(
+                        ColumnDef::new(ScrapeRuns::CourseCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ScrapeRuns::DetailFailures).json().not_null())
+                    .col(ColumnDef::new(ScrapeRuns::Diagnostics).json().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Link recorded changes to the run that detected them
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk-course_changes-scrape_run_id")
+                    .from(CourseChanges::Table, CourseChanges::ScrapeRunId)
+                    .to(ScrapeRuns::Table, ScrapeRuns::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk-course_changes-scrape_run_id")
+                    .table(CourseChanges::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ScrapeRuns::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ScrapeRuns {
+    Table,
+    Id,
+    StartedAt,
+    FinishedAt,
+    Semesters,
+    CourseCount,
+    DetailFailures,
+    Diagnostics,
+}
+
+#[derive(Iden)]
+enum CourseChanges {
+    Table,
+    ScrapeRunId,
+}
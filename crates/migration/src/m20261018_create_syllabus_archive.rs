@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create syllabus_files table, keyed by the SHA-256 hash of the archived file
+        manager
+            .create_table(
+                Table::create()
+                    .table(SyllabusFiles::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SyllabusFiles::Hash)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SyllabusFiles::ContentType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SyllabusFiles::SizeBytes)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    // Plain text extracted from the file, empty if it couldn't be extracted
+                    .col(ColumnDef::new(SyllabusFiles::Text).text().not_null())
+                    .col(
+                        ColumnDef::new(SyllabusFiles::ArchivedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create syllabi table. Rows are keyed by offering rather than component so they
+        // survive a semester being replaced by a new scrape.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Syllabi::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Syllabi::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Syllabi::Number).string().not_null())
+                    .col(ColumnDef::new(Syllabi::Season).string().not_null())
+                    .col(ColumnDef::new(Syllabi::Year).small_unsigned().not_null())
+                    .col(ColumnDef::new(Syllabi::Code).string().not_null())
+                    .col(ColumnDef::new(Syllabi::SourceUrl).text().not_null())
+                    .col(ColumnDef::new(Syllabi::Hash).string().not_null())
+                    .col(
+                        ColumnDef::new(Syllabi::ArchivedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-syllabi-hash")
+                            .from(Syllabi::Table, Syllabi::Hash)
+                            .to(SyllabusFiles::Table, SyllabusFiles::Hash)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-syllabi-offering")
+                    .table(Syllabi::Table)
+                    .col(Syllabi::Number)
+                    .col(Syllabi::Season)
+                    .col(Syllabi::Year)
+                    .col(Syllabi::Code)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Full-text indexes for searching course descriptions and syllabus text
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS \"idx-syllabus_files-text\" ON syllabus_files \
+             USING GIN (to_tsvector('english', text))",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS \"idx-courses-description\" ON courses \
+             USING GIN (to_tsvector('english', coalesce(description, '')))",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS \"idx-courses-description\"")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Syllabi::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SyllabusFiles::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum SyllabusFiles {
+    Table,
+    Hash,
+    ContentType,
+    SizeBytes,
+    Text,
+    ArchivedAt,
+}
+
+#[derive(Iden)]
+enum Syllabi {
+    Table,
+    Id,
+    Number,
+    Season,
+    Year,
+    Code,
+    SourceUrl,
+    Hash,
+    ArchivedAt,
+}
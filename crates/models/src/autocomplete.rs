@@ -0,0 +1,536 @@
+use crate::{course_data::CourseNumber, similarity::CourseDocument};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+/// Number of suggestions returned when none is given
+pub const DEFAULT_SUGGESTIONS: usize = 10;
+
+/// Most suggestions that can be returned at once
+pub const MAX_SUGGESTIONS: usize = 25;
+
+/// Smallest share of trigrams a key must have in common with a query to be suggested when
+/// nothing starts with the query
+const MIN_TRIGRAM_SIMILARITY: f32 = 0.3;
+
+/// Something a search can be completed to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suggestion {
+    /// Courses whose number starts with the department's two digits
+    Department {
+        code: String,
+        /// Name the schedule lists the department under (e.g., "Computer Science"), if known
+        name: Option<String>,
+        course_count: usize,
+    },
+    Course {
+        /// Course number without a dash (e.g., "15122")
+        number: String,
+        /// Title of the course's most recent offering
+        title: Option<String>,
+    },
+    Instructor {
+        name: String,
+    },
+}
+
+impl Suggestion {
+    /// Order of suggestions that match equally well
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Department { .. } => 0,
+            Self::Course { .. } => 1,
+            Self::Instructor { .. } => 2,
+        }
+    }
+}
+
+/// How well a key matched a query, best first
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Match {
+    /// The key starts with the query
+    Prefix,
+    /// A word in the key starts with the query
+    WordPrefix,
+    /// The key shares trigrams with the query; the higher the similarity, the better
+    Trigram(f32),
+}
+
+impl Match {
+    fn order(&self) -> (u8, f32) {
+        match self {
+            Self::Prefix => (0, 0.0),
+            Self::WordPrefix => (1, 0.0),
+            Self::Trigram(similarity) => (2, -similarity),
+        }
+    }
+}
+
+/// A normalized string a suggestion can be found by
+#[derive(Debug, Clone)]
+struct Key {
+    text: String,
+    suggestion: usize,
+}
+
+/// An in-memory index for completing searches to course numbers, titles, instructors, and
+/// departments
+///
+/// Every word of a key can be completed from its prefix, and keys and their words are also
+/// indexed by trigram so misspelled queries still find something.
+#[derive(Debug, Clone, Default)]
+pub struct AutocompleteIndex {
+    suggestions: Vec<Suggestion>,
+    keys: Vec<Key>,
+    /// Each key from the start of each of its words, sorted, with the key's index and whether
+    /// it is the whole key
+    prefixes: Vec<(String, usize, bool)>,
+    /// Whole keys and their words matched by trigram, with the key they are from and their
+    /// number of distinct trigrams
+    fuzzy_terms: Vec<(usize, usize)>,
+    /// Fuzzy terms containing each trigram
+    trigrams: HashMap<String, Vec<usize>>,
+}
+
+/// Lowercases text and turns everything but letters and digits into single spaces, so
+/// "15-122" is written "15 122"
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The distinct trigrams of a normalized string, padded so its start counts for more
+fn trigrams(text: &str) -> Vec<String> {
+    let padded: Vec<char> = format!("  {text} ").chars().collect();
+    let mut trigrams: Vec<String> = padded
+        .windows(3)
+        .map(|window| window.iter().collect())
+        .collect();
+    trigrams.sort_unstable();
+    trigrams.dedup();
+    trigrams
+}
+
+impl AutocompleteIndex {
+    /// Builds the index from every stored offering, department, and instructor
+    ///
+    /// # Arguments
+    /// * `documents` - Offerings in any order; each course is titled by its most recent one
+    /// * `departments` - Department names by the first two digits of their course numbers;
+    ///   departments without courses aren't suggested
+    /// * `instructors` - Instructor names; names differing only in case are suggested once
+    pub fn new(
+        documents: &[CourseDocument],
+        departments: &[(String, String)],
+        instructors: &[String],
+    ) -> Self {
+        let mut latest: BTreeMap<&str, &CourseDocument> = BTreeMap::new();
+        for document in documents {
+            match latest.get(document.number.as_str()) {
+                Some(seen) if seen.semester >= document.semester => {}
+                _ => {
+                    latest.insert(&document.number, document);
+                }
+            }
+        }
+
+        let mut index = Self::default();
+
+        let mut course_counts: BTreeMap<String, usize> = BTreeMap::new();
+        for number in latest.keys() {
+            *course_counts
+                .entry(number.chars().take(2).collect())
+                .or_default() += 1;
+        }
+        let names: HashMap<&str, &str> = departments
+            .iter()
+            .map(|(code, name)| (code.as_str(), name.as_str()))
+            .collect();
+        for (code, course_count) in course_counts {
+            let name = names.get(code.as_str()).copied();
+            let mut keys = vec![code.as_str()];
+            keys.extend(name);
+            index.add(
+                Suggestion::Department {
+                    code: code.clone(),
+                    name: name.map(str::to_owned),
+                    course_count,
+                },
+                &keys,
+            );
+        }
+
+        for (number, document) in latest {
+            let title = document.titles.first().cloned();
+            let dashed = CourseNumber::from_str(number)
+                .map(|number| number.as_full_string())
+                .unwrap_or_else(|_| number.to_owned());
+
+            let mut keys = vec![number, dashed.as_str()];
+            keys.extend(title.as_deref());
+            index.add(
+                Suggestion::Course {
+                    number: number.to_owned(),
+                    title: title.clone(),
+                },
+                &keys,
+            );
+        }
+
+        let mut names: BTreeMap<String, &str> = BTreeMap::new();
+        for name in instructors {
+            names.entry(normalize(name)).or_insert(name.trim());
+        }
+        for name in names.into_values() {
+            index.add(
+                Suggestion::Instructor {
+                    name: name.to_owned(),
+                },
+                &[name],
+            );
+        }
+
+        index.prefixes.sort_unstable();
+        index
+    }
+
+    fn add(&mut self, suggestion: Suggestion, keys: &[&str]) {
+        let id = self.suggestions.len();
+        self.suggestions.push(suggestion);
+
+        let mut texts: Vec<String> = keys.iter().map(|key| normalize(key)).collect();
+        texts.retain(|text| !text.is_empty());
+        texts.dedup();
+
+        for text in texts {
+            let key_id = self.keys.len();
+
+            for (space, _) in text.match_indices(' ') {
+                self.prefixes
+                    .push((text[space + 1..].to_owned(), key_id, false));
+            }
+            self.prefixes.push((text.clone(), key_id, true));
+
+            let words = text.split(' ').filter(|word| word.chars().count() >= 3);
+            let mut terms: Vec<&str> = words.collect();
+            if terms.len() != 1 || terms[0] != text {
+                terms.push(&text);
+            }
+            for term in terms {
+                let term_id = self.fuzzy_terms.len();
+                let term_trigrams = trigrams(term);
+                self.fuzzy_terms.push((key_id, term_trigrams.len()));
+                for trigram in term_trigrams {
+                    self.trigrams.entry(trigram).or_default().push(term_id);
+                }
+            }
+
+            self.keys.push(Key {
+                text,
+                suggestion: id,
+            });
+        }
+    }
+
+    /// Number of things that can be suggested
+    pub fn len(&self) -> usize {
+        self.suggestions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.suggestions.is_empty()
+    }
+
+    /// Keeps a key's match if it is the best for its suggestion so far
+    fn record(&self, best: &mut HashMap<usize, (Match, usize)>, key_id: usize, matched: Match) {
+        let suggestion = self.keys[key_id].suggestion;
+        match best.get(&suggestion) {
+            Some((seen, _)) if seen.order() <= matched.order() => {}
+            _ => {
+                best.insert(suggestion, (matched, key_id));
+            }
+        }
+    }
+
+    /// Completes a search
+    ///
+    /// Suggestions whose key starts with the query come first, then those with a word starting
+    /// with it. Only when there are too few of those are keys and words sharing trigrams with
+    /// the query suggested, so misspellings still find something. Queries without letters, like
+    /// course numbers, are only matched by prefix.
+    ///
+    /// # Arguments
+    /// * `query` - What has been typed so far
+    /// * `limit` - Most suggestions to return
+    ///
+    /// # Returns
+    /// The suggestions, best first, departments before courses before instructors among equal
+    /// matches
+    pub fn suggest(&self, query: &str, limit: usize) -> Vec<Suggestion> {
+        let query = normalize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        // The best match of each suggestion, with the key it matched
+        let mut best: HashMap<usize, (Match, usize)> = HashMap::new();
+
+        let start = self
+            .prefixes
+            .partition_point(|(prefix, _, _)| prefix.as_str() < query.as_str());
+        for (prefix, key_id, whole) in &self.prefixes[start..] {
+            if !prefix.starts_with(&query) {
+                break;
+            }
+            self.record(
+                &mut best,
+                *key_id,
+                if *whole {
+                    Match::Prefix
+                } else {
+                    Match::WordPrefix
+                },
+            );
+        }
+
+        if best.len() < limit
+            && query.chars().count() >= 3
+            && query.chars().any(char::is_alphabetic)
+        {
+            let query_trigrams = trigrams(&query);
+            let mut shared: HashMap<usize, usize> = HashMap::new();
+            for trigram in &query_trigrams {
+                for term_id in self.trigrams.get(trigram).into_iter().flatten() {
+                    *shared.entry(*term_id).or_default() += 1;
+                }
+            }
+
+            for (term_id, count) in shared {
+                let (key_id, term_trigrams) = self.fuzzy_terms[term_id];
+                let similarity =
+                    count as f32 / (query_trigrams.len() + term_trigrams - count) as f32;
+                if similarity >= MIN_TRIGRAM_SIMILARITY {
+                    self.record(&mut best, key_id, Match::Trigram(similarity));
+                }
+            }
+        }
+
+        let mut ranked: Vec<(usize, Match, usize)> = best
+            .into_iter()
+            .map(|(suggestion, (matched, key_id))| (suggestion, matched, key_id))
+            .collect();
+        ranked.sort_by(|(a, a_match, a_key), (b, b_match, b_key)| {
+            let (a_tier, a_score) = a_match.order();
+            let (b_tier, b_score) = b_match.order();
+            let (a_text, b_text) = (&self.keys[*a_key].text, &self.keys[*b_key].text);
+
+            a_tier
+                .cmp(&b_tier)
+                .then(a_score.total_cmp(&b_score))
+                .then(
+                    self.suggestions[*a]
+                        .rank()
+                        .cmp(&self.suggestions[*b].rank()),
+                )
+                .then(a_text.len().cmp(&b_text.len()))
+                .then(a_text.cmp(b_text))
+        });
+
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(suggestion, _, _)| self.suggestions[suggestion].clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syllabus_data::Semester;
+    use std::time::{Duration, Instant};
+
+    fn document(number: &str, semester: &str, title: &str) -> CourseDocument {
+        CourseDocument {
+            number: number.to_owned(),
+            semester: Semester::from_str(semester).unwrap(),
+            description: None,
+            titles: vec![title.to_owned()],
+        }
+    }
+
+    fn index() -> AutocompleteIndex {
+        AutocompleteIndex::new(
+            &[
+                document("15122", "F25", "Principles of Imperative Computation"),
+                document("15150", "F24", "Functional Programming"),
+                document("15150", "F25", "Principles of Functional Programming"),
+                document(
+                    "15210",
+                    "F25",
+                    "Parallel and Sequential Data Structures and Algorithms",
+                ),
+                document("21127", "F25", "Concepts of Mathematics"),
+            ],
+            &[
+                ("15".to_owned(), "Computer Science".to_owned()),
+                ("21".to_owned(), "Mathematical Sciences".to_owned()),
+                ("48".to_owned(), "Architecture".to_owned()),
+            ],
+            &[
+                "Iliano Cervesato".to_owned(),
+                "iliano cervesato".to_owned(),
+                "Frank Pfenning".to_owned(),
+            ],
+        )
+    }
+
+    fn course(number: &str) -> impl Fn(&Suggestion) -> bool + '_ {
+        move |suggestion| matches!(suggestion, Suggestion::Course { number: n, .. } if n == number)
+    }
+
+    #[test]
+    fn test_course_numbers() {
+        let index = index();
+        // Two departments, four courses, and two instructors
+        assert_eq!(index.len(), 8);
+
+        for query in ["15122", "15-122", " 15-122 "] {
+            let suggestions = index.suggest(query, 10);
+            assert_eq!(suggestions.len(), 1, "{query}");
+            assert!(course("15122")(&suggestions[0]));
+        }
+
+        let suggestions = index.suggest("15", 10);
+        assert_eq!(
+            suggestions[0],
+            Suggestion::Department {
+                code: "15".into(),
+                name: Some("Computer Science".into()),
+                course_count: 3,
+            }
+        );
+        assert_eq!(suggestions.len(), 4);
+        assert!(
+            index
+                .suggest("15-1", 10)
+                .iter()
+                .all(|s| !course("15210")(s))
+        );
+    }
+
+    #[test]
+    fn test_titles_and_instructors() {
+        let index = index();
+
+        let suggestions = index.suggest("princ", 10);
+        assert!(suggestions[..2].iter().any(course("15122")));
+        assert!(suggestions[..2].iter().any(course("15150")));
+        assert!(index.suggest("func", 10).iter().any(course("15150")));
+        // Old titles aren't suggested
+        assert!(index.suggest("functional programming", 10).len() == 1);
+
+        assert_eq!(
+            index.suggest("cerv", 10),
+            vec![Suggestion::Instructor {
+                name: "Iliano Cervesato".into()
+            }]
+        );
+
+        // Misspellings are matched by trigram
+        assert!(index.suggest("algoritms", 10).iter().any(course("15210")));
+        assert!(
+            index
+                .suggest("pfening", 10)
+                .iter()
+                .any(|s| matches!(s, Suggestion::Instructor { name } if name == "Frank Pfenning"))
+        );
+
+        assert!(index.suggest("", 10).is_empty());
+        assert_eq!(index.suggest("p", 2).len(), 2);
+    }
+
+    #[test]
+    fn test_department_names() {
+        let index = index();
+        let department = |code: &str| {
+            let code = code.to_owned();
+            move |s: &Suggestion| matches!(s, Suggestion::Department { code: c, .. } if *c == code)
+        };
+
+        assert!(department("15")(&index.suggest("computer", 10)[0]));
+        assert!(department("21")(&index.suggest("sciences", 10)[0]));
+        assert!(
+            index
+                .suggest("compter science", 10)
+                .iter()
+                .any(department("15"))
+        );
+        // Departments without courses aren't suggested
+        assert!(index.suggest("architecture", 10).is_empty());
+    }
+
+    /// Suggestions are computed on every keystroke, so they have to stay well under 10 ms
+    /// even over a whole catalog
+    #[test]
+    fn test_suggest_time() {
+        const WORDS: [&str; 12] = [
+            "introduction",
+            "principles",
+            "advanced",
+            "topics",
+            "systems",
+            "theory",
+            "design",
+            "analysis",
+            "computation",
+            "methods",
+            "seminar",
+            "research",
+        ];
+        let documents: Vec<CourseDocument> = (0..5000)
+            .map(|i| {
+                let title = (0..4)
+                    .map(|j| WORDS[(i * 7 + j * 5 + i / 12) % WORDS.len()])
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                document(&format!("{:05}", 10000 + i * 17), "F25", &title)
+            })
+            .collect();
+        let departments: Vec<(String, String)> = (10..99)
+            .map(|code| {
+                (
+                    code.to_string(),
+                    format!("Department of {}", WORDS[code % WORDS.len()]),
+                )
+            })
+            .collect();
+        let instructors: Vec<String> = (0..3000)
+            .map(|i| format!("Instructor{i} {}", WORDS[i % WORDS.len()]))
+            .collect();
+        let index = AutocompleteIndex::new(&documents, &departments, &instructors);
+
+        // Unoptimized builds are about ten times slower
+        let budget = Duration::from_millis(if cfg!(debug_assertions) { 100 } else { 10 });
+        for query in [
+            "1",
+            "15-1",
+            "intro",
+            "principles of",
+            "computaton",
+            "anlysis",
+            "xyz",
+        ] {
+            let start = Instant::now();
+            index.suggest(query, MAX_SUGGESTIONS);
+            let elapsed = start.elapsed();
+            assert!(elapsed < budget, "suggesting {query:?} took {elapsed:?}");
+        }
+    }
+}
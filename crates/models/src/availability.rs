@@ -0,0 +1,365 @@
+use crate::syllabus_data::{Season, Year};
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Smallest share of recorded years a pattern must fit before a season is called regular
+const REGULAR_FIT: f32 = 0.8;
+
+/// Number of semesters predicted when none is given, two years with the summer sessions
+pub const DEFAULT_PREDICTED_SEMESTERS: usize = 8;
+
+/// Most semesters that can be predicted at once
+pub const MAX_PREDICTED_SEMESTERS: usize = 20;
+
+/// How a course has been offered over the years
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfferingPattern {
+    /// Every fall and every spring
+    EverySemester,
+    EveryFall,
+    EverySpring,
+    /// Every other fall, and never in spring
+    AlternatingFalls,
+    /// Every other spring, and never in fall
+    AlternatingSprings,
+    /// Only in the summer sessions
+    SummerOnly,
+    /// None of the above
+    Irregular,
+}
+
+impl Display for OfferingPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let pattern = match self {
+            Self::EverySemester => "every semester",
+            Self::EveryFall => "every fall",
+            Self::EverySpring => "every spring",
+            Self::AlternatingFalls => "alternating falls",
+            Self::AlternatingSprings => "alternating springs",
+            Self::SummerOnly => "summer only",
+            Self::Irregular => "irregular",
+        };
+        write!(f, "{pattern}")
+    }
+}
+
+/// The chance a course is offered in a future semester
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PredictedOffering {
+    pub season: Season,
+    pub year: Year,
+    /// Between 0 and 1
+    pub likelihood: f32,
+}
+
+/// How a course is offered in a single season
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeasonPattern {
+    Every,
+    Never,
+    /// Offered every other year
+    Alternating,
+    Irregular,
+}
+
+/// The years a season was recorded, and the years a course was offered in it
+struct SeasonStats {
+    recorded: Vec<u16>,
+    offered: HashSet<u16>,
+}
+
+impl SeasonStats {
+    /// Share of the recorded years matching `filter` that the course was offered in
+    fn rate(&self, filter: impl Fn(u16) -> bool) -> Option<f32> {
+        let years: Vec<u16> = self
+            .recorded
+            .iter()
+            .copied()
+            .filter(|&year| filter(year))
+            .collect();
+        let offered = years
+            .iter()
+            .filter(|year| self.offered.contains(year))
+            .count();
+
+        (!years.is_empty()).then(|| offered as f32 / years.len() as f32)
+    }
+
+    /// The pattern that fits the recorded years best, with the share of years it fits
+    ///
+    /// An irregular season's fit is how far the best regular pattern falls short.
+    fn pattern(&self) -> (SeasonPattern, f32) {
+        let Some(rate) = self.rate(|_| true) else {
+            return (SeasonPattern::Irregular, 0.0);
+        };
+
+        let mut candidates = vec![
+            (SeasonPattern::Every, rate),
+            (SeasonPattern::Never, 1.0 - rate),
+        ];
+        // Two years can't tell alternating from irregular
+        if self.recorded.len() >= 3 {
+            for even_years in [true, false] {
+                let fits = self
+                    .recorded
+                    .iter()
+                    .filter(|&year| {
+                        (self.offered.contains(year) == year.is_multiple_of(2)) == even_years
+                    })
+                    .count();
+                candidates.push((
+                    SeasonPattern::Alternating,
+                    fits as f32 / self.recorded.len() as f32,
+                ));
+            }
+        }
+
+        let (pattern, fit) = candidates
+            .into_iter()
+            .reduce(|best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            })
+            .expect("there is always a candidate");
+
+        if fit >= REGULAR_FIT {
+            (pattern, fit)
+        } else {
+            (SeasonPattern::Irregular, 1.0 - fit)
+        }
+    }
+}
+
+/// Every semester a course was offered in, out of the semesters with recorded courses
+#[derive(Debug, Clone, PartialEq)]
+pub struct OfferingHistory {
+    offered: HashSet<(Season, Year)>,
+    recorded: HashSet<(Season, Year)>,
+}
+
+impl OfferingHistory {
+    /// # Arguments
+    /// * `offered` - The semesters the course was offered in
+    /// * `recorded` - Every semester with recorded courses, so a semester missing from the
+    ///   records isn't mistaken for one the course wasn't offered in
+    pub fn new(
+        offered: impl IntoIterator<Item = (Season, Year)>,
+        recorded: impl IntoIterator<Item = (Season, Year)>,
+    ) -> Self {
+        let offered: HashSet<_> = offered.into_iter().collect();
+        let mut recorded: HashSet<_> = recorded.into_iter().collect();
+        recorded.extend(offered.iter().copied());
+
+        Self { offered, recorded }
+    }
+
+    /// Every season the course has been offered in
+    pub fn seasons(&self) -> HashSet<Season> {
+        self.offered.iter().map(|(season, _)| *season).collect()
+    }
+
+    /// The most recent recorded semester
+    pub fn latest_recorded(&self) -> Option<(Season, Year)> {
+        self.recorded
+            .iter()
+            .copied()
+            .max_by_key(|(season, year)| (**year, season.term_order()))
+    }
+
+    fn stats(&self, season: Season) -> SeasonStats {
+        let mut recorded: Vec<u16> = self
+            .recorded
+            .iter()
+            .filter(|(s, _)| *s == season)
+            .map(|(_, year)| **year)
+            .collect();
+        recorded.sort_unstable();
+
+        SeasonStats {
+            recorded,
+            offered: self
+                .offered
+                .iter()
+                .filter(|(s, _)| *s == season)
+                .map(|(_, year)| **year)
+                .collect(),
+        }
+    }
+
+    /// Infers how the course is offered from its fall and spring offerings
+    ///
+    /// # Returns
+    /// The pattern and a confidence between 0 and 1: the share of recorded years the pattern
+    /// fits in its worse season, discounted when few years are recorded
+    pub fn pattern(&self) -> (OfferingPattern, f32) {
+        let fall = self.stats(Season::Fall);
+        let spring = self.stats(Season::Spring);
+        let (fall_pattern, fall_fit) = fall.pattern();
+        let (spring_pattern, spring_fit) = spring.pattern();
+
+        let summers = self
+            .offered
+            .iter()
+            .any(|(season, _)| matches!(season, Season::Summer1 | Season::Summer2));
+
+        let pattern = match (fall_pattern, spring_pattern) {
+            (SeasonPattern::Every, SeasonPattern::Every) => OfferingPattern::EverySemester,
+            (SeasonPattern::Every, SeasonPattern::Never) => OfferingPattern::EveryFall,
+            (SeasonPattern::Never, SeasonPattern::Every) => OfferingPattern::EverySpring,
+            (SeasonPattern::Alternating, SeasonPattern::Never) => OfferingPattern::AlternatingFalls,
+            (SeasonPattern::Never, SeasonPattern::Alternating) => {
+                OfferingPattern::AlternatingSprings
+            }
+            (SeasonPattern::Never, SeasonPattern::Never) if summers => OfferingPattern::SummerOnly,
+            _ => OfferingPattern::Irregular,
+        };
+
+        let years = fall.recorded.len().min(spring.recorded.len()) as f32;
+        let confidence = fall_fit.min(spring_fit) * years / (years + 1.0);
+
+        (pattern, confidence)
+    }
+
+    /// The chance the course is offered in a semester
+    ///
+    /// Recorded semesters are certain. Otherwise it is the share of recorded years the course
+    /// was offered in that season, counting only years of the same parity if the season
+    /// alternates.
+    pub fn likelihood(&self, season: Season, year: Year) -> f32 {
+        if self.recorded.contains(&(season, year)) {
+            return if self.offered.contains(&(season, year)) {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        let stats = self.stats(season);
+        let rate = match stats.pattern() {
+            (SeasonPattern::Alternating, _) => stats.rate(|recorded| recorded % 2 == *year % 2),
+            _ => stats.rate(|_| true),
+        };
+        rate.unwrap_or_default()
+    }
+
+    /// Predicts whether the course is offered in each of the semesters after the most recent
+    /// recorded one, summer sessions included
+    ///
+    /// # Arguments
+    /// * `count` - How many semesters to predict
+    pub fn predict(&self, count: usize) -> Vec<PredictedOffering> {
+        let Some(mut semester) = self.latest_recorded() else {
+            return Vec::new();
+        };
+
+        (0..count)
+            .map(|_| {
+                semester = next_semester(semester);
+                let (season, year) = semester;
+                PredictedOffering {
+                    season,
+                    year,
+                    likelihood: self.likelihood(season, year),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The semester after the given one, in the order spring, summer 1, summer 2, fall
+pub fn next_semester((season, year): (Season, Year)) -> (Season, Year) {
+    match season {
+        Season::Spring => (Season::Summer1, year),
+        Season::Summer1 => (Season::Summer2, year),
+        Season::Summer2 => (Season::Fall, year),
+        Season::Fall => (Season::Spring, Year(*year + 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every fall and spring from 2018 through spring 2025, plus the summers
+    fn recorded() -> Vec<(Season, Year)> {
+        let mut semesters = Vec::new();
+        let mut semester = (Season::Fall, Year(2018));
+        while semester != (Season::Spring, Year(2025)) {
+            semesters.push(semester);
+            semester = next_semester(semester);
+        }
+        semesters.push(semester);
+        semesters
+    }
+
+    fn history(offered: impl Fn(Season, u16) -> bool) -> OfferingHistory {
+        OfferingHistory::new(
+            recorded()
+                .into_iter()
+                .filter(|(season, year)| offered(*season, **year)),
+            recorded(),
+        )
+    }
+
+    #[test]
+    fn test_regular_patterns() {
+        let every_fall = history(|season, _| season == Season::Fall);
+        let (pattern, confidence) = every_fall.pattern();
+        assert_eq!(pattern, OfferingPattern::EveryFall);
+        assert!(confidence > 0.8);
+
+        let predicted = every_fall.predict(4);
+        assert_eq!(
+            predicted
+                .iter()
+                .map(|p| (p.season, *p.year, p.likelihood))
+                .collect::<Vec<_>>(),
+            vec![
+                (Season::Summer1, 2025, 0.0),
+                (Season::Summer2, 2025, 0.0),
+                (Season::Fall, 2025, 1.0),
+                (Season::Spring, 2026, 0.0),
+            ]
+        );
+
+        let every_semester =
+            history(|season, year| matches!(season, Season::Fall | Season::Spring) && year != 2020);
+        assert_eq!(every_semester.pattern().0, OfferingPattern::EverySemester);
+        // Missing a fall and a spring makes both seasons less likely
+        assert!(every_semester.likelihood(Season::Fall, Year(2025)) < 1.0);
+
+        let summer_only = history(|season, _| season == Season::Summer1);
+        assert_eq!(summer_only.pattern().0, OfferingPattern::SummerOnly);
+    }
+
+    #[test]
+    fn test_alternating_and_irregular() {
+        let alternating =
+            history(|season, year| season == Season::Spring && year.is_multiple_of(2));
+        let (pattern, confidence) = alternating.pattern();
+        assert_eq!(pattern, OfferingPattern::AlternatingSprings);
+        assert!(confidence > 0.8);
+
+        // Spring 2025 wasn't offered, so spring 2026 is expected
+        assert_eq!(alternating.likelihood(Season::Spring, Year(2026)), 1.0);
+        assert_eq!(alternating.likelihood(Season::Spring, Year(2027)), 0.0);
+        assert_eq!(alternating.likelihood(Season::Spring, Year(2025)), 0.0);
+
+        let irregular =
+            history(|season, year| season == Season::Fall && matches!(year, 2018 | 2019 | 2022));
+        let (pattern, confidence) = irregular.pattern();
+        assert_eq!(pattern, OfferingPattern::Irregular);
+        assert!(confidence < 0.5);
+        assert_eq!(irregular.likelihood(Season::Fall, Year(2025)), 3.0 / 7.0);
+
+        // Without recorded semesters there is nothing to predict from
+        let unknown = OfferingHistory::new(Vec::new(), Vec::new());
+        assert!(unknown.predict(4).is_empty());
+        assert_eq!(unknown.pattern(), (OfferingPattern::Irregular, 0.0));
+    }
+}
@@ -0,0 +1,524 @@
+use crate::{
+    course_data::{CourseComponent, CourseMetadata, CourseNumber, CourseObject, Meeting},
+    instructor::InstructorName,
+    syllabus_data::{Season, Year},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use strum::{AsRefStr, Display, EnumIter, EnumString};
+
+/// Type of change detected between two offerings of a course
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumIter,
+    AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ChangeKind {
+    /// The course is offered for the first time this semester
+    CourseAdded,
+    /// The course is no longer offered this semester
+    CourseRemoved,
+    /// A new lecture or section was added
+    SectionAdded,
+    /// A lecture or section was cancelled
+    SectionCancelled,
+    /// The days or times of a lecture or section changed
+    MeetingTimeChanged,
+    /// The building or room of a lecture or section changed
+    RoomChanged,
+    /// The instructors of a lecture or section changed
+    InstructorsChanged,
+    /// The number of units changed
+    UnitsChanged,
+    /// The course description changed
+    DescriptionChanged,
+    /// The course prerequisites changed
+    PrerequisitesChanged,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+}
+
+/// Represents a single change to a course between two scrapes, or two semesters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CourseChange {
+    /// Course number (e.g., "15122")
+    pub number: CourseNumber,
+    /// Season of the changed offering
+    pub season: Season,
+    /// Year of the changed offering
+    pub year: Year,
+    /// What kind of change this is
+    pub kind: ChangeKind,
+    /// Lecture/section code the change applies to, if any
+    pub section: Option<String>,
+    /// Value before the change, if applicable
+    pub old_value: Option<String>,
+    /// Value after the change, if applicable
+    pub new_value: Option<String>,
+}
+
+impl CourseChange {
+    fn new(course: &CourseObject, kind: ChangeKind) -> Self {
+        Self {
+            number: course.course.number.clone(),
+            season: course.course.season,
+            year: course.course.year,
+            kind,
+            section: None,
+            old_value: None,
+            new_value: None,
+        }
+    }
+
+    fn with_section(mut self, section: &str) -> Self {
+        self.section = Some(section.to_owned());
+        self
+    }
+
+    fn with_values(mut self, old_value: Option<String>, new_value: Option<String>) -> Self {
+        self.old_value = old_value;
+        self.new_value = new_value;
+        self
+    }
+}
+
+/// Key used to match offerings between two scrapes
+type OfferingKey = (u16, String, String);
+
+fn offering_key(course: &CourseObject) -> OfferingKey {
+    (
+        *course.course.year,
+        course.course.season.as_str().to_owned(),
+        course.course.number.to_string(),
+    )
+}
+
+/// Formats the days and times of a component's meetings (e.g., "MWF 09:00AM-09:50AM")
+fn describe_times(meetings: &[Meeting]) -> String {
+    meetings
+        .iter()
+        .map(|meeting| match meeting.time {
+            Some(time) => format!("{} {time}", meeting.days),
+            None => meeting.days.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Formats the rooms of a component's meetings
+fn describe_rooms(meetings: &[Meeting]) -> String {
+    meetings
+        .iter()
+        .map(|meeting| meeting.bldg_room.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Formats the instructors of a component's meetings, each listed once in alphabetical order
+/// since stored meetings don't keep the order they were listed in
+fn describe_instructors(meetings: &[Meeting]) -> String {
+    meetings
+        .iter()
+        .flat_map(|meeting| &meeting.instructors)
+        .map(String::as_str)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Surnames of the instructors of a component's meetings, since stored instructors may have
+/// full names where the schedule of classes lists surnames
+fn instructor_surnames(meetings: &[Meeting]) -> BTreeSet<String> {
+    meetings
+        .iter()
+        .flat_map(|meeting| &meeting.instructors)
+        .map(|name| InstructorName::parse(name).surname)
+        .collect()
+}
+
+fn diff_components(
+    old: &[CourseComponent],
+    new: &[CourseComponent],
+    course: &CourseObject,
+    changes: &mut Vec<CourseChange>,
+) {
+    let old_by_code: BTreeMap<_, _> = old.iter().map(|c| (c.code.as_str(), c)).collect();
+    let new_by_code: BTreeMap<_, _> = new.iter().map(|c| (c.code.as_str(), c)).collect();
+
+    for (code, new_component) in &new_by_code {
+        let Some(old_component) = old_by_code.get(code) else {
+            changes.push(CourseChange::new(course, ChangeKind::SectionAdded).with_section(code));
+            continue;
+        };
+
+        let (old_times, new_times) = (
+            describe_times(&old_component.meetings),
+            describe_times(&new_component.meetings),
+        );
+        if old_times != new_times {
+            changes.push(
+                CourseChange::new(course, ChangeKind::MeetingTimeChanged)
+                    .with_section(code)
+                    .with_values(Some(old_times), Some(new_times)),
+            );
+        }
+
+        let (old_rooms, new_rooms) = (
+            describe_rooms(&old_component.meetings),
+            describe_rooms(&new_component.meetings),
+        );
+        if old_rooms != new_rooms {
+            changes.push(
+                CourseChange::new(course, ChangeKind::RoomChanged)
+                    .with_section(code)
+                    .with_values(Some(old_rooms), Some(new_rooms)),
+            );
+        }
+
+        // Courses saved before instructors were scraped have none, which isn't a change
+        let (old_surnames, new_surnames) = (
+            instructor_surnames(&old_component.meetings),
+            instructor_surnames(&new_component.meetings),
+        );
+        if !old_surnames.is_empty() && old_surnames != new_surnames {
+            let (old_instructors, new_instructors) = (
+                describe_instructors(&old_component.meetings),
+                describe_instructors(&new_component.meetings),
+            );
+            let non_empty = |names: String| (!names.is_empty()).then_some(names);
+            changes.push(
+                CourseChange::new(course, ChangeKind::InstructorsChanged)
+                    .with_section(code)
+                    .with_values(Some(old_instructors), non_empty(new_instructors)),
+            );
+        }
+    }
+
+    for code in old_by_code.keys() {
+        if !new_by_code.contains_key(code) {
+            changes
+                .push(CourseChange::new(course, ChangeKind::SectionCancelled).with_section(code));
+        }
+    }
+}
+
+fn diff_metadata(
+    old: &CourseMetadata,
+    new: &CourseMetadata,
+    course: &CourseObject,
+    changes: &mut Vec<CourseChange>,
+) {
+    if old.description != new.description {
+        changes.push(
+            CourseChange::new(course, ChangeKind::DescriptionChanged)
+                .with_values(old.description.clone(), new.description.clone()),
+        );
+    }
+
+    if old.prerequisites != new.prerequisites {
+        let to_json = |metadata: &CourseMetadata| {
+            metadata
+                .prerequisites
+                .clone()
+                .into_inner()
+                .and_then(|expr| serde_json::to_string(&expr).ok())
+        };
+
+        changes.push(
+            CourseChange::new(course, ChangeKind::PrerequisitesChanged)
+                .with_values(to_json(old), to_json(new)),
+        );
+    }
+}
+
+/// Computes the changes between two offerings of the same course
+///
+/// The offerings may be from two scrapes of the same semester or from two different semesters.
+/// Metadata is only compared when both sides have it, so a failed detail fetch is not
+/// reported as a removed description.
+///
+/// # Arguments
+/// * `old` - The earlier offering
+/// * `new` - The later offering, which the changes are attributed to
+///
+/// # Returns
+/// The changes to units, then to components by code, then to metadata
+pub fn diff_offering(old: &CourseObject, new: &CourseObject) -> Vec<CourseChange> {
+    let mut changes = Vec::new();
+
+    if old.course.units != new.course.units {
+        changes.push(
+            CourseChange::new(new, ChangeKind::UnitsChanged).with_values(
+                Some(old.course.units.to_string()),
+                Some(new.course.units.to_string()),
+            ),
+        );
+    }
+
+    diff_components(
+        &old.course.components,
+        &new.course.components,
+        new,
+        &mut changes,
+    );
+
+    if let (Some(old_metadata), Some(new_metadata)) = (&old.metadata, &new.metadata) {
+        diff_metadata(old_metadata, new_metadata, new, &mut changes);
+    }
+
+    changes
+}
+
+/// Computes the changes between a previously stored set of courses and a freshly scraped one.
+///
+/// Offerings are matched by course number, season, and year, then compared with
+/// [`diff_offering`].
+///
+/// # Arguments
+/// * `old` - The courses currently stored
+/// * `new` - The courses from the latest scrape
+///
+/// # Returns
+/// A vector of [`CourseChange`]s, ordered by semester and course number, with removed courses
+/// listed last
+pub fn diff_courses(old: &[CourseObject], new: &[CourseObject]) -> Vec<CourseChange> {
+    let old_by_key: BTreeMap<_, _> = old.iter().map(|c| (offering_key(c), c)).collect();
+    let new_by_key: BTreeMap<_, _> = new.iter().map(|c| (offering_key(c), c)).collect();
+
+    let mut changes = Vec::new();
+
+    for (key, new_course) in &new_by_key {
+        let Some(old_course) = old_by_key.get(key) else {
+            changes.push(CourseChange::new(new_course, ChangeKind::CourseAdded));
+            continue;
+        };
+
+        changes.extend(diff_offering(old_course, new_course));
+    }
+
+    for (key, old_course) in &old_by_key {
+        if !new_by_key.contains_key(key) {
+            changes.push(CourseChange::new(old_course, ChangeKind::CourseRemoved));
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        course_data::{ComponentType, CourseEntry, TimeRange},
+        days::Days,
+        requisite::Prerequisites,
+        units::Units,
+    };
+    use std::str::FromStr;
+
+    fn meeting(days: &str, begin: &str, end: &str, room: &str) -> Meeting {
+        Meeting {
+            days: Days::from_str(days).unwrap(),
+            time: TimeRange::from_strings(begin, end),
+            bldg_room: room.to_owned(),
+            campus: "Pittsburgh, Pennsylvania".to_owned(),
+            instructors: vec!["Iliano Cervesato".to_owned()],
+        }
+    }
+
+    fn component(code: &str, meetings: Vec<Meeting>) -> CourseComponent {
+        CourseComponent {
+            title: "Principles of Imperative Computation".to_owned(),
+            component_type: ComponentType::from(code.to_owned()),
+            code: code.to_owned(),
+            meetings,
+        }
+    }
+
+    fn course(number: &str, components: Vec<CourseComponent>) -> CourseObject {
+        CourseObject {
+            course: CourseEntry {
+                number: number.into(),
+                units: Units::new(12.0),
+                components,
+                season: Season::Fall,
+                year: Year(2025),
+                department: None,
+            },
+            metadata: None,
+        }
+    }
+
+    fn metadata(description: &str, prerequisites: &str) -> CourseMetadata {
+        CourseMetadata {
+            related_urls: vec![],
+            special_permission: false,
+            description: Some(description.to_owned()),
+            prerequisites: Prerequisites::from_str(prerequisites).unwrap(),
+            corequisites: Default::default(),
+            crosslisted: Default::default(),
+            notes: None,
+            reservations: vec![],
+        }
+    }
+
+    fn kinds(changes: &[CourseChange]) -> Vec<ChangeKind> {
+        changes.iter().map(|c| c.kind).collect()
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let courses = vec![course(
+            "15122",
+            vec![component(
+                "Lec",
+                vec![meeting("TR", "09:30AM", "10:50AM", "GHC 4401")],
+            )],
+        )];
+
+        assert!(diff_courses(&courses, &courses).is_empty());
+    }
+
+    #[test]
+    fn test_diff_courses_added_and_removed() {
+        let old = vec![course("15122", vec![]), course("15213", vec![])];
+        let new = vec![course("15122", vec![]), course("15251", vec![])];
+
+        let changes = diff_courses(&old, &new);
+        assert_eq!(
+            kinds(&changes),
+            vec![ChangeKind::CourseAdded, ChangeKind::CourseRemoved]
+        );
+        assert_eq!(changes[0].number.to_string(), "15251");
+        assert_eq!(changes[1].number.to_string(), "15213");
+    }
+
+    #[test]
+    fn test_diff_sections() {
+        let old = vec![course(
+            "15122",
+            vec![
+                component("A", vec![meeting("MWF", "09:00AM", "09:50AM", "GHC 4401")]),
+                component("B", vec![meeting("MWF", "10:00AM", "10:50AM", "GHC 4401")]),
+            ],
+        )];
+        let new = vec![course(
+            "15122",
+            vec![
+                component("A", vec![meeting("MW", "09:00AM", "09:50AM", "WEH 5403")]),
+                component("C", vec![meeting("TR", "10:00AM", "10:50AM", "GHC 4401")]),
+            ],
+        )];
+
+        let changes = diff_courses(&old, &new);
+        assert_eq!(
+            kinds(&changes),
+            vec![
+                ChangeKind::MeetingTimeChanged,
+                ChangeKind::RoomChanged,
+                ChangeKind::SectionAdded,
+                ChangeKind::SectionCancelled,
+            ]
+        );
+
+        assert_eq!(changes[0].section.as_deref(), Some("A"));
+        assert_eq!(changes[0].old_value.as_deref(), Some("MWF 09:00AM-09:50AM"));
+        assert_eq!(changes[0].new_value.as_deref(), Some("MW 09:00AM-09:50AM"));
+        assert_eq!(changes[1].new_value.as_deref(), Some("WEH 5403"));
+        assert_eq!(changes[2].section.as_deref(), Some("C"));
+        assert_eq!(changes[3].section.as_deref(), Some("B"));
+    }
+
+    #[test]
+    fn test_diff_metadata() {
+        let mut old = course("15122", vec![]);
+        old.metadata = Some(metadata("Old description", "15112"));
+
+        let mut new = course("15122", vec![]);
+        new.metadata = Some(metadata("New description", "15112 or 15110"));
+
+        let changes = diff_courses(&[old.clone()], &[new]);
+        assert_eq!(
+            kinds(&changes),
+            vec![
+                ChangeKind::DescriptionChanged,
+                ChangeKind::PrerequisitesChanged
+            ]
+        );
+        assert_eq!(changes[0].old_value.as_deref(), Some("Old description"));
+
+        // Missing metadata in the new scrape is not a change
+        let changes = diff_courses(&[old], &[course("15122", vec![])]);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_diff_offering_across_semesters() {
+        let mut old = course(
+            "15122",
+            vec![component(
+                "Lec",
+                vec![meeting("TR", "09:30AM", "10:50AM", "GHC 4401")],
+            )],
+        );
+        old.course.season = Season::Spring;
+
+        let mut lecture = meeting("TR", "09:30AM", "10:50AM", "GHC 4401");
+        lecture.instructors = vec!["Anne Kohlbrenner".to_owned()];
+        let mut new = course("15122", vec![component("Lec", vec![lecture])]);
+        new.course.units = Units::new(10.0);
+
+        let changes = diff_offering(&old, &new);
+        assert_eq!(
+            kinds(&changes),
+            vec![ChangeKind::UnitsChanged, ChangeKind::InstructorsChanged]
+        );
+        assert_eq!(changes[0].old_value.as_deref(), Some("12"));
+        assert_eq!(changes[1].section.as_deref(), Some("Lec"));
+        assert_eq!(changes[1].old_value.as_deref(), Some("Iliano Cervesato"));
+        assert_eq!(changes[1].new_value.as_deref(), Some("Anne Kohlbrenner"));
+        // Changes are attributed to the later offering
+        assert_eq!(changes[1].season, Season::Fall);
+    }
+
+    #[test]
+    fn test_diff_instructors_by_surname() {
+        let mut stored = meeting("TR", "09:30AM", "10:50AM", "GHC 4401");
+        stored.instructors = vec!["CERVESATO, ILIANO".to_owned()];
+        let mut scraped = meeting("TR", "09:30AM", "10:50AM", "GHC 4401");
+        scraped.instructors = vec!["Cervesato".to_owned()];
+        let old = course("15122", vec![component("Lec", vec![stored.clone()])]);
+        let new = course("15122", vec![component("Lec", vec![scraped])]);
+        assert!(diff_offering(&old, &new).is_empty());
+
+        // Stored meetings without instructors predate instructor scraping
+        stored.instructors.clear();
+        let old = course("15122", vec![component("Lec", vec![stored])]);
+        assert!(diff_offering(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn test_change_kind_round_trip() {
+        use strum::IntoEnumIterator;
+
+        for kind in ChangeKind::iter() {
+            assert_eq!(ChangeKind::from_str(kind.as_str()).unwrap(), kind);
+        }
+        assert_eq!(ChangeKind::SectionCancelled.as_str(), "section_cancelled");
+    }
+}
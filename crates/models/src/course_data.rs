@@ -1,5 +1,5 @@
 use crate::{
-    days::Days,
+    days::{DaySet, Days},
     requisite::{Prerequisites, Requisites},
     reservation::Reservation,
     syllabus_data::{Season, Year},
@@ -7,7 +7,7 @@ use crate::{
 };
 use chrono::NaiveTime;
 use sea_orm::EnumIter;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, de::Error as DeError};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     str::FromStr,
@@ -17,7 +17,7 @@ use std::{
 use sea_orm::DeriveActiveEnum;
 
 /// Represents a time range for a meeting
-#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct TimeRange {
     pub begin: NaiveTime,
     pub end: NaiveTime,
@@ -41,15 +41,70 @@ impl TimeRange {
     }
 }
 
+impl Display for TimeRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let fmt = "%I:%M%p";
+        write!(f, "{}-{}", self.begin.format(fmt), self.end.format(fmt))
+    }
+}
+
+/// The same time range on some days of the week, such as a student's free time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeBlock {
+    pub days: DaySet,
+    pub time: TimeRange,
+}
+
+impl FromStr for TimeBlock {
+    type Err = ();
+
+    /// Parses days followed by a 24-hour time range (e.g., "MWF 09:00-12:30")
+    fn from_str(block: &str) -> Result<Self, Self::Err> {
+        let (days, time) = block.trim().split_once(' ').ok_or(())?;
+        let (begin, end) = time.trim().split_once('-').ok_or(())?;
+
+        let days = DaySet::parse_strict(days).filter(|days| *days != DaySet::NONE);
+        let begin = NaiveTime::parse_from_str(begin, "%H:%M").ok();
+        let end = NaiveTime::parse_from_str(end, "%H:%M").ok();
+
+        Ok(Self {
+            days: days.ok_or(())?,
+            time: TimeRange::new(begin.ok_or(())?, end.ok_or(())?).ok_or(())?,
+        })
+    }
+}
+
 /// Represents a single meeting with campus and instructor
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Meeting {
     /// Days the meeting occurs
     pub days: Days,
     /// Time range for the meeting
     pub time: Option<TimeRange>,
+    /// Building and room (e.g., "GHC 4401")
+    pub bldg_room: String,
     /// CMU Campus
     pub campus: String,
+    /// Names of the instructors, empty if none were announced
+    #[serde(default)]
+    pub instructors: Vec<String>,
+}
+
+/// Splits a list of instructor names separated by commas or semicolons
+///
+/// Placeholders for instructors who haven't been announced yet (e.g., "Instructor TBA") are
+/// dropped.
+pub fn parse_instructors(names: &str) -> Vec<String> {
+    names
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|name| {
+            !name.is_empty()
+                && !name.eq_ignore_ascii_case("TBA")
+                && !name.eq_ignore_ascii_case("Instructor TBA")
+        })
+        .map(str::to_owned)
+        .collect()
 }
 
 /// Type of course component
@@ -82,7 +137,7 @@ impl From<String> for ComponentType {
 }
 
 /// Represents a lecture or section of a course
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CourseComponent {
     /// Course title (can vary by section)
     pub title: String,
@@ -122,6 +177,13 @@ impl From<String> for CourseNumber {
     }
 }
 
+impl<'de> Deserialize<'de> for CourseNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let num = String::deserialize(deserializer)?;
+        Self::from_str(&num).map_err(|_| D::Error::custom(format!("invalid course number: {num}")))
+    }
+}
+
 impl Display for CourseNumber {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "{}", self.0)
@@ -137,7 +199,7 @@ impl CourseNumber {
 }
 
 /// Represents a course entry from the schedule
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CourseEntry {
     /// Course number (e.g., "15122")
     pub number: CourseNumber,
@@ -149,10 +211,13 @@ pub struct CourseEntry {
     pub season: Season,
     /// Year that the course is offered
     pub year: Year,
+    /// Department the schedule lists the course under (e.g., "Computer Science")
+    #[serde(default)]
+    pub department: Option<String>,
 }
 
 /// Represents additional metadata for a course
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CourseMetadata {
     /// Related URLs for the course
     pub related_urls: Vec<String>,
@@ -173,7 +238,7 @@ pub struct CourseMetadata {
 }
 
 /// Represents a course object with additional metadata
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CourseObject {
     /// The base course entry
     pub course: CourseEntry,
@@ -184,6 +249,7 @@ pub struct CourseObject {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::reservation::Restriction;
     use chrono::{NaiveTime, Timelike};
 
     #[test]
@@ -219,6 +285,22 @@ mod test {
         assert!(TimeRange::from_strings("11:00AM", "09:00AM").is_none());
     }
 
+    #[test]
+    fn test_time_block_from_str() {
+        let block = TimeBlock::from_str("MWF 09:00-12:30").unwrap();
+        assert_eq!(
+            block.days,
+            DaySet::MONDAY | DaySet::WEDNESDAY | DaySet::FRIDAY
+        );
+        assert_eq!(block.time.begin, NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        assert_eq!(block.time.end, NaiveTime::from_hms_opt(12, 30, 0).unwrap());
+
+        assert!(TimeBlock::from_str("MWF").is_err());
+        assert!(TimeBlock::from_str("XYZ 09:00-12:30").is_err());
+        assert!(TimeBlock::from_str("TR 13:00-11:00").is_err());
+        assert!(TimeBlock::from_str("TR 1pm-3pm").is_err());
+    }
+
     #[test]
     fn test_course_number_round_trip() {
         for course_number in [
@@ -230,4 +312,52 @@ mod test {
             assert_eq!(CourseNumber::from_str(&s).unwrap(), course_number);
         }
     }
+
+    #[test]
+    fn test_course_object_serde_round_trip() {
+        let course_obj = CourseObject {
+            course: CourseEntry {
+                number: "15122".into(),
+                units: Units::from_str("12").unwrap(),
+                components: vec![CourseComponent {
+                    title: "Principles of Imperative Computation".to_string(),
+                    component_type: ComponentType::Lecture,
+                    code: "Lec 1".to_string(),
+                    meetings: vec![Meeting {
+                        days: Days::from_str("TR").unwrap(),
+                        time: TimeRange::from_strings("09:30AM", "10:50AM"),
+                        bldg_room: "GHC 4401".to_string(),
+                        campus: "Pittsburgh, Pennsylvania".to_string(),
+                        instructors: vec!["Iliano Cervesato".to_string()],
+                    }],
+                }],
+                season: Season::Fall,
+                year: Year(2025),
+                department: Some("Computer Science".to_string()),
+            },
+            metadata: Some(CourseMetadata {
+                related_urls: vec![],
+                special_permission: false,
+                description: Some("Write correct programs.".to_string()),
+                prerequisites: Prerequisites::from_str("15112 or 15110").unwrap(),
+                corequisites: Requisites::from(vec!["15151".to_string()]),
+                crosslisted: Requisites::default(),
+                notes: None,
+                reservations: vec![Reservation {
+                    section: "A".to_string(),
+                    restrictions: vec![
+                        Restriction::from_str("Some reservations are for Freshmen in SCS").unwrap(),
+                    ],
+                }],
+            }),
+        };
+
+        let json = serde_json::to_string(&course_obj).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CourseObject>(&json).unwrap(),
+            course_obj
+        );
+
+        assert!(serde_json::from_str::<CourseNumber>("\"15-122\"").is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as DeError};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not},
@@ -6,7 +6,9 @@ use std::{
 };
 
 /// Represents the days of the week a meeting occurs
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+///
+/// Serialized as its string form (e.g., "MWF").
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct DaySet(u8);
 
@@ -60,6 +62,22 @@ impl DaySet {
     pub fn remove(&mut self, day: Self) {
         *self &= !day;
     }
+
+    /// Parses days written only with day letters (e.g., "MWF"), unlike [`DaySet::from_str`],
+    /// which skips any other characters
+    pub fn parse_strict(days: &str) -> Option<Self> {
+        days.chars().try_fold(Self::NONE, |set, c| {
+            Self::DAY_CHARS
+                .iter()
+                .find(|(_, day_char)| *day_char == c)
+                .map(|(day, _)| set | *day)
+        })
+    }
+
+    /// The days as a bitmask, Monday being the lowest bit
+    pub fn bits(self) -> u8 {
+        self.0
+    }
 }
 
 impl FromStr for DaySet {
@@ -95,6 +113,19 @@ impl Display for DaySet {
     }
 }
 
+impl Serialize for DaySet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DaySet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let days = String::deserialize(deserializer)?;
+        Self::parse_strict(&days).ok_or_else(|| D::Error::custom(format!("invalid days: {days}")))
+    }
+}
+
 // Bitwise operators
 impl BitOr for DaySet {
     type Output = Self;
@@ -134,7 +165,9 @@ impl BitAndAssign for DaySet {
 }
 
 /// Represents when a meeting can occur
-#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+///
+/// Serialized as its string form, either the days (e.g., "MWF") or "TBA".
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum Days {
     /// Specific days
     Days(DaySet),
@@ -164,6 +197,24 @@ impl FromStr for Days {
     }
 }
 
+impl Serialize for Days {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Days {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let days = String::deserialize(deserializer)?;
+        match days.as_str() {
+            "TBA" => Ok(Self::TBA),
+            _ => DaySet::parse_strict(&days)
+                .map(Self::Days)
+                .ok_or_else(|| D::Error::custom(format!("invalid days: {days}"))),
+        }
+    }
+}
+
 impl From<String> for Days {
     fn from(days: String) -> Self {
         Self::from_str(&days).unwrap_or_default()
@@ -187,6 +238,16 @@ mod test {
         assert!(!days.contains(DaySet::SUNDAY));
     }
 
+    #[test]
+    fn test_day_set_parse_strict() {
+        let days = DaySet::parse_strict("TR").unwrap();
+        assert_eq!(days, DaySet::TUESDAY | DaySet::THURSDAY);
+        assert_eq!(days.bits(), 0b01010);
+        assert_eq!(DaySet::parse_strict(""), Some(DaySet::NONE));
+        assert!(DaySet::parse_strict("M W").is_none());
+        assert!(DaySet::parse_strict("TBA").is_none());
+    }
+
     #[test]
     fn test_day_set_display() {
         let mut days = DaySet::new();
@@ -215,4 +276,20 @@ mod test {
         let days = Days::from_str("MWF").unwrap();
         assert_eq!(days.to_string(), "MWF");
     }
+
+    #[test]
+    fn test_days_serde_round_trip() {
+        for days in [Days::from_str("TR").unwrap(), Days::TBA] {
+            let json = serde_json::to_string(&days).unwrap();
+            assert_eq!(json, format!("\"{days}\""));
+            assert_eq!(serde_json::from_str::<Days>(&json).unwrap(), days);
+        }
+
+        assert!(serde_json::from_str::<Days>("\"M W\"").is_err());
+        assert!(serde_json::from_str::<DaySet>("\"TBA\"").is_err());
+        assert_eq!(
+            serde_json::from_str::<DaySet>("\"MWF\"").unwrap(),
+            DaySet::MONDAY | DaySet::WEDNESDAY | DaySet::FRIDAY
+        );
+    }
 }
@@ -0,0 +1,127 @@
+use crate::units::Units;
+use std::{collections::HashMap, str::FromStr};
+
+/// Fewest units in each units bucket with the bucket's label, each bucket ending where the
+/// next one begins (e.g., "6-<10" holds courses with at least 6 and fewer than 10 units)
+const UNITS_BUCKETS: [(f32, &str); 4] = [
+    (0.0, "0-<6"),
+    (6.0, "6-<10"),
+    (10.0, "10-<13"),
+    (13.0, "13+"),
+];
+
+/// Label of the units bucket of courses with variable units
+pub const VARIABLE_UNITS_BUCKET: &str = "VAR";
+
+/// Number of courses with each value of a filter, in display order, leaving out values
+/// without any courses
+pub type FacetCounts = Vec<(String, u64)>;
+
+/// Facet counts of the courses matching a search
+///
+/// Each facet is counted with every active filter except its own, so selecting another value
+/// of the same filter widens the results by the value's count.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Facets {
+    /// First two digits of the course number
+    pub department: FacetCounts,
+    pub season: FacetCounts,
+    pub year: FacetCounts,
+    /// Units buckets (e.g., "6-<10"), see [`units_buckets`]
+    pub units: FacetCounts,
+    pub campus: FacetCounts,
+    /// Days of the week of a meeting (e.g., "MWF" or "TBA")
+    pub days: FacetCounts,
+    /// When a meeting starts ("morning", "afternoon", "evening", or "TBA")
+    pub time_of_day: FacetCounts,
+}
+
+/// Labels of the units buckets a course falls in
+///
+/// A course that can be taken for a range of units (e.g., "3-12") falls in every bucket
+/// between its fewest and most units.
+pub fn units_buckets(units: &Units) -> Vec<&'static str> {
+    if matches!(units, Units::VAR) {
+        return vec![VARIABLE_UNITS_BUCKET];
+    }
+
+    let (min, max) = (units.min_value(), units.max_value());
+    UNITS_BUCKETS
+        .iter()
+        .enumerate()
+        .filter(|(i, (start, _))| {
+            let end = UNITS_BUCKETS
+                .get(i + 1)
+                .map_or(f32::INFINITY, |(next, _)| *next);
+            min < end && max >= *start
+        })
+        .map(|(_, (_, label))| *label)
+        .collect()
+}
+
+/// Counts courses by units bucket
+///
+/// # Arguments
+/// * `counts` - Number of courses with each units string (e.g., "12" or "3-9")
+///
+/// # Returns
+/// The number of courses in each units bucket, smallest first, skipping units that can't be
+/// parsed
+pub fn count_units_buckets(counts: impl IntoIterator<Item = (String, u64)>) -> FacetCounts {
+    let mut buckets: HashMap<&str, u64> = HashMap::new();
+    for (units, count) in counts {
+        let Ok(units) = Units::from_str(&units) else {
+            continue;
+        };
+        for bucket in units_buckets(&units) {
+            *buckets.entry(bucket).or_default() += count;
+        }
+    }
+
+    UNITS_BUCKETS
+        .iter()
+        .map(|(_, label)| *label)
+        .chain([VARIABLE_UNITS_BUCKET])
+        .filter_map(|label| Some((label.to_owned(), buckets.remove(label)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buckets(units: &str) -> Vec<&'static str> {
+        units_buckets(&Units::from_str(units).unwrap())
+    }
+
+    #[test]
+    fn test_units_buckets() {
+        assert_eq!(buckets("12"), vec!["10-<13"]);
+        assert_eq!(buckets("4.5"), vec!["0-<6"]);
+        assert_eq!(buckets("5.5"), vec!["0-<6"]);
+        assert_eq!(buckets("6"), vec!["6-<10"]);
+        assert_eq!(buckets("9.5"), vec!["6-<10"]);
+        assert_eq!(buckets("3-12"), vec!["0-<6", "6-<10", "10-<13"]);
+        assert_eq!(buckets("36,48"), vec!["13+"]);
+        assert_eq!(buckets("VAR"), vec!["VAR"]);
+    }
+
+    #[test]
+    fn test_count_units_buckets() {
+        let counts = count_units_buckets([
+            ("VAR".to_owned(), 2),
+            ("12".to_owned(), 5),
+            ("9".to_owned(), 3),
+            ("6-12".to_owned(), 1),
+            ("not units".to_owned(), 4),
+        ]);
+        assert_eq!(
+            counts,
+            vec![
+                ("6-<10".to_owned(), 4),
+                ("10-<13".to_owned(), 6),
+                ("VAR".to_owned(), 2),
+            ]
+        );
+    }
+}
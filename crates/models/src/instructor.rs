@@ -0,0 +1,184 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// An instructor name split into its parts, lowercased so differently written names compare
+/// equal
+///
+/// The schedule of classes lists instructors by surname (e.g., "Cervesato"), while FCE exports
+/// write them as "CERVESATO, ILIANO".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructorName {
+    pub surname: String,
+    /// Given names, if the name has them
+    pub given: Option<String>,
+}
+
+impl InstructorName {
+    /// Splits a name written as "Surname, Given", "Given Surname", or just "Surname"
+    pub fn parse(name: &str) -> Self {
+        let name = name.trim().to_lowercase();
+        let non_empty = |part: &str| Some(part.trim().to_owned()).filter(|part| !part.is_empty());
+
+        if let Some((surname, given)) = name.split_once(',') {
+            return Self {
+                surname: surname.trim().to_owned(),
+                given: non_empty(given),
+            };
+        }
+        match name.rsplit_once(char::is_whitespace) {
+            Some((given, surname)) => Self {
+                surname: surname.to_owned(),
+                given: non_empty(given),
+            },
+            None => Self {
+                surname: name,
+                given: None,
+            },
+        }
+    }
+
+    /// Whether both names could belong to the same person: the surnames are the same, and the
+    /// given names start with the same letter when both names have them
+    pub fn could_be(&self, other: &Self) -> bool {
+        let initial = |given: &Option<String>| given.as_ref().and_then(|g| g.chars().next());
+        self.surname == other.surname
+            && match (initial(&self.given), initial(&other.given)) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+/// What a scraped instructor name refers to among the stored instructors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructorMatch<Id> {
+    Found(Id),
+    /// Several stored instructors could be meant, so none is picked
+    Ambiguous,
+    /// No stored instructor could be meant
+    Missing,
+}
+
+/// Stored instructors grouped by surname, to find who a scraped name refers to
+#[derive(Debug, Clone)]
+pub struct InstructorDirectory<Id> {
+    by_surname: HashMap<String, Vec<(Id, InstructorName)>>,
+}
+
+impl<Id: Copy + Eq + Hash> InstructorDirectory<Id> {
+    pub fn new<'a>(instructors: impl IntoIterator<Item = (Id, &'a str)>) -> Self {
+        let mut directory = Self {
+            by_surname: HashMap::new(),
+        };
+        for (id, name) in instructors {
+            directory.insert(id, name);
+        }
+        directory
+    }
+
+    pub fn insert(&mut self, id: Id, name: &str) {
+        let name = InstructorName::parse(name);
+        self.by_surname
+            .entry(name.surname.clone())
+            .or_default()
+            .push((id, name));
+    }
+
+    /// Finds the stored instructor a scraped name refers to
+    ///
+    /// Instructors with given names are preferred over ones stored by surname alone. When
+    /// several could be meant, the one who taught the course before is picked, if there's
+    /// exactly one.
+    ///
+    /// # Arguments
+    /// * `name` - The scraped name (e.g., "Cervesato")
+    /// * `taught_course` - Whether a stored instructor taught the course the name was scraped
+    ///   for
+    pub fn find(&self, name: &str, taught_course: impl Fn(Id) -> bool) -> InstructorMatch<Id> {
+        let name = InstructorName::parse(name);
+        let candidates: Vec<&(Id, InstructorName)> = self
+            .by_surname
+            .get(&name.surname)
+            .into_iter()
+            .flatten()
+            .filter(|(_, candidate)| candidate.could_be(&name))
+            .collect();
+
+        let (named, surname_only): (Vec<_>, Vec<_>) = candidates
+            .into_iter()
+            .partition(|(_, candidate)| candidate.given.is_some());
+        let candidates = if named.is_empty() {
+            surname_only
+        } else {
+            named
+        };
+
+        match candidates.as_slice() {
+            [] => InstructorMatch::Missing,
+            [(id, _)] => InstructorMatch::Found(*id),
+            _ => {
+                let mut taught = candidates.iter().filter(|(id, _)| taught_course(*id));
+                match (taught.next(), taught.next()) {
+                    (Some((id, _)), None) => InstructorMatch::Found(*id),
+                    _ => InstructorMatch::Ambiguous,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instructor_name() {
+        let name = InstructorName::parse("CERVESATO, ILIANO");
+        assert_eq!(name.surname, "cervesato");
+        assert_eq!(name.given.as_deref(), Some("iliano"));
+
+        assert_eq!(InstructorName::parse("Iliano Cervesato"), name);
+        assert_eq!(
+            InstructorName::parse(" Cervesato "),
+            InstructorName {
+                surname: "cervesato".to_owned(),
+                given: None,
+            }
+        );
+        assert!(InstructorName::parse("Cervesato").could_be(&name));
+        assert!(!InstructorName::parse("Anne Cervesato").could_be(&name));
+    }
+
+    #[test]
+    fn test_find_instructor() {
+        let directory = InstructorDirectory::new([
+            (1, "CERVESATO, ILIANO"),
+            (2, "Cervesato"),
+            (3, "SMITH, JOHN"),
+            (4, "SMITH, ANNE"),
+            (5, "Kaynar"),
+        ]);
+        let never = |_| false;
+
+        // A full name is preferred over the same instructor stored by surname
+        assert_eq!(
+            directory.find("Cervesato", never),
+            InstructorMatch::Found(1)
+        );
+        assert_eq!(directory.find("Kaynar", never), InstructorMatch::Found(5));
+        assert_eq!(
+            directory.find("Kohlbrenner", never),
+            InstructorMatch::Missing
+        );
+
+        // Different people with the same surname aren't merged
+        assert_eq!(directory.find("Smith", never), InstructorMatch::Ambiguous);
+        assert_eq!(
+            directory.find("Smith", |id| id == 4),
+            InstructorMatch::Found(4)
+        );
+        assert_eq!(
+            directory.find("John Smith", never),
+            InstructorMatch::Found(3)
+        );
+    }
+}
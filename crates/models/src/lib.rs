@@ -1,7 +1,18 @@
+pub mod autocomplete;
+pub mod availability;
+pub mod course_change;
 pub mod course_data;
 pub mod days;
+pub mod facets;
+pub mod instructor;
+pub mod plan;
+pub mod prerequisite_graph;
+pub mod program;
 pub mod requisite;
 pub mod reservation;
 pub mod reservation_type;
+pub mod search_query;
+pub mod semester_report;
+pub mod similarity;
 pub mod syllabus_data;
 pub mod units;
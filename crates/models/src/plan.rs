@@ -0,0 +1,386 @@
+use crate::{
+    availability::OfferingHistory,
+    requisite::{Expr, Requisites},
+    syllabus_data::{Season, Year},
+    units::Units,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Most units a student may take in a semester without an overload petition
+pub const DEFAULT_MAX_UNITS: f32 = 54.0;
+
+/// Chance of being offered below which a course planned in a season it has been offered in is
+/// reported
+const UNLIKELY_OFFERED: f32 = 0.5;
+
+/// A semester of a student's plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedSemester {
+    pub season: Season,
+    pub year: Year,
+    /// Course numbers without a dash (e.g., "15122")
+    pub courses: Vec<String>,
+}
+
+/// What a plan needs to know about a course, from its offerings over the years
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseHistory {
+    /// Prerequisites of the most recent offering
+    pub prerequisites: Option<Expr>,
+    /// Corequisites of the most recent offering
+    pub corequisites: Requisites,
+    /// Units of the most recent offering
+    pub units: Units,
+    /// Every semester the course has been offered in
+    pub offerings: OfferingHistory,
+}
+
+/// A problem with a course in a plan
+#[derive(Debug, Clone, PartialEq)]
+pub enum CourseIssue {
+    /// The course has never been offered
+    UnknownCourse,
+    /// The course is planned more than once
+    Repeated,
+    /// Prerequisites aren't completed in earlier semesters; holds what remains
+    MissingPrerequisites(Expr),
+    /// Corequisites aren't taken in the same or an earlier semester
+    MissingCorequisites(Vec<String>),
+    /// The course has never been offered in the planned season
+    NotOffered { offered: Vec<Season> },
+    /// The course has been offered in the planned season, but probably won't be that year;
+    /// holds the chance it is
+    UnlikelyOffered(f32),
+}
+
+impl Display for CourseIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::UnknownCourse => write!(f, "Course has never been offered"),
+            Self::Repeated => write!(f, "Course is planned more than once"),
+            Self::MissingPrerequisites(remaining) => {
+                write!(f, "Missing prerequisites: {remaining}")
+            }
+            Self::MissingCorequisites(courses) => {
+                write!(f, "Missing corequisites: {}", courses.join(", "))
+            }
+            Self::NotOffered { offered } => {
+                let seasons: Vec<&str> = offered.iter().map(Season::as_full_str).collect();
+                write!(f, "Only offered in {}", seasons.join(", "))
+            }
+            Self::UnlikelyOffered(likelihood) => {
+                write!(
+                    f,
+                    "Only {:.0}% likely to be offered this semester",
+                    likelihood * 100.0
+                )
+            }
+        }
+    }
+}
+
+/// The result of validating a course in a plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseReport {
+    pub number: String,
+    /// Chance the course is offered in the planned semester, if it has been offered before
+    pub likelihood: Option<f32>,
+    pub issues: Vec<CourseIssue>,
+}
+
+/// The result of validating a semester of a plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemesterReport {
+    pub season: Season,
+    pub year: Year,
+    /// Fewest units the semester's courses add up to, counting variable-unit courses as 0
+    pub min_units: f32,
+    /// Whether `min_units` exceeds the overload cap
+    pub overloaded: bool,
+    pub courses: Vec<CourseReport>,
+}
+
+impl SemesterReport {
+    /// Whether the semester has no problems
+    pub fn is_valid(&self) -> bool {
+        !self.overloaded && self.courses.iter().all(|course| course.issues.is_empty())
+    }
+}
+
+/// Checks prerequisites against the courses taken before a semester, letting courses that may
+/// be taken concurrently come from the semester itself
+///
+/// Requirements that aren't courses, like instructor permission, can't be shown by a plan, so
+/// they are assumed to be met.
+fn prerequisites_met(expr: &Expr, earlier: &[String], through_current: &[String]) -> bool {
+    match expr {
+        Expr::Concurrent(_) => expr.evaluate(through_current),
+        Expr::Course(_) | Expr::MinGrade { .. } => expr.evaluate(earlier),
+        Expr::InstructorPermission | Expr::ClassStanding(_) | Expr::Text(_) => true,
+        Expr::And(left, right) => {
+            prerequisites_met(left, earlier, through_current)
+                && prerequisites_met(right, earlier, through_current)
+        }
+        Expr::Or(left, right) => {
+            prerequisites_met(left, earlier, through_current)
+                || prerequisites_met(right, earlier, through_current)
+        }
+    }
+}
+
+/// Validates a multi-semester plan
+///
+/// # Arguments
+/// * `semesters` - The plan's semesters, in the order they are taken
+/// * `history` - The history of every course in the plan, keyed by course number
+/// * `completed` - Courses completed before the plan starts
+/// * `max_units` - Most units allowed in a semester before it is an overload
+///
+/// # Returns
+/// A [`SemesterReport`] for each semester, with the issues of each course
+pub fn validate_plan(
+    semesters: &[PlannedSemester],
+    history: &HashMap<String, CourseHistory>,
+    completed: &[String],
+    max_units: f32,
+) -> Vec<SemesterReport> {
+    let mut earlier = completed.to_vec();
+    let mut planned = HashSet::new();
+    let mut reports = Vec::with_capacity(semesters.len());
+
+    for semester in semesters {
+        let through_current: Vec<String> =
+            earlier.iter().chain(&semester.courses).cloned().collect();
+        let mut min_total = 0.0;
+
+        let courses = semester
+            .courses
+            .iter()
+            .map(|number| {
+                let mut issues = Vec::new();
+                if !planned.insert(number.as_str()) || completed.contains(number) {
+                    issues.push(CourseIssue::Repeated);
+                }
+
+                let Some(course) = history.get(number) else {
+                    issues.push(CourseIssue::UnknownCourse);
+                    return CourseReport {
+                        number: number.clone(),
+                        likelihood: None,
+                        issues,
+                    };
+                };
+                min_total += course.units.min_value();
+
+                if let Some(prerequisites) = &course.prerequisites
+                    && !prerequisites_met(prerequisites, &earlier, &through_current)
+                {
+                    let remaining = prerequisites
+                        .simplify(&earlier)
+                        .unwrap_or_else(|| prerequisites.clone());
+                    issues.push(CourseIssue::MissingPrerequisites(remaining));
+                }
+
+                let missing: Vec<String> = course
+                    .corequisites
+                    .iter()
+                    .filter(|corequisite| !through_current.contains(corequisite))
+                    .cloned()
+                    .collect();
+                if !missing.is_empty() {
+                    issues.push(CourseIssue::MissingCorequisites(missing));
+                }
+
+                let seasons = course.offerings.seasons();
+                let likelihood = course.offerings.likelihood(semester.season, semester.year);
+                if !seasons.contains(&semester.season) {
+                    let mut offered: Vec<Season> = seasons.into_iter().collect();
+                    offered.sort_by_key(Season::term_order);
+                    issues.push(CourseIssue::NotOffered { offered });
+                } else if likelihood < UNLIKELY_OFFERED {
+                    issues.push(CourseIssue::UnlikelyOffered(likelihood));
+                }
+
+                CourseReport {
+                    number: number.clone(),
+                    likelihood: Some(likelihood),
+                    issues,
+                }
+            })
+            .collect();
+
+        reports.push(SemesterReport {
+            season: semester.season,
+            year: semester.year,
+            min_units: min_total,
+            overloaded: min_total > max_units,
+            courses,
+        });
+        earlier = through_current;
+    }
+
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn course(
+        prerequisites: &str,
+        corequisites: &[&str],
+        units: &str,
+        seasons: &[Season],
+    ) -> CourseHistory {
+        CourseHistory {
+            prerequisites: (!prerequisites.is_empty()).then(|| prerequisites.parse().unwrap()),
+            corequisites: corequisites
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .into(),
+            units: units.parse().unwrap(),
+            offerings: OfferingHistory::new(
+                recorded()
+                    .into_iter()
+                    .filter(|(season, _)| seasons.contains(season)),
+                recorded(),
+            ),
+        }
+    }
+
+    /// Every semester from 2022 through 2024
+    fn recorded() -> Vec<(Season, Year)> {
+        (2022..=2024)
+            .flat_map(|year| Season::all().into_iter().map(move |s| (s, Year(year))))
+            .collect()
+    }
+
+    fn semester(season: Season, year: u16, courses: &[&str]) -> PlannedSemester {
+        PlannedSemester {
+            season,
+            year: Year(year),
+            courses: courses.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    fn history() -> HashMap<String, CourseHistory> {
+        use Season::*;
+        HashMap::from([
+            (
+                "15112".to_owned(),
+                course("", &[], "12", &[Fall, Spring, Summer1]),
+            ),
+            ("21127".to_owned(), course("", &[], "12", &[Fall, Spring])),
+            (
+                "15122".to_owned(),
+                course("15112 and concurrent 21127", &[], "12", &[Fall, Spring]),
+            ),
+            (
+                "15150".to_owned(),
+                course(
+                    "15122 and (21127 or permission of instructor)",
+                    &["15151"],
+                    "12",
+                    &[Fall, Spring],
+                ),
+            ),
+            ("15151".to_owned(), course("21127", &[], "12", &[Fall])),
+            (
+                "15213".to_owned(),
+                course("15122", &[], "12", &[Fall, Spring]),
+            ),
+            ("99101".to_owned(), course("", &[], "3", &[Fall])),
+            ("15400".to_owned(), course("", &[], "VAR", &[Spring])),
+        ])
+    }
+
+    #[test]
+    fn test_validate_valid_plan() {
+        let plan = [
+            semester(Season::Fall, 2025, &["15112", "21127", "99101"]),
+            semester(Season::Spring, 2026, &["15122"]),
+            semester(Season::Fall, 2026, &["15150", "15151", "15213"]),
+        ];
+
+        let reports = validate_plan(&plan, &history(), &[], DEFAULT_MAX_UNITS);
+        assert!(reports.iter().all(SemesterReport::is_valid));
+        assert_eq!(reports[0].min_units, 27.0);
+
+        // Concurrent prerequisites may be taken in the same semester
+        let plan = [semester(Season::Fall, 2025, &["15122", "21127"])];
+        let reports = validate_plan(&plan, &history(), &["15112".to_owned()], 54.0);
+        assert!(reports[0].is_valid());
+    }
+
+    #[test]
+    fn test_validate_issues() {
+        let plan = [
+            semester(Season::Fall, 2025, &["15122", "15112"]),
+            semester(
+                Season::Spring,
+                2026,
+                &["15150", "15151", "15112", "98999", "15400"],
+            ),
+        ];
+
+        let reports = validate_plan(&plan, &history(), &[], 30.0);
+        let issues = |semester: usize, course: usize| &reports[semester].courses[course].issues;
+
+        // 15-112 is only completed after the semester, and 21-127 isn't planned at all
+        assert_eq!(
+            issues(0, 0),
+            &vec![CourseIssue::MissingPrerequisites(
+                "15112 and concurrent 21127".parse().unwrap()
+            )]
+        );
+        assert!(issues(0, 1).is_empty());
+        assert!(!reports[0].overloaded);
+
+        // Instructor permission stands in for 21-127, and 15-151 is a corequisite taken alongside
+        assert!(issues(1, 0).is_empty());
+        assert_eq!(
+            issues(1, 1),
+            &vec![
+                CourseIssue::MissingPrerequisites(Expr::Course("21127".to_owned())),
+                CourseIssue::NotOffered {
+                    offered: vec![Season::Fall]
+                },
+            ]
+        );
+        assert_eq!(issues(1, 2), &vec![CourseIssue::Repeated]);
+        assert_eq!(issues(1, 3), &vec![CourseIssue::UnknownCourse]);
+        assert!(issues(1, 4).is_empty());
+
+        assert_eq!(reports[1].courses[3].likelihood, None);
+        assert_eq!(reports[1].courses[4].likelihood, Some(1.0));
+
+        // Unknown and variable-unit courses don't count towards the total
+        assert_eq!(reports[1].min_units, 36.0);
+        assert!(reports[1].overloaded);
+    }
+
+    #[test]
+    fn test_validate_unlikely_offering() {
+        // Offered in the springs of even years only
+        let mut history = history();
+        history.get_mut("15400").unwrap().offerings = OfferingHistory::new(
+            [(Season::Spring, Year(2022)), (Season::Spring, Year(2024))],
+            recorded(),
+        );
+
+        let plan = [semester(Season::Spring, 2025, &["15400"])];
+        let reports = validate_plan(&plan, &history, &[], DEFAULT_MAX_UNITS);
+        assert_eq!(
+            reports[0].courses[0].issues,
+            vec![CourseIssue::UnlikelyOffered(0.0)]
+        );
+
+        let plan = [semester(Season::Spring, 2026, &["15400"])];
+        let reports = validate_plan(&plan, &history, &[], DEFAULT_MAX_UNITS);
+        assert!(reports[0].is_valid());
+    }
+}
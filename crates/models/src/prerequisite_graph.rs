@@ -0,0 +1,366 @@
+use crate::requisite::Expr;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// Error returned when no path to a course can be found
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathError {
+    /// The course isn't in the graph
+    UnknownCourse(String),
+    /// Every way to satisfy the prerequisites leads back to a course already on the chain,
+    /// listed from the first repeated course to its repetition
+    Cycle(Vec<String>),
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::UnknownCourse(number) => write!(f, "Unknown course: {number}"),
+            Self::Cycle(cycle) => write!(f, "Prerequisite cycle: {}", cycle.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// The courses a student must take to become eligible for a course
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoursePath {
+    /// The course the path leads to
+    pub target: String,
+    /// Courses to take in each semester, earliest first; a course is only placed after the
+    /// courses it requires, or alongside ones it may take concurrently
+    pub semesters: Vec<Vec<String>>,
+    /// Requirements that aren't courses, like instructor permission, which the path relies on
+    pub conditions: Vec<Expr>,
+}
+
+/// The courses chosen to satisfy an expression
+#[derive(Debug, Clone, Default)]
+struct Plan {
+    /// Each chosen course with the courses it requires and whether it may take them
+    /// concurrently
+    requires: BTreeMap<String, Vec<(String, bool)>>,
+    /// Non-course requirements the plan relies on
+    conditions: Vec<Expr>,
+    /// Courses the expression itself names, as opposed to their own prerequisites
+    direct: Vec<(String, bool)>,
+}
+
+impl Plan {
+    fn merge(mut self, other: Plan) -> Plan {
+        for (course, requires) in other.requires {
+            self.requires.entry(course).or_insert(requires);
+        }
+        for condition in other.conditions {
+            if !self.conditions.contains(&condition) {
+                self.conditions.push(condition);
+            }
+        }
+        self.direct.extend(other.direct);
+        self
+    }
+
+    /// Plans that rely on conditions the student may not meet are worse than any plan that
+    /// doesn't, and fewer courses are better
+    fn cost(&self) -> (bool, usize) {
+        (!self.conditions.is_empty(), self.requires.len())
+    }
+}
+
+/// The prerequisites of every course, used to find paths to a course
+#[derive(Debug, Clone, Default)]
+pub struct PrerequisiteGraph {
+    prerequisites: HashMap<String, Option<Expr>>,
+}
+
+impl PrerequisiteGraph {
+    /// Creates a graph from the prerequisites of each course number
+    pub fn new(prerequisites: HashMap<String, Option<Expr>>) -> Self {
+        Self { prerequisites }
+    }
+
+    /// Finds the fewest courses a student must take to become eligible for a course
+    ///
+    /// Where an `or` offers alternatives, the one needing the fewest courses is chosen,
+    /// preferring alternatives that don't rely on instructor permission or other conditions.
+    /// Alternatives that lead back to a course already on the chain are skipped.
+    ///
+    /// # Arguments
+    /// * `target` - The course number to reach
+    /// * `completed` - Course numbers the student has already completed
+    ///
+    /// # Returns
+    /// The [`CoursePath`], or a [`PathError`] if the course is unknown or every way to reach it
+    /// is cyclic
+    pub fn path(&self, target: &str, completed: &[String]) -> Result<CoursePath, PathError> {
+        let prerequisites = self
+            .prerequisites
+            .get(target)
+            .ok_or_else(|| PathError::UnknownCourse(target.to_owned()))?;
+
+        let mut expansion = Expansion {
+            completed: completed.iter().map(String::as_str).collect(),
+            chain: vec![target.to_owned()],
+            ..Expansion::default()
+        };
+        let plan = match prerequisites {
+            Some(expr) => self.expand(expr, &mut expansion)?,
+            None => Plan::default(),
+        };
+
+        // A course goes in the semester after everything it requires, or the same semester
+        // as courses it may take concurrently
+        let mut semester_of: HashMap<&str, usize> = HashMap::new();
+        fn semester<'a>(
+            course: &'a str,
+            requires: &'a BTreeMap<String, Vec<(String, bool)>>,
+            semester_of: &mut HashMap<&'a str, usize>,
+        ) -> usize {
+            if let Some(&semester) = semester_of.get(course) {
+                return semester;
+            }
+
+            let semester = requires
+                .get(course)
+                .into_iter()
+                .flatten()
+                .map(|(required, concurrent)| {
+                    semester(required, requires, semester_of) + usize::from(!concurrent)
+                })
+                .max()
+                .unwrap_or(1)
+                .max(1);
+            semester_of.insert(course, semester);
+            semester
+        }
+
+        let mut semesters: Vec<Vec<String>> = Vec::new();
+        for course in plan.requires.keys() {
+            let index = semester(course, &plan.requires, &mut semester_of) - 1;
+            if semesters.len() <= index {
+                semesters.resize(index + 1, Vec::new());
+            }
+            semesters[index].push(course.clone());
+        }
+
+        Ok(CoursePath {
+            target: target.to_owned(),
+            semesters,
+            conditions: plan.conditions,
+        })
+    }
+
+    /// Chooses the courses that satisfy an expression
+    fn expand(&self, expr: &Expr, expansion: &mut Expansion) -> Result<Plan, PathError> {
+        match expr {
+            Expr::Course(course) | Expr::MinGrade { course, .. } => {
+                self.expand_course(course, false, expansion)
+            }
+            Expr::Concurrent(course) => self.expand_course(course, true, expansion),
+            Expr::InstructorPermission | Expr::ClassStanding(_) | Expr::Text(_) => Ok(Plan {
+                conditions: vec![expr.clone()],
+                ..Plan::default()
+            }),
+            Expr::And(left, right) => {
+                let left = self.expand(left, expansion)?;
+                Ok(left.merge(self.expand(right, expansion)?))
+            }
+            Expr::Or(left, right) => {
+                match (self.expand(left, expansion), self.expand(right, expansion)) {
+                    (Ok(left), Ok(right)) if right.cost() < left.cost() => Ok(right),
+                    (Ok(left), _) => Ok(left),
+                    (Err(_), Ok(right)) => Ok(right),
+                    (Err(e), Err(_)) => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Chooses the courses needed to take a course, including the course itself
+    fn expand_course(
+        &self,
+        course: &str,
+        concurrent: bool,
+        expansion: &mut Expansion,
+    ) -> Result<Plan, PathError> {
+        if expansion.completed.contains(course) {
+            return Ok(Plan::default());
+        }
+
+        if let Some(start) = expansion.chain.iter().position(|c| c == course) {
+            expansion.cycles += 1;
+            let mut cycle = expansion.chain[start..].to_vec();
+            cycle.push(course.to_owned());
+            return Err(PathError::Cycle(cycle));
+        }
+
+        let mut plan = match expansion.plans.get(course) {
+            Some(plan) => plan.clone(),
+            None => {
+                let cycles = expansion.cycles;
+                expansion.chain.push(course.to_owned());
+                // Courses without recorded prerequisites, like ones no longer offered, need
+                // nothing
+                let result = match self.prerequisites.get(course) {
+                    Some(Some(expr)) => self.expand(expr, expansion),
+                    _ => Ok(Plan::default()),
+                };
+                expansion.chain.pop();
+
+                let mut plan = result?;
+                let direct = std::mem::take(&mut plan.direct);
+                plan.requires.entry(course.to_owned()).or_insert(direct);
+
+                // A plan that skipped a cycle may depend on which courses led to it
+                if expansion.cycles == cycles {
+                    expansion.plans.insert(course.to_owned(), plan.clone());
+                }
+                plan
+            }
+        };
+        plan.direct = vec![(course.to_owned(), concurrent)];
+        Ok(plan)
+    }
+}
+
+/// The state of a search for a path, shared by the expansion of every course on it
+#[derive(Debug, Default)]
+struct Expansion<'a> {
+    completed: HashSet<&'a str>,
+    /// Courses being expanded, from the target down to the current course
+    chain: Vec<String>,
+    /// Plans of the courses already expanded, so a course required along several
+    /// alternatives is only expanded once
+    plans: HashMap<String, Plan>,
+    /// Number of cycles skipped so far
+    cycles: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(courses: &[(&str, &str)]) -> PrerequisiteGraph {
+        PrerequisiteGraph::new(
+            courses
+                .iter()
+                .map(|(number, prerequisites)| {
+                    let expr = (!prerequisites.is_empty()).then(|| prerequisites.parse().unwrap());
+                    (number.to_string(), expr)
+                })
+                .collect(),
+        )
+    }
+
+    fn semesters(path: &CoursePath) -> Vec<Vec<&str>> {
+        path.semesters
+            .iter()
+            .map(|semester| semester.iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_path_chooses_shortest_alternative() {
+        let graph = graph(&[
+            ("10701", "(15122 and 21241) or 10601"),
+            ("10601", "15122 or permission of instructor"),
+            ("15122", "15112 and concurrent 21127"),
+            ("15112", ""),
+            ("21127", ""),
+            ("21241", "21127"),
+        ]);
+
+        let path = graph.path("10701", &[]).unwrap();
+        assert_eq!(
+            semesters(&path),
+            vec![vec!["15112", "21127"], vec!["15122", "21241"]]
+        );
+        assert!(path.conditions.is_empty());
+
+        // Completed courses are skipped, so 10-601 becomes the shorter route
+        let completed = ["15112".to_owned(), "21127".to_owned(), "15122".to_owned()];
+        let path = graph.path("10701", &completed).unwrap();
+        assert_eq!(semesters(&path), vec![vec!["21241"]]);
+
+        let completed = ["10601".to_owned()];
+        assert!(
+            graph
+                .path("10701", &completed)
+                .unwrap()
+                .semesters
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_path_concurrent_and_conditions() {
+        let graph = graph(&[
+            ("21128", "concurrent 21127"),
+            ("21127", ""),
+            ("98000", "permission of instructor"),
+        ]);
+
+        let path = graph.path("21128", &[]).unwrap();
+        assert_eq!(semesters(&path), vec![vec!["21127"]]);
+
+        let path = graph.path("98000", &[]).unwrap();
+        assert!(path.semesters.is_empty());
+        assert_eq!(path.conditions, vec![Expr::InstructorPermission]);
+    }
+
+    #[test]
+    fn test_path_expands_shared_prerequisites_once() {
+        // Each level offers two alternatives that both need the level below, so without
+        // remembering plans the number of expansions doubles with every level
+        let mut courses = vec![("10000".to_owned(), String::new())];
+        for level in 1..40 {
+            let below = format!("{}", 10000 + level - 1);
+            courses.push((
+                format!("{}", 10000 + level),
+                format!("{below} or (concurrent {below})"),
+            ));
+        }
+        let courses: Vec<(&str, &str)> = courses
+            .iter()
+            .map(|(number, prerequisites)| (number.as_str(), prerequisites.as_str()))
+            .collect();
+
+        let path = graph(&courses).path("10039", &[]).unwrap();
+        assert_eq!(path.semesters.len(), 39);
+    }
+
+    #[test]
+    fn test_path_errors() {
+        let graph = graph(&[
+            ("15150", "15151 or 15210"),
+            ("15151", "15150"),
+            ("15210", "15150"),
+            ("15213", "15122 or 15151"),
+            ("15122", "15213"),
+        ]);
+
+        assert_eq!(
+            graph.path("15999", &[]),
+            Err(PathError::UnknownCourse("15999".to_owned()))
+        );
+        assert_eq!(
+            graph.path("15150", &[]),
+            Err(PathError::Cycle(vec![
+                "15150".to_owned(),
+                "15151".to_owned(),
+                "15150".to_owned()
+            ]))
+        );
+
+        // Both alternatives are cyclic until completing 15-150 breaks the cycle through 15-151
+        assert!(matches!(graph.path("15213", &[]), Err(PathError::Cycle(_))));
+        let completed = ["15150".to_owned()];
+        assert_eq!(
+            semesters(&graph.path("15213", &completed).unwrap()),
+            vec![vec!["15151"]]
+        );
+    }
+}
@@ -0,0 +1,517 @@
+use crate::requisite::Expr;
+use serde::{Deserialize, Deserializer, de::Error as DeError};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+/// Program definitions, one file per version of a program
+///
+/// Files are named `{id}.{version}.json`; a new catalog year gets a new file so audits against
+/// older versions keep working.
+const PROGRAM_FILES: &[&str] = &[
+    include_str!("../programs/cs-minor.2024.json"),
+    include_str!("../programs/cs-minor.2025.json"),
+];
+
+static PROGRAMS: LazyLock<Vec<Program>> = LazyLock::new(|| {
+    PROGRAM_FILES
+        .iter()
+        .map(|file| serde_json::from_str(file).expect("Program files are valid"))
+        .collect()
+});
+
+/// A course number pattern like "15-3xx", where each `x` matches any digit
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CoursePattern(String);
+
+impl CoursePattern {
+    /// Whether a course number without a dash (e.g., "15312") matches the pattern
+    pub fn matches(&self, number: &str) -> bool {
+        number.len() == self.0.len()
+            && number
+                .bytes()
+                .zip(self.0.bytes())
+                .all(|(digit, pattern)| pattern == b'x' || digit == pattern)
+    }
+}
+
+impl FromStr for CoursePattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern = s.trim().replace('-', "").to_lowercase();
+        if pattern.len() == 5 && pattern.bytes().all(|b| b.is_ascii_digit() || b == b'x') {
+            Ok(Self(pattern))
+        } else {
+            Err(format!("Invalid course pattern: {s}"))
+        }
+    }
+}
+
+impl Display for CoursePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}-{}", &self.0[..2], &self.0[2..])
+    }
+}
+
+impl<'de> Deserialize<'de> for CoursePattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(DeError::custom)
+    }
+}
+
+/// Deserializes an [`Expr`] from requirement text like "15122 and (21127 or 21128)"
+fn deserialize_expr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Expr, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(|e| DeError::custom(format!("{e}")))
+}
+
+/// How a requirement is satisfied
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Rule {
+    /// Any one course matching the pattern
+    Course { pattern: CoursePattern },
+    /// Courses satisfying a requisite expression
+    Expr {
+        #[serde(deserialize_with = "deserialize_expr")]
+        expr: Expr,
+    },
+    /// Every one of the rules
+    All { of: Vec<Rule> },
+    /// At least `count` of the rules
+    Choose { count: usize, of: Vec<Rule> },
+    /// At least `min` units of courses matching any of the patterns
+    Units {
+        min: f32,
+        courses: Vec<CoursePattern>,
+    },
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let list = |rules: &[Rule]| {
+            rules
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let patterns = |patterns: &[CoursePattern]| {
+            patterns
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" or ")
+        };
+
+        match self {
+            Self::Course { pattern } => write!(f, "{pattern}"),
+            Self::Expr { expr } => write!(f, "{expr}"),
+            Self::All { of } => write!(f, "all of ({})", list(of)),
+            Self::Choose { count, of } => write!(f, "{count} of ({})", list(of)),
+            Self::Units { min, courses } => write!(f, "{min} units of {}", patterns(courses)),
+        }
+    }
+}
+
+/// A named requirement of a program
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Requirement {
+    pub name: String,
+    pub rule: Rule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgramKind {
+    Major,
+    Minor,
+}
+
+/// A major or minor and the requirements to complete it, as of a catalog year
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Program {
+    /// Identifier shared by every version of the program (e.g., "cs-minor")
+    pub id: String,
+    pub name: String,
+    pub kind: ProgramKind,
+    /// Catalog year the requirements apply from (e.g., "2025")
+    pub version: String,
+    pub requirements: Vec<Requirement>,
+}
+
+impl Program {
+    /// Every version of every program
+    pub fn all() -> &'static [Program] {
+        &PROGRAMS
+    }
+
+    /// Finds a program by ID
+    ///
+    /// # Arguments
+    /// * `id` - The program ID
+    /// * `version` - The version to find, or `None` for the latest
+    pub fn find(id: &str, version: Option<&str>) -> Option<&'static Program> {
+        let mut versions = Self::all().iter().filter(|program| program.id == id);
+        match version {
+            Some(version) => versions.find(|program| program.version == version),
+            None => versions.max_by(|a, b| a.version.cmp(&b.version)),
+        }
+    }
+
+    /// Audits completed courses against the program
+    ///
+    /// Requirements are checked in order, and a course only counts towards one of them.
+    ///
+    /// # Arguments
+    /// * `completed` - Numbers of completed courses without a dash
+    /// * `units` - Units of each completed course; missing courses count as 0 units
+    ///
+    /// # Returns
+    /// The [`AuditReport`] for the program
+    pub fn audit(&self, completed: &[String], units: &HashMap<String, f32>) -> AuditReport {
+        let mut completed = completed.to_vec();
+        completed.sort();
+        completed.dedup();
+
+        let mut auditor = Auditor {
+            completed: &completed,
+            units,
+            used: HashSet::new(),
+        };
+
+        let requirements: Vec<RequirementResult> = self
+            .requirements
+            .iter()
+            .map(|requirement| {
+                let outcome = auditor.check(&requirement.rule);
+                RequirementResult {
+                    name: requirement.name.clone(),
+                    satisfied: outcome.satisfied,
+                    courses: outcome.courses,
+                    remaining: outcome.remaining,
+                }
+            })
+            .collect();
+
+        AuditReport {
+            program: self.id.clone(),
+            version: self.version.clone(),
+            satisfied: requirements.iter().all(|requirement| requirement.satisfied),
+            requirements,
+            unused: completed
+                .iter()
+                .filter(|course| !auditor.used.contains(course.as_str()))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// The result of auditing a requirement
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequirementResult {
+    pub name: String,
+    pub satisfied: bool,
+    /// Completed courses counted towards the requirement
+    pub courses: Vec<String>,
+    /// What is still needed, empty if the requirement is satisfied
+    pub remaining: Vec<String>,
+}
+
+/// The result of auditing completed courses against a program
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditReport {
+    pub program: String,
+    pub version: String,
+    /// Whether every requirement is satisfied
+    pub satisfied: bool,
+    pub requirements: Vec<RequirementResult>,
+    /// Completed courses that didn't count towards any requirement
+    pub unused: Vec<String>,
+}
+
+/// The result of checking a rule
+#[derive(Debug, Default)]
+struct Outcome {
+    satisfied: bool,
+    courses: Vec<String>,
+    remaining: Vec<String>,
+}
+
+/// Checks rules against completed courses, keeping track of the courses already counted
+struct Auditor<'a> {
+    completed: &'a [String],
+    units: &'a HashMap<String, f32>,
+    used: HashSet<&'a str>,
+}
+
+impl<'a> Auditor<'a> {
+    /// Completed courses that haven't counted towards a requirement yet
+    fn available(&self) -> impl Iterator<Item = &'a String> + '_ {
+        self.completed
+            .iter()
+            .filter(|course| !self.used.contains(course.as_str()))
+    }
+
+    fn check(&mut self, rule: &Rule) -> Outcome {
+        match rule {
+            Rule::Course { pattern } => {
+                let course = self.available().find(|c| pattern.matches(c));
+                match course {
+                    Some(course) => {
+                        self.used.insert(course);
+                        Outcome {
+                            satisfied: true,
+                            courses: vec![course.clone()],
+                            remaining: Vec::new(),
+                        }
+                    }
+                    None => Outcome {
+                        remaining: vec![pattern.to_string()],
+                        ..Outcome::default()
+                    },
+                }
+            }
+            Rule::Expr { expr } => {
+                let available: Vec<String> = self.available().cloned().collect();
+                match expr.simplify(&available) {
+                    // Only the courses of one way to satisfy the expression count towards it,
+                    // so the alternatives left over stay available to other requirements
+                    None => {
+                        let courses = satisfying_courses(expr, &available);
+                        for course in &courses {
+                            if let Some(course) = self.completed.iter().find(|c| *c == course) {
+                                self.used.insert(course);
+                            }
+                        }
+                        Outcome {
+                            satisfied: true,
+                            courses,
+                            remaining: Vec::new(),
+                        }
+                    }
+                    Some(remaining) => Outcome {
+                        remaining: vec![remaining.to_string()],
+                        ..Outcome::default()
+                    },
+                }
+            }
+            Rule::All { of } => {
+                let outcomes: Vec<Outcome> = of.iter().map(|rule| self.check(rule)).collect();
+                Outcome {
+                    satisfied: outcomes.iter().all(|outcome| outcome.satisfied),
+                    courses: outcomes.iter().flat_map(|o| o.courses.clone()).collect(),
+                    remaining: outcomes.into_iter().flat_map(|o| o.remaining).collect(),
+                }
+            }
+            Rule::Choose { count, of } => {
+                let mut courses = Vec::new();
+                let mut missing = Vec::new();
+                let mut satisfied = 0;
+
+                for rule in of {
+                    if satisfied == *count {
+                        break;
+                    }
+
+                    // Only count the courses of rules that are fully satisfied, so a partial
+                    // option doesn't use up courses another option needs
+                    let used = self.used.clone();
+                    let outcome = self.check(rule);
+                    if outcome.satisfied {
+                        satisfied += 1;
+                        courses.extend(outcome.courses);
+                    } else {
+                        self.used = used;
+                        missing.push(rule.to_string());
+                    }
+                }
+
+                let remaining = if satisfied < *count {
+                    vec![format!(
+                        "{} more of ({})",
+                        count - satisfied,
+                        missing.join(", ")
+                    )]
+                } else {
+                    Vec::new()
+                };
+
+                Outcome {
+                    satisfied: satisfied >= *count,
+                    courses,
+                    remaining,
+                }
+            }
+            Rule::Units {
+                min,
+                courses: patterns,
+            } => {
+                let matching: Vec<&'a String> = self
+                    .available()
+                    .filter(|course| patterns.iter().any(|pattern| pattern.matches(course)))
+                    .collect();
+
+                let mut total = 0.0;
+                let mut courses = Vec::new();
+                for course in matching {
+                    if total >= *min {
+                        break;
+                    }
+                    total += self.units.get(course).copied().unwrap_or_default();
+                    self.used.insert(course);
+                    courses.push(course.clone());
+                }
+
+                let remaining = if total < *min {
+                    let patterns: Vec<String> = patterns.iter().map(ToString::to_string).collect();
+                    vec![format!(
+                        "{} more units of {}",
+                        min - total,
+                        patterns.join(" or ")
+                    )]
+                } else {
+                    Vec::new()
+                };
+
+                Outcome {
+                    satisfied: total >= *min,
+                    courses,
+                    remaining,
+                }
+            }
+        }
+    }
+}
+
+/// The fewest completed courses that satisfy an expression, in the order they're listed in
+///
+/// # Returns
+/// The courses of the smallest clause of the expression's disjunctive normal form whose
+/// requirements are all completed courses, or no courses if there isn't one
+fn satisfying_courses(expr: &Expr, completed: &[String]) -> Vec<String> {
+    let completed_course = |requirement: &Expr| match requirement {
+        Expr::Course(course) | Expr::MinGrade { course, .. } | Expr::Concurrent(course) => {
+            completed.contains(course).then(|| course.clone())
+        }
+        Expr::InstructorPermission | Expr::ClassStanding(_) | Expr::Text(_) => None,
+        Expr::And(..) | Expr::Or(..) => None,
+    };
+
+    expr.to_dnf()
+        .iter()
+        .filter_map(|clause| {
+            clause
+                .iter()
+                .map(completed_course)
+                .collect::<Option<Vec<_>>>()
+        })
+        .min_by_key(Vec::len)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn courses(numbers: &[&str]) -> Vec<String> {
+        numbers.iter().map(|number| number.to_string()).collect()
+    }
+
+    #[test]
+    fn test_course_pattern() {
+        let pattern: CoursePattern = "15-3xx".parse().unwrap();
+        assert!(pattern.matches("15312"));
+        assert!(!pattern.matches("15213"));
+        assert!(!pattern.matches("153"));
+        assert_eq!(pattern.to_string(), "15-3xx");
+
+        assert!("15-3x".parse::<CoursePattern>().is_err());
+        assert!("ab-cde".parse::<CoursePattern>().is_err());
+    }
+
+    #[test]
+    fn test_program_files() {
+        assert!(!Program::all().is_empty());
+
+        let latest = Program::find("cs-minor", None).unwrap();
+        assert_eq!(latest.version, "2025");
+        assert_eq!(
+            Program::find("cs-minor", Some("2024")).unwrap().version,
+            "2024"
+        );
+        assert!(Program::find("cs-minor", Some("1999")).is_none());
+        assert!(Program::find("underwater-basket-weaving", None).is_none());
+    }
+
+    #[test]
+    fn test_audit() {
+        let program: Program = serde_json::from_str(
+            r#"{
+                "id": "test",
+                "name": "Test",
+                "kind": "minor",
+                "version": "2025",
+                "requirements": [
+                    { "name": "Core", "rule": { "type": "expr", "expr": "15122 and (21127 or 21128)" } },
+                    { "name": "Systems", "rule": { "type": "choose", "count": 2, "of": [
+                        { "type": "course", "pattern": "15-213" },
+                        { "type": "course", "pattern": "15-410" },
+                        { "type": "course", "pattern": "15-440" }
+                    ] } },
+                    { "name": "Electives", "rule": { "type": "units", "min": 18, "courses": ["15-3xx", "15-4xx"] } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let units = HashMap::from([
+            ("15213".to_owned(), 12.0),
+            ("15410".to_owned(), 15.0),
+            ("15440".to_owned(), 12.0),
+            ("15312".to_owned(), 12.0),
+        ]);
+
+        let report = program.audit(
+            &courses(&[
+                "15122", "21127", "15213", "15410", "15440", "15312", "79104",
+            ]),
+            &units,
+        );
+        assert!(report.satisfied);
+        assert_eq!(report.requirements[0].courses, courses(&["15122", "21127"]));
+        assert_eq!(report.requirements[1].courses, courses(&["15213", "15410"]));
+        // 15-440 wasn't needed for systems, so it counts as an elective
+        assert_eq!(report.requirements[2].courses, courses(&["15312", "15440"]));
+        assert_eq!(report.unused, courses(&["79104"]));
+
+        // Only one of the alternatives counts towards the core, and a partly completed
+        // requirement doesn't use up its courses
+        let report = program.audit(&courses(&["15122", "21127", "21128"]), &units);
+        assert_eq!(report.requirements[0].courses, courses(&["15122", "21127"]));
+        assert_eq!(report.unused, courses(&["21128"]));
+
+        let report = program.audit(&courses(&["15122"]), &units);
+        assert!(report.requirements[0].courses.is_empty());
+        assert_eq!(report.unused, courses(&["15122"]));
+
+        let report = program.audit(&courses(&["15122", "15410", "15312"]), &units);
+        assert!(!report.satisfied);
+        assert_eq!(report.requirements[0].remaining, vec!["21-127 or 21-128"]);
+        assert_eq!(
+            report.requirements[1].remaining,
+            vec!["1 more of (15-213, 15-440)"]
+        );
+        assert_eq!(
+            report.requirements[2].remaining,
+            vec!["6 more units of 15-3xx or 15-4xx"]
+        );
+    }
+}
@@ -1,22 +1,85 @@
+use crate::course_data::CourseNumber;
 use serde::{Deserialize, Serialize};
-use std::{ops::Deref, str::FromStr};
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::Deref,
+    str::FromStr,
+};
 
 #[cfg(feature = "database")]
 use sea_orm::Value;
 
+/// A class standing a course can require
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Standing {
+    FirstYear,
+    Sophomore,
+    Junior,
+    Senior,
+    Graduate,
+}
+
+impl FromStr for Standing {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "freshman" | "first-year" | "first year" => Ok(Self::FirstYear),
+            "sophomore" => Ok(Self::Sophomore),
+            "junior" => Ok(Self::Junior),
+            "senior" => Ok(Self::Senior),
+            "graduate" => Ok(Self::Graduate),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Standing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let standing = match self {
+            Self::FirstYear => "first-year",
+            Self::Sophomore => "sophomore",
+            Self::Junior => "junior",
+            Self::Senior => "senior",
+            Self::Graduate => "graduate",
+        };
+        write!(f, "{standing}")
+    }
+}
+
 /// Represents a node in the expression tree
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expr {
+    /// A course that must be completed, by number without a dash (e.g., "15122")
     Course(String),
+    /// A course that must be completed with at least the given letter grade
+    MinGrade {
+        course: String,
+        grade: String,
+    },
+    /// A course that may be taken in the same semester instead of before
+    Concurrent(String),
+    /// Permission of the instructor
+    InstructorPermission,
+    /// A minimum class standing
+    ClassStanding(Standing),
+    /// Text that doesn't match the grammar, kept as written so it can still be shown
+    Text(String),
     And(Box<Expr>, Box<Expr>),
     Or(Box<Expr>, Box<Expr>),
 }
 
 impl Expr {
     /// Evaluate if this expression is satisfied by the given completed courses
+    ///
+    /// Requirements that aren't courses, like instructor permission, can't be satisfied by
+    /// completing courses, so they never are.
     pub fn evaluate(&self, completed_courses: &[String]) -> bool {
         match self {
-            Expr::Course(course) => completed_courses.contains(course),
+            Expr::Course(course) | Expr::MinGrade { course, .. } | Expr::Concurrent(course) => {
+                completed_courses.contains(course)
+            }
+            Expr::InstructorPermission | Expr::ClassStanding(_) | Expr::Text(_) => false,
             Expr::And(left, right) => {
                 left.evaluate(completed_courses) && right.evaluate(completed_courses)
             }
@@ -33,14 +96,19 @@ impl Expr {
         match self {
             // For a course node, if it's completed return None (satisfied)
             // otherwise return the course requirement
-            Expr::Course(course) => {
+            Expr::Course(course) | Expr::MinGrade { course, .. } | Expr::Concurrent(course) => {
                 if completed_courses.contains(course) {
                     None // Course is already completed
                 } else {
-                    Some(Expr::Course(course.clone())) // Course still needed
+                    Some(self.clone()) // Course still needed
                 }
             }
 
+            // Requirements other than courses always remain
+            Expr::InstructorPermission | Expr::ClassStanding(_) | Expr::Text(_) => {
+                Some(self.clone())
+            }
+
             // For AND nodes, both sides must be satisfied
             Expr::And(left, right) => {
                 match (
@@ -68,126 +136,515 @@ impl Expr {
     }
 }
 
-/// Custom error type for parsing requisites
-#[derive(Debug)]
-pub struct ParseError(pub String);
+impl Expr {
+    /// Binding strength of the expression's top-level operator, used to decide where
+    /// parentheses are needed
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Or(_, _) => 0,
+            Expr::And(_, _) => 1,
+            _ => 2,
+        }
+    }
 
-impl FromStr for Expr {
-    type Err = ParseError;
+    /// Gets the operands of a chain of the same operator, so `a and (b and c)` gives
+    /// `[a, b, c]`
+    ///
+    /// # Returns
+    /// The operands in order, or just this expression if it isn't an And or Or
+    pub fn operands(&self) -> Vec<&Expr> {
+        match self {
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                let same_op =
+                    |child: &Expr| std::mem::discriminant(child) == std::mem::discriminant(self);
+
+                [left, right]
+                    .into_iter()
+                    .flat_map(|child| {
+                        if same_op(child) {
+                            child.operands()
+                        } else {
+                            vec![&**child]
+                        }
+                    })
+                    .collect()
+            }
+            _ => vec![self],
+        }
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let input = s.trim();
-
-        // Handle parenthesized expressions
-        if input.starts_with('(') && input.ends_with(')') {
-            // Find matching parentheses by counting
-            let mut count = 0;
-            let mut found_closing = false;
-
-            for (i, ch) in input.chars().enumerate() {
-                if ch == '(' {
-                    count += 1;
-                } else if ch == ')' {
-                    count -= 1;
-                    if count == 0 && i < input.len() - 1 {
-                        // We found a closing parenthesis before the end, so this isn't a simple parenthesized expression
-                        found_closing = true;
-                        break;
-                    }
+    /// Expands the expression into clauses of leaves, splitting on `Or` for DNF and on `And`
+    /// for CNF and distributing the other operator
+    fn clauses(&self, split_on_or: bool) -> Vec<Vec<Expr>> {
+        match self {
+            Expr::Or(left, right) | Expr::And(left, right) => {
+                let (left, right) = (left.clauses(split_on_or), right.clauses(split_on_or));
+
+                if matches!(self, Expr::Or(_, _)) == split_on_or {
+                    left.into_iter().chain(right).collect()
+                } else {
+                    left.iter()
+                        .flat_map(|l| {
+                            right
+                                .iter()
+                                .map(move |r| l.iter().chain(r).cloned().collect())
+                        })
+                        .collect()
                 }
             }
+            leaf => vec![vec![leaf.clone()]],
+        }
+    }
 
-            if !found_closing {
-                // This is a simple parenthesized expression, extract inner content
-                let inner = &input[1..input.len() - 1].trim();
-                return inner.parse();
+    /// Removes duplicate leaves within clauses and clauses implied by a smaller clause
+    fn reduce(clauses: Vec<Vec<Expr>>) -> Vec<Vec<Expr>> {
+        let clauses: Vec<Vec<Expr>> = clauses
+            .into_iter()
+            .map(|clause| {
+                let mut unique = Vec::new();
+                for leaf in clause {
+                    if !unique.contains(&leaf) {
+                        unique.push(leaf);
+                    }
+                }
+                unique
+            })
+            .collect();
+
+        let subsumes = |a: &Vec<Expr>, b: &Vec<Expr>| a.iter().all(|leaf| b.contains(leaf));
+
+        let mut reduced: Vec<Vec<Expr>> = Vec::new();
+        for (i, clause) in clauses.iter().enumerate() {
+            // Keep the first of equal clauses and drop any clause with a strict subset
+            let redundant = clauses
+                .iter()
+                .enumerate()
+                .any(|(j, other)| subsumes(other, clause) && (other.len() < clause.len() || j < i));
+            if !redundant {
+                reduced.push(clause.clone());
             }
         }
+        reduced
+    }
 
-        // Try to parse as OR expression first (lower precedence)
-        if let Some(or_parts) = split_top_level(input, "or")
-            && or_parts.len() >= 2
-        {
-            let mut result = or_parts[0].parse()?;
-            for part in &or_parts[1..] {
-                result = Expr::Or(Box::new(result), Box::new(part.parse()?));
-            }
-            return Ok(result);
+    /// Converts the expression to disjunctive normal form: the ways to satisfy it
+    ///
+    /// # Returns
+    /// Clauses of requirements that must all be met, of which any one clause suffices.
+    /// Duplicate requirements and clauses that contain a smaller clause are removed.
+    pub fn to_dnf(&self) -> Vec<Vec<Expr>> {
+        Self::reduce(self.clauses(true))
+    }
+
+    /// Converts the expression to disjunctive normal form, like [`Expr::to_dnf`], unless that
+    /// takes more than `max_clauses` clauses before duplicates are removed
+    ///
+    /// The number of clauses grows exponentially with the number of alternatives that must
+    /// all be met, so it's counted before any clause is built.
+    pub fn to_dnf_within(&self, max_clauses: usize) -> Option<Vec<Vec<Expr>>> {
+        (self.clause_count() <= max_clauses).then(|| self.to_dnf())
+    }
+
+    /// Number of clauses of the disjunctive normal form before duplicates are removed
+    fn clause_count(&self) -> usize {
+        match self {
+            Expr::Or(left, right) => left.clause_count().saturating_add(right.clause_count()),
+            Expr::And(left, right) => left.clause_count().saturating_mul(right.clause_count()),
+            _ => 1,
         }
+    }
 
-        // Try to parse as AND expression (higher precedence)
-        if let Some(and_parts) = split_top_level(input, "and")
-            && and_parts.len() >= 2
-        {
-            let mut result = and_parts[0].parse()?;
-            for part in &and_parts[1..] {
-                result = Expr::And(Box::new(result), Box::new(part.parse()?));
+    /// Converts the expression to conjunctive normal form
+    ///
+    /// # Returns
+    /// Clauses of alternatives of which any one suffices, all of which must be met.
+    /// Duplicate requirements and clauses that contain a smaller clause are removed.
+    pub fn to_cnf(&self) -> Vec<Vec<Expr>> {
+        Self::reduce(self.clauses(false))
+    }
+}
+
+impl Display for Expr {
+    /// Renders the expression as it would be written in the course catalog, with
+    /// parentheses only where `and` binds tighter than the intended grouping
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let dashed = |number: &str| {
+            CourseNumber::from_str(number)
+                .map(|number| number.as_full_string())
+                .unwrap_or_else(|_| number.to_owned())
+        };
+
+        match self {
+            Expr::Course(course) => write!(f, "{}", dashed(course)),
+            Expr::MinGrade { course, grade } => {
+                write!(f, "{} (min. grade {grade})", dashed(course))
             }
-            return Ok(result);
+            Expr::Concurrent(course) => write!(f, "{} (concurrently)", dashed(course)),
+            Expr::InstructorPermission => write!(f, "permission of instructor"),
+            Expr::ClassStanding(standing) => write!(f, "{standing} standing"),
+            Expr::Text(text) => write!(f, "{text}"),
+            Expr::And(_, _) | Expr::Or(_, _) => {
+                let separator = if matches!(self, Expr::And(_, _)) {
+                    " and "
+                } else {
+                    " or "
+                };
+
+                for (i, operand) in self.operands().into_iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{separator}")?;
+                    }
+                    if operand.precedence() < self.precedence() {
+                        write!(f, "({operand})")?;
+                    } else {
+                        write!(f, "{operand}")?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Custom error type for parsing requisites
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Byte offset in the input where parsing failed
+    pub position: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A token of a requisite string
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    /// A course number without a dash
+    Course(String),
+    /// Any other word
+    Word(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    /// Byte offsets of the token in the input
+    start: usize,
+    end: usize,
+}
+
+/// Parses a course number like "15122" or "15-122", ignoring trailing punctuation
+fn course_number(word: &str) -> Option<String> {
+    let word = word.trim_end_matches(['.', ',', ';']);
+    let digits: String = word.chars().filter(|&c| c != '-').collect();
+
+    let dash_ok = match word.find('-') {
+        None => true,
+        Some(i) => i == 2 && word.matches('-').count() == 1,
+    };
+
+    (dash_ok && digits.len() == 5 && digits.chars().all(|c| c.is_ascii_digit())).then_some(digits)
+}
+
+/// Splits a requisite string into tokens
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
         }
 
-        // Check if it's a parenthesized expression
-        if input.starts_with('(') && input.ends_with(')') {
-            let inner = &input[1..input.len() - 1].trim();
-            return inner.parse();
+        if c == '(' || c == ')' {
+            chars.next();
+            let kind = if c == '(' {
+                TokenKind::LParen
+            } else {
+                TokenKind::RParen
+            };
+            tokens.push(Token {
+                kind,
+                start,
+                end: start + 1,
+            });
+            continue;
         }
 
-        // If no operators found and input is just digits, it's a course number
-        if input.chars().all(|c| c.is_ascii_digit()) {
-            return Ok(Expr::Course(input.to_string()));
+        let mut end = start;
+        while let Some(&(i, c)) = chars.peek() {
+            if c.is_whitespace() || c == '(' || c == ')' {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
         }
 
-        Err(ParseError(format!("Failed to parse: {input}")))
+        let word = &input[start..end];
+        let kind = match word.to_lowercase().as_str() {
+            "and" => TokenKind::And,
+            "or" => TokenKind::Or,
+            _ => match course_number(word) {
+                Some(number) => TokenKind::Course(number),
+                None => TokenKind::Word(word.to_owned()),
+            },
+        };
+        tokens.push(Token { kind, start, end });
     }
+
+    tokens
 }
 
-impl From<String> for Expr {
-    fn from(s: String) -> Self {
-        s.parse().unwrap()
-    }
-}
-
-/// Split a string on a top level operator, respecting nested parentheses
-fn split_top_level(input: &str, op: &str) -> Option<Vec<String>> {
-    let mut result = Vec::new();
-    let mut paren_count = 0;
-    let mut start = 0;
-    let mut i = 0;
-    let chars: Vec<char> = input.chars().collect();
-
-    while i < chars.len() {
-        match chars[i] {
-            '(' => paren_count += 1,
-            ')' => paren_count -= 1,
-            ' ' if paren_count == 0 && i + op.len() + 2 <= input.len() => {
-                // Need to match pattern " op " (with spaces on both sides)
-                let slice = &input[i..i + op.len() + 2];
-                if slice.starts_with(' ') && slice[1..].starts_with(op) && slice.ends_with(' ') {
-                    let part = input[start..i].trim();
-                    if !part.is_empty() {
-                        result.push(part.to_string());
-                    }
+/// Normalizes a phrase for matching, lowercasing it and dropping punctuation
+fn normalize_phrase(words: &[&str]) -> String {
+    words
+        .iter()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric() && c != '+' && c != '-')
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses a minimum grade phrase like "min. grade C" into its grade
+fn min_grade(phrase: &str) -> Option<String> {
+    let grade = [
+        "minimum grade of ",
+        "min grade of ",
+        "minimum grade ",
+        "min grade ",
+    ]
+    .iter()
+    .find_map(|prefix| phrase.strip_prefix(prefix))?
+    .to_uppercase();
+
+    let mut chars = grade.chars();
+    let letter_ok = chars.next().is_some_and(|c| ('A'..='D').contains(&c));
+    let rest = chars.as_str();
+    (letter_ok && (rest.is_empty() || rest == "+" || rest == "-")).then_some(grade)
+}
+
+/// Whether a phrase marks a course as takeable in the same semester
+fn is_concurrent(phrase: &str) -> bool {
+    matches!(
+        phrase,
+        "concurrent"
+            | "concurrently"
+            | "concurrent with"
+            | "concurrently with"
+            | "may be taken concurrently"
+            | "corequisite"
+    )
+}
+
+/// Classifies a phrase that isn't a course
+fn phrase_expr(phrase: &str, raw: &str) -> Expr {
+    match phrase {
+        "permission of instructor"
+        | "permission of the instructor"
+        | "instructor permission"
+        | "instructor's permission"
+        | "consent of instructor"
+        | "consent of the instructor" => return Expr::InstructorPermission,
+        _ => {}
+    }
+
+    let standing = phrase.strip_suffix(" standing").unwrap_or(phrase);
+    let standing = standing.strip_suffix(" class").unwrap_or(standing);
+    match Standing::from_str(standing) {
+        Ok(standing) => Expr::ClassStanding(standing),
+        Err(()) => Expr::Text(raw.to_owned()),
+    }
+}
+
+/// Recursive descent parser over the tokens of a requisite string
+///
+/// ```text
+/// or_expr  := and_expr ("or" and_expr)*
+/// and_expr := unary ("and" unary)*
+/// unary    := "(" or_expr ")" | requirement
+/// requirement := concurrent_phrase course
+///              | course ["(" suffix ")" | suffix]
+///              | phrase
+/// ```
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            tokens: tokenize(input),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|token| &token.kind)
+    }
+
+    /// Byte offset of the current token, or the end of the input
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.input.len(), |token| token.start)
+    }
 
-                    i += op.len() + 1; // Skip the operator and one space
-                    start = i + 1;
+    fn parse(mut self) -> Result<Expr, ParseError> {
+        let expr = self.parse_or()?;
+
+        match self.peek() {
+            None => Ok(expr),
+            Some(TokenKind::RParen) => Err(ParseError::new(self.offset(), "Unmatched ')'")),
+            Some(_) => Err(ParseError::new(self.offset(), "Expected 'and' or 'or'")),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&TokenKind::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_unary()?;
+        while self.peek() == Some(&TokenKind::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        match self.peek() {
+            Some(TokenKind::LParen) => {
+                let open = self.offset();
+                self.pos += 1;
+                let expr = self.parse_or()?;
+
+                if self.peek() != Some(&TokenKind::RParen) {
+                    return Err(ParseError::new(open, "Unclosed '('"));
                 }
+                self.pos += 1;
+                Ok(expr)
+            }
+            Some(TokenKind::Course(_) | TokenKind::Word(_)) => self.parse_requirement(),
+            Some(TokenKind::RParen) => Err(ParseError::new(self.offset(), "Unexpected ')'")),
+            Some(TokenKind::And | TokenKind::Or) | None => {
+                Err(ParseError::new(self.offset(), "Expected a requirement"))
             }
-            _ => {}
+        }
+    }
+
+    /// Collects the words starting at the current token, without consuming them
+    fn words_ahead(&self, from: usize) -> Vec<&'a str> {
+        self.tokens[from..]
+            .iter()
+            .map_while(|token| match token.kind {
+                TokenKind::Word(_) => Some(&self.input[token.start..token.end]),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn parse_requirement(&mut self) -> Result<Expr, ParseError> {
+        if let Some(TokenKind::Course(course)) = self.peek() {
+            let course = course.clone();
+            self.pos += 1;
+            return Ok(self.parse_course_suffix(course));
+        }
+
+        let words = self.words_ahead(self.pos);
+        let start = self.tokens[self.pos].start;
+        let end = self.tokens[self.pos + words.len() - 1].end;
+        self.pos += words.len();
+
+        let phrase = normalize_phrase(&words);
+        if is_concurrent(&phrase)
+            && let Some(TokenKind::Course(course)) = self.peek()
+        {
+            let course = course.clone();
+            self.pos += 1;
+            return Ok(Expr::Concurrent(course));
         }
 
-        i += 1;
+        Ok(phrase_expr(&phrase, &self.input[start..end]))
     }
 
-    // Add the last part
-    let last_part = input[start..].trim();
-    if !last_part.is_empty() {
-        result.push(last_part.to_string());
+    /// Applies a minimum grade or concurrency note following a course, either in parentheses
+    /// or not
+    fn parse_course_suffix(&mut self, course: String) -> Expr {
+        let parenthesized = self.peek() == Some(&TokenKind::LParen);
+        let from = self.pos + usize::from(parenthesized);
+        let words = self.words_ahead(from);
+        let after = from + words.len();
+
+        if words.is_empty()
+            || (parenthesized
+                && self.tokens.get(after).map(|token| &token.kind) != Some(&TokenKind::RParen))
+        {
+            return Expr::Course(course);
+        }
+
+        let phrase = normalize_phrase(&words);
+        let expr = if let Some(grade) = min_grade(&phrase) {
+            Expr::MinGrade { course, grade }
+        } else if is_concurrent(&phrase) {
+            Expr::Concurrent(course)
+        } else {
+            return Expr::Course(course);
+        };
+
+        self.pos = after + usize::from(parenthesized);
+        expr
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    /// Parses a requisite string like "15-122 (min. grade C) and (21-127 or concurrent
+    /// 21-128)"
+    ///
+    /// Words that don't form a known requirement become [`Expr::Text`] leaves, so only
+    /// structural problems like unbalanced parentheses or a missing operand are errors.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::new(s).parse()
     }
+}
 
-    if result.len() >= 2 {
-        Some(result)
-    } else {
-        None
+impl From<String> for Expr {
+    /// Parses a requisite string, keeping it as [`Expr::Text`] if it can't be parsed
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(Expr::Text(s))
     }
 }
 
@@ -246,7 +703,7 @@ impl sea_orm::sea_query::Nullable for Expr {
 }
 
 /// Represents a courses' prerequisites
-#[derive(Debug, Clone, PartialEq, Serialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Prerequisites(Option<Expr>);
 
 impl Prerequisites {
@@ -255,6 +712,29 @@ impl Prerequisites {
     }
 }
 
+impl From<Option<Expr>> for Prerequisites {
+    fn from(expr: Option<Expr>) -> Self {
+        Prerequisites(expr)
+    }
+}
+
+impl Prerequisites {
+    /// Parses a requirement string, keeping it as [`Expr::Text`] if it can't be parsed so the
+    /// prerequisites are never lost
+    ///
+    /// # Returns
+    /// The [`Prerequisites`] and the [`ParseError`], if there was one
+    pub fn parse_or_raw(s: &str) -> (Self, Option<ParseError>) {
+        match s.parse() {
+            Ok(prerequisites) => (prerequisites, None),
+            Err(e) => (
+                Prerequisites(Some(Expr::Text(s.trim().to_owned()))),
+                Some(e),
+            ),
+        }
+    }
+}
+
 impl FromStr for Prerequisites {
     type Err = ParseError;
 
@@ -271,7 +751,7 @@ impl FromStr for Prerequisites {
 }
 
 /// Represents a courses' corequisites or cross-listed courses
-#[derive(Debug, Clone, PartialEq, Serialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Requisites(Vec<String>);
 
 impl Deref for Requisites {
@@ -433,4 +913,232 @@ mod tests {
         let simplified = expr.simplify(&["15213".to_string()]);
         assert_eq!(simplified, None);
     }
+
+    fn course(number: &str) -> Box<Expr> {
+        Box::new(Expr::Course(number.to_owned()))
+    }
+
+    #[test]
+    fn test_parse_dashed_numbers_and_precedence() {
+        assert_eq!(
+            "15-122 or 15-150 and 21-127".parse::<Expr>().unwrap(),
+            Expr::Or(
+                course("15122"),
+                Box::new(Expr::And(course("15150"), course("21127")))
+            )
+        );
+        assert_eq!(
+            "(15122 or 15150) AND 21127".parse::<Expr>().unwrap(),
+            Expr::And(
+                Box::new(Expr::Or(course("15122"), course("15150"))),
+                course("21127")
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_min_grade_and_concurrent() {
+        assert_eq!(
+            "15-122 (min. grade C)".parse::<Expr>().unwrap(),
+            Expr::MinGrade {
+                course: "15122".to_owned(),
+                grade: "C".to_owned()
+            }
+        );
+        assert_eq!(
+            "15-122 Minimum grade of B-".parse::<Expr>().unwrap(),
+            Expr::MinGrade {
+                course: "15122".to_owned(),
+                grade: "B-".to_owned()
+            }
+        );
+        assert_eq!(
+            "21-127 or concurrent 21-128".parse::<Expr>().unwrap(),
+            Expr::Or(
+                course("21127"),
+                Box::new(Expr::Concurrent("21128".to_owned()))
+            )
+        );
+        assert_eq!(
+            "21-128 (concurrently)".parse::<Expr>().unwrap(),
+            Expr::Concurrent("21128".to_owned())
+        );
+
+        // A parenthesized group after a course is not a suffix
+        assert_eq!("15122 (or 15150)".parse::<Expr>().unwrap_err().position, 6);
+    }
+
+    #[test]
+    fn test_parse_permission_standing_and_text() {
+        assert_eq!(
+            "Permission of instructor".parse::<Expr>().unwrap(),
+            Expr::InstructorPermission
+        );
+        assert_eq!(
+            "15-213 or Senior standing".parse::<Expr>().unwrap(),
+            Expr::Or(
+                course("15213"),
+                Box::new(Expr::ClassStanding(Standing::Senior))
+            )
+        );
+        assert_eq!(
+            "Junior or Senior standing".parse::<Expr>().unwrap(),
+            Expr::Or(
+                Box::new(Expr::ClassStanding(Standing::Junior)),
+                Box::new(Expr::ClassStanding(Standing::Senior))
+            )
+        );
+        assert_eq!(
+            "33-141 or strong physics background"
+                .parse::<Expr>()
+                .unwrap(),
+            Expr::Or(
+                course("33141"),
+                Box::new(Expr::Text("strong physics background".to_owned()))
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |s: &str| s.parse::<Expr>().unwrap_err();
+
+        assert_eq!(
+            error("15122 or"),
+            ParseError::new(8, "Expected a requirement")
+        );
+        assert_eq!(
+            error("(15122 and 21127"),
+            ParseError::new(0, "Unclosed '('")
+        );
+        assert_eq!(
+            error("15122) or 15150"),
+            ParseError::new(5, "Unmatched ')'")
+        );
+        assert_eq!(
+            error("15122 15150"),
+            ParseError::new(6, "Expected 'and' or 'or'")
+        );
+        assert_eq!(error(""), ParseError::new(0, "Expected a requirement"));
+        assert_eq!(
+            error("15122 or").to_string(),
+            "Expected a requirement at position 8"
+        );
+    }
+
+    #[test]
+    fn test_non_course_requirements() {
+        let expr = "21-127 (min. grade C) or permission of instructor"
+            .parse::<Expr>()
+            .unwrap();
+
+        assert!(expr.evaluate(&["21127".to_owned()]));
+        assert!(!expr.evaluate(&[]));
+        assert_eq!(expr.simplify(&["21127".to_owned()]), None);
+        assert_eq!(expr.simplify(&[]), Some(expr.clone()));
+    }
+
+    #[test]
+    fn test_prerequisites_parse_or_raw() {
+        assert_eq!(
+            Prerequisites::parse_or_raw("None"),
+            (Prerequisites(None), None)
+        );
+
+        let (prerequisites, error) = Prerequisites::parse_or_raw("15122 or (15150");
+        assert_eq!(
+            prerequisites.into_inner(),
+            Some(Expr::Text("15122 or (15150".to_owned()))
+        );
+        assert_eq!(error.unwrap().position, 9);
+
+        // Existing JSON keeps deserializing
+        assert_eq!(
+            serde_json::from_str::<Expr>(r#"{"Or":[{"Course":"15112"},{"Course":"15110"}]}"#)
+                .unwrap(),
+            Expr::Or(course("15112"), course("15110"))
+        );
+    }
+
+    #[test]
+    fn test_display_minimal_parens() {
+        let display = |s: &str| s.parse::<Expr>().unwrap().to_string();
+
+        assert_eq!(display("15122"), "15-122");
+        assert_eq!(display("((15122 and 21127))"), "15-122 and 21-127");
+        assert_eq!(
+            display("15122 or (15150 and 21127)"),
+            "15-122 or 15-150 and 21-127"
+        );
+        assert_eq!(
+            display("(15122 or 15150) and (21127 or 21128)"),
+            "(15-122 or 15-150) and (21-127 or 21-128)"
+        );
+        assert_eq!(
+            display("15122 (min. grade C) and (concurrent 21128 or permission of instructor)"),
+            "15-122 (min. grade C) and (21-128 (concurrently) or permission of instructor)"
+        );
+        assert_eq!(
+            display("15-213 or Senior standing"),
+            "15-213 or senior standing"
+        );
+
+        // Rendered expressions parse back to the same tree
+        let expr = "(15122 or 15150) and (21127 or 21128 and 15151)"
+            .parse::<Expr>()
+            .unwrap();
+        assert_eq!(expr.to_string().parse::<Expr>().unwrap(), expr);
+    }
+
+    #[test]
+    fn test_operands_flatten_chains() {
+        let expr = "15122 and (21127 and 21128) and (15150 or 15151)"
+            .parse::<Expr>()
+            .unwrap();
+        let operands: Vec<String> = expr.operands().iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            operands,
+            vec!["15-122", "21-127", "21-128", "15-150 or 15-151"]
+        );
+    }
+
+    #[test]
+    fn test_normal_forms() {
+        let leaves = |clauses: Vec<Vec<Expr>>| -> Vec<Vec<String>> {
+            clauses
+                .iter()
+                .map(|clause| clause.iter().map(ToString::to_string).collect())
+                .collect()
+        };
+
+        let expr = "(15122 or 15150) and (21127 or 21128)"
+            .parse::<Expr>()
+            .unwrap();
+        assert_eq!(
+            leaves(expr.to_dnf()),
+            vec![
+                vec!["15-122", "21-127"],
+                vec!["15-122", "21-128"],
+                vec!["15-150", "21-127"],
+                vec!["15-150", "21-128"],
+            ]
+        );
+        assert_eq!(
+            leaves(expr.to_cnf()),
+            vec![vec!["15-122", "15-150"], vec!["21-127", "21-128"]]
+        );
+
+        // Duplicates and absorbed clauses are removed
+        let expr = "15122 or 15122 and 21127 or (15150 and 15150)"
+            .parse::<Expr>()
+            .unwrap();
+        assert_eq!(leaves(expr.to_dnf()), vec![vec!["15-122"], vec!["15-150"]]);
+        assert_eq!(expr.to_dnf_within(3), Some(expr.to_dnf()));
+        assert_eq!(expr.to_dnf_within(2), None);
+        assert_eq!(
+            leaves(Expr::Course("15122".to_owned()).to_cnf()),
+            vec![vec!["15-122"]]
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::reservation_type::ReservationType;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     str::FromStr,
@@ -7,7 +7,7 @@ use std::{
 use strum::EnumIter;
 
 /// Represents different types of students that reservations target
-#[derive(Debug, Clone, PartialEq, Serialize, EnumIter)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, EnumIter)]
 pub enum StudentType {
     Freshmen,
     Sophomores,
@@ -53,7 +53,7 @@ impl Display for StudentType {
 }
 
 /// Represents a course reservation restriction
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Restriction {
     pub student_type: Option<StudentType>,
     pub restriction_type: Option<ReservationType>,
@@ -141,7 +141,7 @@ fn parse_school_pattern(content: &str) -> Option<(&str, &str)> {
 }
 
 /// Represents a course reservation for a section
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Reservation {
     pub section: String,
     pub restrictions: Vec<Restriction>,
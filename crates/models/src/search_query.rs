@@ -0,0 +1,85 @@
+/// The words and phrases of a web-style search query (e.g., `"machine learning" -vision`),
+/// without its operators
+///
+/// Full-text search understands the operators itself, but matching by trigram similarity
+/// compares the query as plain text, so quotes, `or`, and excluded words have to be taken out
+/// first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchTerms {
+    /// Words and phrases the results should match
+    pub included: Vec<String>,
+    /// Words and phrases following a `-`, which the results must not contain
+    pub excluded: Vec<String>,
+}
+
+impl SearchTerms {
+    /// Splits a search query the way `websearch_to_tsquery` reads it
+    pub fn parse(search: &str) -> Self {
+        let mut terms = Self::default();
+        let mut rest = search.trim_start();
+
+        while !rest.is_empty() {
+            let negated = rest.starts_with('-');
+            if negated {
+                rest = &rest[1..];
+            }
+
+            let (term, quoted, remaining) = match rest.strip_prefix('"') {
+                Some(phrase) => {
+                    let end = phrase.find('"').unwrap_or(phrase.len());
+                    (&phrase[..end], true, phrase.get(end + 1..).unwrap_or(""))
+                }
+                None => {
+                    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                    (&rest[..end], false, &rest[end..])
+                }
+            };
+            rest = remaining.trim_start();
+
+            let term = term
+                .split_whitespace()
+                .map(|word| word.trim_matches('"'))
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if term.is_empty() || (!quoted && !negated && term.eq_ignore_ascii_case("or")) {
+                continue;
+            }
+
+            if negated {
+                terms.excluded.push(term);
+            } else {
+                terms.included.push(term);
+            }
+        }
+
+        terms
+    }
+
+    /// The included words and phrases as one string, to compare by similarity
+    pub fn included_text(&self) -> String {
+        self.included.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_terms() {
+        let terms = SearchTerms::parse(r#""machine learning" or deep -vision -"neural nets""#);
+        assert_eq!(terms.included, vec!["machine learning", "deep"]);
+        assert_eq!(terms.excluded, vec!["vision", "neural nets"]);
+        assert_eq!(terms.included_text(), "machine learning deep");
+
+        assert_eq!(
+            SearchTerms::parse(r#"  machne   "learn "#),
+            SearchTerms {
+                included: vec!["machne".to_owned(), "learn".to_owned()],
+                excluded: Vec::new(),
+            }
+        );
+        assert_eq!(SearchTerms::parse(" - \"\" "), SearchTerms::default());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::syllabus_data::Semester;
+use std::collections::{BTreeMap, HashMap};
+
+/// A course offered in a semester
+#[derive(Debug, Clone, PartialEq)]
+pub struct Offering {
+    /// Course number without a dash (e.g., "15122")
+    pub number: String,
+    pub semester: Semester,
+    pub title: Option<String>,
+}
+
+/// A course in a report, with its title in the semester it was offered
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportedCourse {
+    pub number: String,
+    pub title: Option<String>,
+}
+
+/// A course offered again after it was not offered in the baseline semester
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReturningCourse {
+    pub number: String,
+    pub title: Option<String>,
+    /// The most recent semester it was offered in before this one
+    pub last_offered: Semester,
+}
+
+/// A course offered in both semesters under a different title
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleChange {
+    pub number: String,
+    pub old_title: Option<String>,
+    pub new_title: Option<String>,
+}
+
+/// How a department's courses changed between two semesters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DepartmentChanges {
+    /// Course numbers never offered before
+    pub new: Vec<ReportedCourse>,
+    /// Courses offered before, but not in the baseline semester
+    pub returning: Vec<ReturningCourse>,
+    /// Courses offered in the baseline semester, but not this one
+    pub discontinued: Vec<ReportedCourse>,
+    pub title_changes: Vec<TitleChange>,
+}
+
+impl DepartmentChanges {
+    fn is_empty(&self) -> bool {
+        self.new.is_empty()
+            && self.returning.is_empty()
+            && self.discontinued.is_empty()
+            && self.title_changes.is_empty()
+    }
+}
+
+/// How the courses of a semester changed since a baseline semester
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemesterComparison {
+    pub semester: Semester,
+    pub baseline: Semester,
+    /// Changes keyed by department, the first two digits of the course number; departments
+    /// without changes are left out
+    pub departments: BTreeMap<String, DepartmentChanges>,
+}
+
+/// Compares the courses offered in a semester with those offered in a baseline semester
+///
+/// # Arguments
+/// * `offerings` - The offerings of both semesters, along with every earlier offering of the
+///   semester's courses, so new courses can be told from returning ones
+/// * `semester` - The semester to report on
+/// * `baseline` - The semester to compare it with
+///
+/// # Returns
+/// The new, returning, and discontinued courses and the title changes of each department,
+/// each sorted by course number
+pub fn compare_semesters(
+    offerings: &[Offering],
+    semester: Semester,
+    baseline: Semester,
+) -> SemesterComparison {
+    let titles = |target: Semester| -> HashMap<&str, &Option<String>> {
+        offerings
+            .iter()
+            .filter(|offering| offering.semester == target)
+            .map(|offering| (offering.number.as_str(), &offering.title))
+            .collect()
+    };
+    let current = titles(semester);
+    let previous = titles(baseline);
+
+    // The most recent offering of each course before the semester
+    let mut last_offered: HashMap<&str, Semester> = HashMap::new();
+    for offering in offerings.iter().filter(|o| o.semester < semester) {
+        let last = last_offered
+            .entry(offering.number.as_str())
+            .or_insert(offering.semester);
+        *last = (*last).max(offering.semester);
+    }
+
+    let mut numbers: Vec<&str> = current.keys().chain(previous.keys()).copied().collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+
+    let mut departments: BTreeMap<String, DepartmentChanges> = BTreeMap::new();
+    for number in numbers {
+        let changes = departments
+            .entry(number.chars().take(2).collect())
+            .or_default();
+        let course = |title: &Option<String>| ReportedCourse {
+            number: number.to_owned(),
+            title: title.clone(),
+        };
+
+        match (current.get(number), previous.get(number)) {
+            (Some(new_title), Some(old_title)) if new_title != old_title => {
+                changes.title_changes.push(TitleChange {
+                    number: number.to_owned(),
+                    old_title: (*old_title).clone(),
+                    new_title: (*new_title).clone(),
+                });
+            }
+            (Some(_), Some(_)) => {}
+            (Some(title), None) => match last_offered.get(number) {
+                Some(&last_offered) => changes.returning.push(ReturningCourse {
+                    number: number.to_owned(),
+                    title: (*title).clone(),
+                    last_offered,
+                }),
+                None => changes.new.push(course(title)),
+            },
+            (None, Some(title)) => changes.discontinued.push(course(title)),
+            (None, None) => unreachable!("every number comes from one of the semesters"),
+        }
+    }
+    departments.retain(|_, changes| !changes.is_empty());
+
+    SemesterComparison {
+        semester,
+        baseline,
+        departments,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn offering(number: &str, semester: &str, title: &str) -> Offering {
+        Offering {
+            number: number.to_owned(),
+            semester: Semester::from_str(semester).unwrap(),
+            title: Some(title.to_owned()),
+        }
+    }
+
+    #[test]
+    fn test_compare_semesters() {
+        let offerings = [
+            offering("15122", "F24", "Principles of Imperative Computation"),
+            offering("15122", "F25", "Principles of Imperative Computation"),
+            offering("15150", "F24", "Functional Programming"),
+            offering("15150", "F25", "Principles of Functional Programming"),
+            offering("15210", "F24", "Parallel and Sequential Data Structures"),
+            offering(
+                "15251",
+                "F23",
+                "Great Ideas in Theoretical Computer Science",
+            ),
+            offering(
+                "15251",
+                "F25",
+                "Great Ideas in Theoretical Computer Science",
+            ),
+            offering("21127", "F25", "Concepts of Mathematics"),
+        ];
+        let f25 = Semester::from_str("F25").unwrap();
+        let f24 = Semester::from_str("F24").unwrap();
+
+        let comparison = compare_semesters(&offerings, f25, f24);
+        assert_eq!(comparison.departments.len(), 2);
+
+        let cs = &comparison.departments["15"];
+        assert!(cs.new.is_empty());
+        assert_eq!(
+            cs.returning,
+            vec![ReturningCourse {
+                number: "15251".into(),
+                title: Some("Great Ideas in Theoretical Computer Science".into()),
+                last_offered: Semester::from_str("F23").unwrap(),
+            }]
+        );
+        assert_eq!(cs.discontinued.len(), 1);
+        assert_eq!(cs.discontinued[0].number, "15210");
+        assert_eq!(
+            cs.title_changes,
+            vec![TitleChange {
+                number: "15150".into(),
+                old_title: Some("Functional Programming".into()),
+                new_title: Some("Principles of Functional Programming".into()),
+            }]
+        );
+
+        let math = &comparison.departments["21"];
+        assert_eq!(math.new.len(), 1);
+        assert_eq!(math.new[0].number, "21127");
+    }
+}
@@ -0,0 +1,322 @@
+use crate::syllabus_data::Semester;
+use std::collections::{BTreeSet, HashMap};
+
+/// Number of similar courses returned when none is given
+pub const DEFAULT_SIMILAR_COURSES: usize = 10;
+
+/// Most similar courses that can be returned at once
+pub const MAX_SIMILAR_COURSES: usize = 50;
+
+/// Words too common in course descriptions to tell courses apart
+const STOP_WORDS: &[&str] = &[
+    "about", "also", "and", "any", "are", "been", "both", "but", "can", "course", "courses",
+    "each", "for", "from", "has", "have", "how", "into", "its", "may", "more", "most", "not", "of",
+    "one", "only", "other", "our", "over", "such", "students", "student", "that", "the", "their",
+    "them", "then", "there", "these", "they", "this", "those", "through", "topics", "use", "used",
+    "well", "were", "what", "when", "which", "while", "who", "will", "with", "within", "you",
+    "your",
+];
+
+/// The text of a course offering used to find similar courses
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseDocument {
+    /// Course number without a dash (e.g., "15122")
+    pub number: String,
+    pub semester: Semester,
+    pub description: Option<String>,
+    /// Titles of the offering's lectures and sections, the first being the course title
+    pub titles: Vec<String>,
+}
+
+/// A course similar to another one
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarCourse {
+    pub number: String,
+    pub title: Option<String>,
+    /// Cosine similarity between 0 and 1
+    pub score: f32,
+}
+
+/// A course number with the text of its most recent offering
+#[derive(Debug, Clone)]
+struct IndexedCourse {
+    number: String,
+    title: Option<String>,
+    /// Every semester the course was offered in
+    semesters: BTreeSet<Semester>,
+    /// TF-IDF weight of each term, normalized to unit length
+    weights: HashMap<u32, f32>,
+}
+
+/// A TF-IDF index over course descriptions and titles
+///
+/// Each course number is indexed once, using the text of its most recent offering, so
+/// courses can be compared even when they were never offered in the same semester.
+#[derive(Debug, Clone, Default)]
+pub struct SimilarityIndex {
+    courses: Vec<IndexedCourse>,
+    by_number: HashMap<String, usize>,
+    /// Courses containing each term, with the term's weight in the course
+    postings: HashMap<u32, Vec<(usize, f32)>>,
+}
+
+/// Splits text into lowercase words, dropping numbers, short words, and stop words
+///
+/// A trailing plural "s" is removed, so "algorithm" and "algorithms" are the same term, but
+/// not from words like "class", "analysis", or "focus".
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() >= 3 && !word.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_lowercase)
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.len() >= 4 && !stem.ends_with(['s', 'i', 'u']) => stem.to_owned(),
+            _ => word,
+        })
+}
+
+impl SimilarityIndex {
+    /// Builds the index from every stored offering
+    ///
+    /// # Arguments
+    /// * `documents` - Offerings in any order; the most recent offering of each course number
+    ///   is indexed
+    pub fn new(documents: Vec<CourseDocument>) -> Self {
+        let mut latest: HashMap<String, CourseDocument> = HashMap::new();
+        let mut semesters: HashMap<String, BTreeSet<Semester>> = HashMap::new();
+        for document in documents {
+            semesters
+                .entry(document.number.clone())
+                .or_default()
+                .insert(document.semester);
+            match latest.get(&document.number) {
+                Some(seen) if seen.semester >= document.semester => {}
+                _ => {
+                    latest.insert(document.number.clone(), document);
+                }
+            }
+        }
+
+        let mut numbers: Vec<String> = latest.keys().cloned().collect();
+        numbers.sort_unstable();
+
+        // Count the terms of each course, and the courses each term appears in
+        let mut term_ids: HashMap<String, u32> = HashMap::new();
+        let mut document_frequency: HashMap<u32, usize> = HashMap::new();
+        let mut counts: Vec<HashMap<u32, usize>> = Vec::with_capacity(numbers.len());
+        for number in &numbers {
+            let document = &latest[number];
+            let text = document
+                .titles
+                .iter()
+                .map(String::as_str)
+                .chain(document.description.as_deref())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let mut term_counts: HashMap<u32, usize> = HashMap::new();
+            for term in tokenize(&text) {
+                let next_id = term_ids.len() as u32;
+                let id = *term_ids.entry(term).or_insert(next_id);
+                *term_counts.entry(id).or_default() += 1;
+            }
+            for id in term_counts.keys() {
+                *document_frequency.entry(*id).or_default() += 1;
+            }
+            counts.push(term_counts);
+        }
+
+        let total = numbers.len() as f32;
+        let mut index = Self::default();
+        for (position, (number, term_counts)) in numbers.into_iter().zip(counts).enumerate() {
+            // Sublinear term frequency, so a word repeated in a long description doesn't
+            // dominate it
+            let mut weights: HashMap<u32, f32> = term_counts
+                .into_iter()
+                .map(|(id, count)| {
+                    let idf = (total / document_frequency[&id] as f32).ln() + 1.0;
+                    (id, (1.0 + (count as f32).ln()) * idf)
+                })
+                .collect();
+            let norm = weights.values().map(|w| w * w).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                weights.values_mut().for_each(|w| *w /= norm);
+            }
+
+            for (id, weight) in &weights {
+                index
+                    .postings
+                    .entry(*id)
+                    .or_default()
+                    .push((position, *weight));
+            }
+            index.by_number.insert(number.clone(), position);
+            index.courses.push(IndexedCourse {
+                title: latest[&number].titles.first().cloned(),
+                semesters: semesters.remove(&number).unwrap_or_default(),
+                number,
+                weights,
+            });
+        }
+
+        index
+    }
+
+    /// Number of indexed course numbers
+    pub fn len(&self) -> usize {
+        self.courses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.courses.is_empty()
+    }
+
+    /// Finds the courses whose text is most similar to a course's
+    ///
+    /// # Arguments
+    /// * `number` - Course number without a dash
+    /// * `semester` - Only return courses offered in this semester, if given
+    /// * `limit` - Most courses to return
+    ///
+    /// # Returns
+    /// The most similar courses, best first, leaving out the course itself and courses with
+    /// nothing in common with it, or `None` if the course isn't indexed
+    pub fn similar(
+        &self,
+        number: &str,
+        semester: Option<Semester>,
+        limit: usize,
+    ) -> Option<Vec<SimilarCourse>> {
+        let &target = self.by_number.get(number)?;
+
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        for (id, weight) in &self.courses[target].weights {
+            for (position, other) in &self.postings[id] {
+                *scores.entry(*position).or_default() += weight * other;
+            }
+        }
+
+        let mut ranked: Vec<(usize, f32)> = scores
+            .into_iter()
+            .filter(|(position, score)| *position != target && *score > 0.0)
+            .filter(|(position, _)| {
+                semester
+                    .is_none_or(|semester| self.courses[*position].semesters.contains(&semester))
+            })
+            .collect();
+        // Ties are broken by course number, so results are stable
+        ranked.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| self.courses[*a].number.cmp(&self.courses[*b].number))
+        });
+
+        Some(
+            ranked
+                .into_iter()
+                .take(limit)
+                .map(|(position, score)| {
+                    let course = &self.courses[position];
+                    SimilarCourse {
+                        number: course.number.clone(),
+                        title: course.title.clone(),
+                        score: score.min(1.0),
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn document(number: &str, semester: &str, title: &str, description: &str) -> CourseDocument {
+        CourseDocument {
+            number: number.to_owned(),
+            semester: Semester::from_str(semester).unwrap(),
+            description: Some(description.to_owned()),
+            titles: vec![title.to_owned()],
+        }
+    }
+
+    fn index() -> SimilarityIndex {
+        SimilarityIndex::new(vec![
+            document(
+                "10301",
+                "F25",
+                "Introduction to Machine Learning",
+                "Machine learning algorithms for classification, regression, and neural networks.",
+            ),
+            document(
+                "10601",
+                "S25",
+                "Introduction to Machine Learning (Master's)",
+                "Graduate machine learning: classification, regression, and probabilistic models.",
+            ),
+            document(
+                "10601",
+                "F24",
+                "Machine Learning",
+                "An outdated description about gardening.",
+            ),
+            document(
+                "15210",
+                "F25",
+                "Parallel and Sequential Data Structures and Algorithms",
+                "Parallel algorithms and data structures, with an emphasis on functional programming.",
+            ),
+            document(
+                "82101",
+                "F25",
+                "Elementary French I",
+                "Speaking, reading, and writing French.",
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("The algorithms of 15-122, in analysis!").collect::<Vec<_>>(),
+            vec!["algorithm", "analysis"]
+        );
+    }
+
+    #[test]
+    fn test_similar_courses() {
+        let index = index();
+        assert_eq!(index.len(), 4);
+
+        let similar = index.similar("10301", None, 10).unwrap();
+        assert_eq!(similar[0].number, "10601");
+        // The most recent offering's title is used
+        assert_eq!(
+            similar[0].title.as_deref(),
+            Some("Introduction to Machine Learning (Master's)")
+        );
+        assert!(similar[0].score > 0.3 && similar[0].score <= 1.0);
+        assert!(similar.iter().all(|course| course.number != "82101"));
+        assert!(
+            similar
+                .windows(2)
+                .all(|pair| pair[0].score >= pair[1].score)
+        );
+
+        // 10-601 is only offered in the spring, and in fall 2024
+        let fall = Semester::from_str("F25").unwrap();
+        let similar = index.similar("10301", Some(fall), 10).unwrap();
+        assert_eq!(
+            similar
+                .iter()
+                .map(|course| course.number.as_str())
+                .collect::<Vec<_>>(),
+            vec!["15210"]
+        );
+
+        assert_eq!(index.similar("10301", None, 1).unwrap().len(), 1);
+        assert!(index.similar("99999", None, 10).is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::{Datelike, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
+    cmp::Ordering,
     collections::HashMap,
     fmt::{Display as FmtDisplay, Formatter, Result as FmtResult},
     hash::Hash,
@@ -16,7 +17,18 @@ use strum::{
 pub type SyllabusMap = HashMap<(Year, Season, String, String), String>;
 
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, EnumString, EnumIter, AsRefStr, EnumProperty,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumString,
+    EnumIter,
+    AsRefStr,
+    EnumProperty,
 )]
 pub enum Season {
     #[strum(serialize = "F", props(full = "fall"))]
@@ -41,9 +53,19 @@ impl Season {
     pub fn all() -> Vec<Season> {
         Season::iter().collect()
     }
+
+    /// Position of the season within a calendar year, for ordering semesters chronologically
+    pub fn term_order(&self) -> u8 {
+        match self {
+            Season::Spring => 0,
+            Season::Summer1 => 1,
+            Season::Summer2 => 2,
+            Season::Fall => 3,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Year(pub u16);
 
 impl Year {
@@ -76,8 +98,92 @@ impl FmtDisplay for Year {
     }
 }
 
+/// A single semester, written as a season code and year (e.g., "F25" or "F2025")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Semester {
+    pub season: Season,
+    pub year: Year,
+}
+
+impl FromStr for Semester {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid semester: {s} (expected e.g. F25 or F2025)");
+
+        let (season, year) = s.split_at_checked(1).ok_or_else(invalid)?;
+        let season = Season::from_str(&season.to_uppercase()).map_err(|_| invalid())?;
+        let year = match year.len() {
+            2 => 2000 + year.parse::<u16>().map_err(|_| invalid())?,
+            4 => year.parse::<u16>().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            season,
+            year: Year(year),
+        })
+    }
+}
+
+impl FmtDisplay for Semester {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}{}", self.season.as_str(), self.year)
+    }
+}
+
+impl Semester {
+    /// The semester before this one in the academic calendar, where fall follows spring and
+    /// the summer sessions come between them
+    ///
+    /// Fall and spring skip over the summer sessions, since few courses run in them.
+    pub fn previous(&self) -> Semester {
+        let (season, year) = match self.season {
+            Season::Fall => (Season::Spring, *self.year),
+            Season::Spring => (Season::Fall, *self.year - 1),
+            Season::Summer1 => (Season::Spring, *self.year),
+            Season::Summer2 => (Season::Summer1, *self.year),
+        };
+
+        Semester {
+            season,
+            year: Year(year),
+        }
+    }
+
+    /// The same season a year earlier
+    pub fn year_before(&self) -> Semester {
+        Semester {
+            season: self.season,
+            year: Year(*self.year - 1),
+        }
+    }
+}
+
+impl PartialOrd for Semester {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Semesters are ordered chronologically
+impl Ord for Semester {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self.year, self.season.term_order()).cmp(&(*other.year, other.season.term_order()))
+    }
+}
+
 #[derive(
-    Debug, Clone, Copy, PartialEq, Serialize, Display, EnumString, EnumIter, IntoStaticStr,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumIter,
+    IntoStaticStr,
 )]
 pub enum Department {
     CB,
@@ -215,4 +321,26 @@ mod tests {
         assert!(all.contains(&Year(2018)));
         assert!(all.contains(&Year(current_year)));
     }
+
+    #[test]
+    fn test_semester_from_str() {
+        let f25 = Semester::from_str("f25").unwrap();
+        assert_eq!(f25, Semester::from_str("F2025").unwrap());
+        assert_eq!(f25.to_string(), "F25");
+        assert!(Semester::from_str("X25").is_err());
+        assert!(Semester::from_str("F5").is_err());
+    }
+
+    #[test]
+    fn test_semester_order() {
+        let semester = |s: &str| Semester::from_str(s).unwrap();
+
+        assert!(semester("S25") < semester("M25"));
+        assert!(semester("N25") < semester("F25"));
+        assert!(semester("F24") < semester("S25"));
+        assert_eq!(semester("F25").previous(), semester("S25"));
+        assert_eq!(semester("S25").previous(), semester("F24"));
+        assert_eq!(semester("N25").previous(), semester("M25"));
+        assert_eq!(semester("F25").year_before(), semester("F24"));
+    }
 }
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as DeError};
 use std::{
     cmp::Ordering,
     fmt::{Display, Formatter, Result as FmtResult},
@@ -31,7 +31,7 @@ fn compare_units(min_a: f32, max_a: f32, min_b: f32, max_b: f32) -> Option<Order
 }
 
 /// Represents the number of units a course is worth
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum UnitTypeSimple {
     /// A fixed number of units
     Single(f32),
@@ -92,7 +92,7 @@ impl Display for UnitTypeSimple {
 }
 
 /// Represents the number of units a course is worth
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum UnitType {
     /// A fixed number of units
     Single(f32),
@@ -288,7 +288,9 @@ impl Display for UnitType {
 }
 
 /// Represents how many units a course is worth
-#[derive(Debug, Clone, Serialize, PartialEq)]
+///
+/// Serialized as its string form (e.g., "12", "3-9", or "VAR").
+#[derive(Debug, Clone, PartialEq)]
 pub enum Units {
     /// Variable units
     VAR,
@@ -300,6 +302,22 @@ impl Units {
     pub fn new(value: f32) -> Self {
         Self::Value(UnitType::Single(value))
     }
+
+    /// Fewest units the course can be taken for, counting variable units as 0
+    pub fn min_value(&self) -> f32 {
+        match self {
+            Self::VAR => 0.0,
+            Self::Value(value) => value.min_value(),
+        }
+    }
+
+    /// Most units the course can be taken for, counting variable units as infinite
+    pub fn max_value(&self) -> f32 {
+        match self {
+            Self::VAR => f32::INFINITY,
+            Self::Value(value) => value.max_value(),
+        }
+    }
 }
 
 impl PartialOrd for Units {
@@ -352,6 +370,19 @@ impl Display for Units {
     }
 }
 
+impl Serialize for Units {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Units {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let units = String::deserialize(deserializer)?;
+        Self::from_str(&units).map_err(|e| D::Error::custom(format!("{e}: {units}")))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -530,4 +561,16 @@ mod tests {
             assert_eq!(unit, unit.to_string().parse::<Units>().unwrap());
         }
     }
+
+    #[test]
+    fn test_units_serde_round_trip() {
+        for input in ["12", "4.5", "3-9", "3,6-9", "VAR"] {
+            let units = Units::from_str(input).unwrap();
+            let json = serde_json::to_string(&units).unwrap();
+            assert_eq!(json, format!("\"{input}\""));
+            assert_eq!(serde_json::from_str::<Units>(&json).unwrap(), units);
+        }
+
+        assert!(serde_json::from_str::<Units>("\"\"").is_err());
+    }
 }
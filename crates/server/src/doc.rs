@@ -1,4 +1,6 @@
-use crate::routes::{auth, course, root};
+use crate::routes::{
+    auth, autocomplete, catalog, change, course, plan, program, report, root, syllabus,
+};
 use utoipa::{
     Modify, OpenApi,
     openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
@@ -29,12 +31,30 @@ impl Modify for SecurityAddon {
         auth::auth,
         course::get_courses,
         course::get_course_by_id,
-        course::get_course_filters
+        course::get_course_filters,
+        course::get_course_path,
+        course::compare_course,
+        course::get_similar_courses,
+        autocomplete::autocomplete,
+        catalog::get_catalog_course,
+        change::get_changes,
+        plan::validate,
+        program::get_programs,
+        program::audit_program,
+        report::get_semester_report,
+        syllabus::get_syllabus
     ),
     modifiers(&SecurityAddon),
     tags(
         (name = "Authentication", description = "Authentication related endpoints"),
         (name = "Courses", description = "Course related endpoints"),
+        (name = "Search", description = "Search completion endpoints"),
+        (name = "Catalog", description = "Course history across semesters"),
+        (name = "Changes", description = "Course change feed endpoints"),
+        (name = "Syllabi", description = "Archived syllabus endpoints"),
+        (name = "Plans", description = "Multi-semester plan endpoints"),
+        (name = "Programs", description = "Degree requirement and audit endpoints"),
+        (name = "Reports", description = "Semester-over-semester course reports"),
     ),
     info(
         title = "Course API",
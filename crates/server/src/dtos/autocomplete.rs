@@ -0,0 +1,55 @@
+use crate::dtos::course::dashed;
+use models::autocomplete::Suggestion;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct AutocompleteQueryParams {
+    /// What has been typed so far (e.g., `15-1`, `imperative`, or an instructor's name)
+    pub q: String,
+    /// Most suggestions to return, 10 by default and at most 25
+    pub limit: Option<usize>,
+}
+
+/// Something a search can be completed to
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SuggestionResponse {
+    /// Courses whose number starts with the department's two digits
+    Department {
+        /// First two digits of the department's course numbers (e.g., "15")
+        code: String,
+        /// Department name (e.g., "Computer Science"), if known
+        name: Option<String>,
+        course_count: usize,
+    },
+    Course {
+        /// Course number (e.g., "15-122")
+        number: String,
+        title: Option<String>,
+    },
+    Instructor {
+        name: String,
+    },
+}
+
+impl From<Suggestion> for SuggestionResponse {
+    fn from(suggestion: Suggestion) -> Self {
+        match suggestion {
+            Suggestion::Department {
+                code,
+                name,
+                course_count,
+            } => Self::Department {
+                code,
+                name,
+                course_count,
+            },
+            Suggestion::Course { number, title } => Self::Course {
+                number: dashed(&number),
+                title,
+            },
+            Suggestion::Instructor { name } => Self::Instructor { name },
+        }
+    }
+}
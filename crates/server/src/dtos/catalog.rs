@@ -0,0 +1,168 @@
+use crate::dtos::course::dashed;
+use database::entities::{catalog_courses, course_offerings};
+use models::availability::{OfferingHistory, OfferingPattern};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// A course across every semester it has been offered
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CatalogCourseResponse {
+    pub number: String,
+    /// Title of the most recent offering
+    pub title: Option<String>,
+    /// Description of the most recent offering
+    pub description: Option<String>,
+    /// Every offering, oldest first
+    pub offerings: Vec<OfferingResponse>,
+    /// Each title the course has had, oldest first
+    pub title_history: Vec<HistoryEntry>,
+    /// Each description the course has had, oldest first
+    pub description_history: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OfferingResponse {
+    pub season: String,
+    pub year: i16,
+    /// ID of the offering's course at `/courses/{id}`, if its semester is still stored
+    pub course_id: Option<String>,
+    pub title: Option<String>,
+    pub units: String,
+}
+
+/// A value a course had over a run of consecutive offerings
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HistoryEntry {
+    pub value: Option<String>,
+    pub first_season: String,
+    pub first_year: i16,
+    pub last_season: String,
+    pub last_year: i16,
+}
+
+/// Collapses consecutive offerings with the same value into one entry
+fn history(
+    offerings: &[course_offerings::Model],
+    value: impl Fn(&course_offerings::Model) -> &Option<String>,
+) -> Vec<HistoryEntry> {
+    let mut entries: Vec<HistoryEntry> = Vec::new();
+
+    for offering in offerings {
+        match entries.last_mut() {
+            Some(entry) if entry.value == *value(offering) => {
+                entry.last_season = offering.season.clone();
+                entry.last_year = offering.year;
+            }
+            _ => entries.push(HistoryEntry {
+                value: value(offering).clone(),
+                first_season: offering.season.clone(),
+                first_year: offering.year,
+                last_season: offering.season.clone(),
+                last_year: offering.year,
+            }),
+        }
+    }
+
+    entries
+}
+
+impl From<(catalog_courses::Model, Vec<course_offerings::Model>)> for CatalogCourseResponse {
+    fn from((course, offerings): (catalog_courses::Model, Vec<course_offerings::Model>)) -> Self {
+        Self {
+            number: dashed(&course.number),
+            title: course.title,
+            description: course.description,
+            title_history: history(&offerings, |offering| &offering.title),
+            description_history: history(&offerings, |offering| &offering.description),
+            offerings: offerings
+                .into_iter()
+                .map(|offering| OfferingResponse {
+                    season: offering.season,
+                    year: offering.year,
+                    course_id: offering.course_id.map(|id| id.to_string()),
+                    title: offering.title,
+                    units: offering.units,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct AvailabilityQueryParams {
+    /// Number of future semesters to predict, summer sessions included (defaults to 8, at
+    /// most 20)
+    pub semesters: Option<usize>,
+}
+
+/// How a course has been offered, and whether it will be offered in coming semesters
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AvailabilityResponse {
+    pub number: String,
+    pub pattern: OfferingPatternKind,
+    /// How well the pattern fits the course's history, from 0 to 1
+    pub confidence: f32,
+    /// The semesters after the most recent recorded one, earliest first
+    pub predictions: Vec<PredictionResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OfferingPatternKind {
+    EverySemester,
+    EveryFall,
+    EverySpring,
+    AlternatingFalls,
+    AlternatingSprings,
+    SummerOnly,
+    Irregular,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PredictionResponse {
+    pub season: String,
+    pub year: u16,
+    /// Chance the course is offered, from 0 to 1
+    pub likelihood: f32,
+}
+
+impl From<OfferingPattern> for OfferingPatternKind {
+    fn from(pattern: OfferingPattern) -> Self {
+        match pattern {
+            OfferingPattern::EverySemester => Self::EverySemester,
+            OfferingPattern::EveryFall => Self::EveryFall,
+            OfferingPattern::EverySpring => Self::EverySpring,
+            OfferingPattern::AlternatingFalls => Self::AlternatingFalls,
+            OfferingPattern::AlternatingSprings => Self::AlternatingSprings,
+            OfferingPattern::SummerOnly => Self::SummerOnly,
+            OfferingPattern::Irregular => Self::Irregular,
+        }
+    }
+}
+
+impl AvailabilityResponse {
+    /// Predicts the availability of a course
+    ///
+    /// # Arguments
+    /// * `number` - Course number without a dash
+    /// * `history` - The course's offerings
+    /// * `semesters` - Number of future semesters to predict
+    pub fn new(number: &str, history: &OfferingHistory, semesters: usize) -> Self {
+        let (pattern, confidence) = history.pattern();
+
+        Self {
+            number: dashed(number),
+            pattern: pattern.into(),
+            confidence,
+            predictions: history
+                .predict(semesters)
+                .into_iter()
+                .map(|prediction| PredictionResponse {
+                    season: prediction.season.as_str().to_owned(),
+                    year: *prediction.year,
+                    likelihood: prediction.likelihood,
+                })
+                .collect(),
+        }
+    }
+}
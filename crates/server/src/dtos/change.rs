@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CourseChangeResponse {
+    pub id: String,
+    pub scrape_run_id: String,
+    pub number: String,
+    pub season: String,
+    pub year: i16,
+    pub change_type: String,
+    pub section: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ChangeQueryParams {
+    /// Only return changes detected after this time
+    pub since: Option<DateTime<Utc>>,
+    pub season: Option<String>,
+    pub year: Option<i16>,
+}
@@ -1,5 +1,15 @@
+use crate::dtos::catalog::AvailabilityResponse;
 use chrono::NaiveTime;
+use models::{
+    course_change::CourseChange,
+    course_data::CourseNumber,
+    facets::{FacetCounts, Facets},
+    prerequisite_graph::CoursePath,
+    requisite::Expr,
+    similarity::SimilarCourse,
+};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -11,12 +21,125 @@ pub struct CourseResponse {
     pub year: i16,
     pub special_permission: bool,
     pub description: Option<String>,
-    pub prerequisites: Option<String>,
+    pub prerequisites: Option<PrerequisitesResponse>,
     pub notes: Option<String>,
     pub related_urls: Vec<String>,
     pub components: Vec<ComponentResponse>,
 }
 
+/// A course with the predicted availability of its course number
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CourseDetailResponse {
+    #[serde(flatten)]
+    pub course: CourseResponse,
+    pub availability: AvailabilityResponse,
+}
+
+/// A course's prerequisites, rendered for display and as a tree
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PrerequisitesResponse {
+    /// The prerequisites as written in the catalog (e.g., "15-122 and (21-127 or 21-128)")
+    pub text: String,
+    pub tree: RequisiteNode,
+    /// The ways to satisfy the prerequisites, each a list of requirements that must all be met;
+    /// only listed for a single course, and left out when there are too many ways
+    pub options: Option<Vec<Vec<RequisiteNode>>>,
+}
+
+impl PrerequisitesResponse {
+    /// Most ways to satisfy prerequisites that are listed
+    const MAX_OPTIONS: usize = 64;
+
+    /// Lists the ways to satisfy the prerequisites, unless there are too many
+    pub fn with_options(mut self, expr: &Expr) -> Self {
+        self.options = expr.to_dnf_within(Self::MAX_OPTIONS).map(|clauses| {
+            clauses
+                .iter()
+                .map(|clause| clause.iter().map(RequisiteNode::from).collect())
+                .collect()
+        });
+        self
+    }
+}
+
+impl From<Expr> for PrerequisitesResponse {
+    fn from(expr: Expr) -> Self {
+        Self {
+            text: expr.to_string(),
+            tree: RequisiteNode::from(&expr),
+            options: None,
+        }
+    }
+}
+
+/// A node of a prerequisite tree, with chains of the same operator flattened
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RequisiteNode {
+    /// A course that must be completed
+    Course {
+        number: String,
+    },
+    /// A course that must be completed with at least a letter grade
+    MinGrade {
+        number: String,
+        grade: String,
+    },
+    /// A course that may be taken in the same semester
+    Concurrent {
+        number: String,
+    },
+    InstructorPermission,
+    /// A minimum class standing (e.g., "junior")
+    ClassStanding {
+        standing: String,
+    },
+    /// Text that could not be parsed
+    Text {
+        text: String,
+    },
+    /// Every child must be satisfied
+    And {
+        #[schema(no_recursion)]
+        children: Vec<RequisiteNode>,
+    },
+    /// Any child suffices
+    Or {
+        #[schema(no_recursion)]
+        children: Vec<RequisiteNode>,
+    },
+}
+
+impl From<&Expr> for RequisiteNode {
+    fn from(expr: &Expr) -> Self {
+        let children = || expr.operands().into_iter().map(Self::from).collect();
+
+        match expr {
+            Expr::Course(number) => Self::Course {
+                number: number.clone(),
+            },
+            Expr::MinGrade { course, grade } => Self::MinGrade {
+                number: course.clone(),
+                grade: grade.clone(),
+            },
+            Expr::Concurrent(number) => Self::Concurrent {
+                number: number.clone(),
+            },
+            Expr::InstructorPermission => Self::InstructorPermission,
+            Expr::ClassStanding(standing) => Self::ClassStanding {
+                standing: standing.to_string(),
+            },
+            Expr::Text(text) => Self::Text { text: text.clone() },
+            Expr::And(_, _) => Self::And {
+                children: children(),
+            },
+            Expr::Or(_, _) => Self::Or {
+                children: children(),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ComponentResponse {
     pub id: String,
@@ -24,6 +147,8 @@ pub struct ComponentResponse {
     pub component_type: String,
     pub code: String,
     pub syllabus_url: Option<String>,
+    /// Path of the archived copy of the syllabus, which doesn't expire like `syllabus_url`
+    pub syllabus_archive_url: Option<String>,
     pub meetings: Vec<MeetingResponse>,
 }
 
@@ -33,15 +158,67 @@ pub struct MeetingResponse {
     pub days_pattern: String,
     pub time_begin: Option<NaiveTime>,
     pub time_end: Option<NaiveTime>,
+    pub bldg_room: String,
     pub campus: String,
+    pub instructors: Vec<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PaginatedCoursesResponse {
     pub courses: Vec<CourseResponse>,
+    pub facets: FacetsResponse,
+    /// Titles and instructor names resembling the search, if it matched nothing
+    pub did_you_mean: Vec<String>,
     pub pagination: PaginationMeta,
 }
 
+/// Number of matching courses with each value of a filter, leaving out values without any
+///
+/// Each facet is counted with every active filter except its own, so the counts of a filter's
+/// other values are how many courses selecting them adds.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FacetsResponse {
+    /// First two digits of the course number
+    pub department: Vec<FacetCountResponse>,
+    pub season: Vec<FacetCountResponse>,
+    pub year: Vec<FacetCountResponse>,
+    /// Units ranges ("0-<6", "6-<10", "10-<13", "13+", or "VAR"), counting courses with a range of
+    /// units in every range it overlaps
+    pub units: Vec<FacetCountResponse>,
+    pub campus: Vec<FacetCountResponse>,
+    /// Days of the week a meeting occurs (e.g., "MWF" or "TBA")
+    pub days: Vec<FacetCountResponse>,
+    /// When a meeting starts ("morning", "afternoon", "evening", or "TBA")
+    pub time_of_day: Vec<FacetCountResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FacetCountResponse {
+    pub value: String,
+    pub count: u64,
+}
+
+impl From<Facets> for FacetsResponse {
+    fn from(facets: Facets) -> Self {
+        let counts = |counts: FacetCounts| {
+            counts
+                .into_iter()
+                .map(|(value, count)| FacetCountResponse { value, count })
+                .collect()
+        };
+
+        Self {
+            department: counts(facets.department),
+            season: counts(facets.season),
+            year: counts(facets.year),
+            units: counts(facets.units),
+            campus: counts(facets.campus),
+            days: counts(facets.days),
+            time_of_day: counts(facets.time_of_day),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PaginationMeta {
     pub page: u64,
@@ -62,8 +239,27 @@ pub struct CourseQueryParams {
 
     pub season: Option<Vec<String>>,
     pub year: Option<Vec<i16>>,
-    // pub search: Option<String>,
+    /// Search course descriptions, titles, instructors, and syllabus text (e.g.,
+    /// `"machine learning" -vision`); misspelled titles and names are still matched
+    pub search: Option<String>,
     pub department: Option<Vec<String>>,
+    /// Days to keep free of meetings (e.g., `F` for no Friday meetings)
+    pub free_days: Option<String>,
+    /// Days the course must meet on (e.g., `TR`)
+    pub required_days: Option<String>,
+    /// Earliest time meetings may begin (e.g., `10:00`)
+    #[param(value_type = Option<String>)]
+    pub earliest_start: Option<NaiveTime>,
+    /// Latest time meetings may end (e.g., `17:00`)
+    #[param(value_type = Option<String>)]
+    pub latest_end: Option<NaiveTime>,
+    /// Comma-separated blocks of free time every meeting must fit in, each days followed by a
+    /// 24-hour time range (e.g., `MWF 09:00-12:00,TR 13:00-17:00`)
+    pub free_blocks: Option<String>,
+    /// Whether meetings with days or times to be announced pass the day and time filters,
+    /// since they can't be shown to fit (defaults to false)
+    #[serde(default)]
+    pub include_tba: bool,
 }
 
 fn default_page() -> u64 {
@@ -73,3 +269,111 @@ fn default_page() -> u64 {
 fn default_per_page() -> u64 {
     20
 }
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct PathQueryParams {
+    /// Comma-separated numbers of courses already completed (e.g., `15112,21127`)
+    pub completed: Option<String>,
+}
+
+/// The courses a student must take to become eligible for a course
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PathResponse {
+    /// Course number of the target (e.g., "10-701")
+    pub target: String,
+    /// Minimum number of semesters needed before the target can be taken
+    pub semester_count: usize,
+    /// Courses to take in each semester, earliest first
+    pub semesters: Vec<Vec<String>>,
+    /// Requirements that aren't courses, like instructor permission, which the path relies on
+    pub conditions: Vec<String>,
+}
+
+impl From<CoursePath> for PathResponse {
+    fn from(path: CoursePath) -> Self {
+        Self {
+            target: dashed(&path.target),
+            semester_count: path.semesters.len(),
+            semesters: path
+                .semesters
+                .iter()
+                .map(|semester| semester.iter().map(|number| dashed(number)).collect())
+                .collect(),
+            conditions: path.conditions.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct CompareQueryParams {
+    /// The earlier semester, as a season code and year (e.g., `F24`)
+    pub a: String,
+    /// The later semester, as a season code and year (e.g., `F25`)
+    pub b: String,
+}
+
+/// Two offerings of a course and what changed between them
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CourseComparisonResponse {
+    pub a: CourseResponse,
+    pub b: CourseResponse,
+    /// Changes from `a` to `b`: units, then sections by code, then description and
+    /// prerequisites
+    pub changes: Vec<OfferingChangeResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OfferingChangeResponse {
+    pub change_type: String,
+    /// Lecture/section code the change applies to, if any
+    pub section: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl From<CourseChange> for OfferingChangeResponse {
+    fn from(change: CourseChange) -> Self {
+        Self {
+            change_type: change.kind.as_str().to_owned(),
+            section: change.section,
+            old_value: change.old_value,
+            new_value: change.new_value,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct SimilarQueryParams {
+    /// Only return courses offered in this semester (e.g., `F25`)
+    pub semester: Option<String>,
+    /// Most courses to return, 10 by default and at most 50
+    pub limit: Option<usize>,
+}
+
+/// A course whose description and titles resemble another course's
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimilarCourseResponse {
+    /// Course number (e.g., "10-601")
+    pub number: String,
+    /// Title of the course's most recent offering
+    pub title: Option<String>,
+    /// Similarity between 0 and 1
+    pub score: f32,
+}
+
+impl From<SimilarCourse> for SimilarCourseResponse {
+    fn from(course: SimilarCourse) -> Self {
+        Self {
+            number: dashed(&course.number),
+            title: course.title,
+            score: course.score,
+        }
+    }
+}
+
+/// Formats a course number with a dash (e.g., "15-122"), leaving invalid numbers as they are
+pub fn dashed(number: &str) -> String {
+    CourseNumber::from_str(number)
+        .map(|number| number.as_full_string())
+        .unwrap_or_else(|_| number.to_owned())
+}
@@ -1 +1,7 @@
+pub mod autocomplete;
+pub mod catalog;
+pub mod change;
 pub mod course;
+pub mod plan;
+pub mod program;
+pub mod report;
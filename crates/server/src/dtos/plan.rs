@@ -0,0 +1,118 @@
+use crate::dtos::{catalog::AvailabilityResponse, course::dashed};
+use models::plan::{CourseIssue, CourseReport, SemesterReport};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PlanRequest {
+    /// The plan's semesters, in the order they are taken
+    pub semesters: Vec<PlannedSemesterRequest>,
+    /// Courses completed before the plan starts
+    #[serde(default)]
+    pub completed: Vec<String>,
+    /// Most units allowed in a semester before it is an overload (defaults to 54)
+    pub max_units: Option<f32>,
+    /// Number of future semesters to predict the availability of each course for, summer
+    /// sessions included (defaults to 8, at most 20)
+    pub predicted_semesters: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PlannedSemesterRequest {
+    /// Season code (e.g., "F" or "S")
+    pub season: String,
+    /// Full year (e.g., 2025)
+    pub year: u16,
+    /// Course numbers, with or without a dash
+    pub courses: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PlanResponse {
+    /// Whether no semester has any issues
+    pub valid: bool,
+    pub semesters: Vec<SemesterReportResponse>,
+    /// Predicted availability of each planned course that has been offered before
+    pub availability: Vec<AvailabilityResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SemesterReportResponse {
+    pub season: String,
+    pub year: u16,
+    /// Fewest units the semester's courses add up to, counting variable-unit courses as 0
+    pub min_units: f32,
+    pub overloaded: bool,
+    pub courses: Vec<CourseReportResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CourseReportResponse {
+    pub number: String,
+    /// Chance the course is offered in the planned semester, from 0 to 1, if it has been
+    /// offered before
+    pub likelihood: Option<f32>,
+    pub issues: Vec<CourseIssueResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CourseIssueResponse {
+    pub kind: CourseIssueKind,
+    /// Human-readable description of the issue
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CourseIssueKind {
+    UnknownCourse,
+    Repeated,
+    MissingPrerequisites,
+    MissingCorequisites,
+    NotOffered,
+    UnlikelyOffered,
+}
+
+impl From<&CourseIssue> for CourseIssueResponse {
+    fn from(issue: &CourseIssue) -> Self {
+        let kind = match issue {
+            CourseIssue::UnknownCourse => CourseIssueKind::UnknownCourse,
+            CourseIssue::Repeated => CourseIssueKind::Repeated,
+            CourseIssue::MissingPrerequisites(_) => CourseIssueKind::MissingPrerequisites,
+            CourseIssue::MissingCorequisites(_) => CourseIssueKind::MissingCorequisites,
+            CourseIssue::NotOffered { .. } => CourseIssueKind::NotOffered,
+            CourseIssue::UnlikelyOffered(_) => CourseIssueKind::UnlikelyOffered,
+        };
+
+        Self {
+            kind,
+            message: issue.to_string(),
+        }
+    }
+}
+
+impl From<CourseReport> for CourseReportResponse {
+    fn from(report: CourseReport) -> Self {
+        Self {
+            number: dashed(&report.number),
+            likelihood: report.likelihood,
+            issues: report
+                .issues
+                .iter()
+                .map(CourseIssueResponse::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<SemesterReport> for SemesterReportResponse {
+    fn from(report: SemesterReport) -> Self {
+        Self {
+            season: report.season.as_str().to_owned(),
+            year: *report.year,
+            min_units: report.min_units,
+            overloaded: report.overloaded,
+            courses: report.courses.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::dtos::course::dashed;
+use models::program::{AuditReport, Program, ProgramKind, RequirementResult};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProgramResponse {
+    /// Identifier shared by every version of the program (e.g., "cs-minor")
+    pub id: String,
+    pub name: String,
+    /// "major" or "minor"
+    pub kind: String,
+    /// Catalog year the requirements apply from
+    pub version: String,
+    pub requirements: Vec<RequirementResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RequirementResponse {
+    pub name: String,
+    /// The requirement in words (e.g., "2 of (15-410, 15-411, 15-440)")
+    pub description: String,
+}
+
+impl From<&Program> for ProgramResponse {
+    fn from(program: &Program) -> Self {
+        let kind = match program.kind {
+            ProgramKind::Major => "major",
+            ProgramKind::Minor => "minor",
+        };
+
+        Self {
+            id: program.id.clone(),
+            name: program.name.clone(),
+            kind: kind.to_owned(),
+            version: program.version.clone(),
+            requirements: program
+                .requirements
+                .iter()
+                .map(|requirement| RequirementResponse {
+                    name: requirement.name.clone(),
+                    description: requirement.rule.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct AuditQueryParams {
+    /// Comma-separated numbers of completed courses (e.g., `15122,21127`)
+    pub completed: Option<String>,
+    /// Version of the program to audit against, defaulting to the latest
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditResponse {
+    pub program: String,
+    pub version: String,
+    /// Whether every requirement is satisfied
+    pub satisfied: bool,
+    pub requirements: Vec<RequirementAuditResponse>,
+    /// Completed courses that didn't count towards any requirement
+    pub unused: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RequirementAuditResponse {
+    pub name: String,
+    pub satisfied: bool,
+    /// Completed courses counted towards the requirement
+    pub courses: Vec<String>,
+    /// What is still needed, empty if the requirement is satisfied
+    pub remaining: Vec<String>,
+}
+
+impl From<RequirementResult> for RequirementAuditResponse {
+    fn from(result: RequirementResult) -> Self {
+        Self {
+            name: result.name,
+            satisfied: result.satisfied,
+            courses: result.courses.iter().map(|number| dashed(number)).collect(),
+            remaining: result.remaining,
+        }
+    }
+}
+
+impl From<AuditReport> for AuditResponse {
+    fn from(report: AuditReport) -> Self {
+        Self {
+            program: report.program,
+            version: report.version,
+            satisfied: report.satisfied,
+            requirements: report.requirements.into_iter().map(Into::into).collect(),
+            unused: report.unused.iter().map(|number| dashed(number)).collect(),
+        }
+    }
+}
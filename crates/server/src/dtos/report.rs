@@ -0,0 +1,112 @@
+use crate::dtos::course::dashed;
+use models::semester_report::{
+    DepartmentChanges, ReportedCourse, ReturningCourse, SemesterComparison, TitleChange,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// How the courses of a semester changed since the same season a year earlier and since the
+/// previous semester
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SemesterChangesResponse {
+    pub season: String,
+    pub year: u16,
+    pub since_last_year: ComparisonResponse,
+    pub since_previous_semester: ComparisonResponse,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ComparisonResponse {
+    /// Season of the semester compared with
+    pub baseline_season: String,
+    /// Year of the semester compared with
+    pub baseline_year: u16,
+    /// Departments with changes, by the first two digits of their course numbers
+    pub departments: Vec<DepartmentChangesResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DepartmentChangesResponse {
+    /// First two digits of the department's course numbers (e.g., "15")
+    pub department: String,
+    /// Course numbers never offered before
+    pub new: Vec<ReportedCourseResponse>,
+    /// Courses offered before, but not in the semester compared with
+    pub returning: Vec<ReturningCourseResponse>,
+    /// Courses offered in the semester compared with, but not this one
+    pub discontinued: Vec<ReportedCourseResponse>,
+    pub title_changes: Vec<TitleChangeResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReportedCourseResponse {
+    pub number: String,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReturningCourseResponse {
+    pub number: String,
+    pub title: Option<String>,
+    pub last_offered_season: String,
+    pub last_offered_year: u16,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TitleChangeResponse {
+    pub number: String,
+    pub old_title: Option<String>,
+    pub new_title: Option<String>,
+}
+
+impl From<ReportedCourse> for ReportedCourseResponse {
+    fn from(course: ReportedCourse) -> Self {
+        Self {
+            number: dashed(&course.number),
+            title: course.title,
+        }
+    }
+}
+
+impl From<ReturningCourse> for ReturningCourseResponse {
+    fn from(course: ReturningCourse) -> Self {
+        Self {
+            number: dashed(&course.number),
+            title: course.title,
+            last_offered_season: course.last_offered.season.as_str().to_owned(),
+            last_offered_year: *course.last_offered.year,
+        }
+    }
+}
+
+impl From<TitleChange> for TitleChangeResponse {
+    fn from(change: TitleChange) -> Self {
+        Self {
+            number: dashed(&change.number),
+            old_title: change.old_title,
+            new_title: change.new_title,
+        }
+    }
+}
+
+impl From<(String, DepartmentChanges)> for DepartmentChangesResponse {
+    fn from((department, changes): (String, DepartmentChanges)) -> Self {
+        Self {
+            department,
+            new: changes.new.into_iter().map(Into::into).collect(),
+            returning: changes.returning.into_iter().map(Into::into).collect(),
+            discontinued: changes.discontinued.into_iter().map(Into::into).collect(),
+            title_changes: changes.title_changes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<SemesterComparison> for ComparisonResponse {
+    fn from(comparison: SemesterComparison) -> Self {
+        Self {
+            baseline_season: comparison.baseline.season.as_str().to_owned(),
+            baseline_year: *comparison.baseline.year,
+            departments: comparison.departments.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -1,14 +1,22 @@
 mod doc;
 mod dtos;
 mod routes;
+mod state;
 mod utils;
 
+use axum::middleware;
+use database::{archive::SyllabusStore, db::create_connection};
 use doc::ApiDoc;
-use log::info;
-use routes::{auth, course, root};
+use log::{info, warn};
+use routes::{auth, autocomplete, catalog, change, course, plan, program, report, root, syllabus};
+use state::AppState;
 use tower::ServiceBuilder;
 use tower_oauth2_resource_server::server::OAuth2ResourceServer;
-use utils::shutdown::shutdown_signal;
+use utils::{
+    freshness::{LastFinished, last_modified},
+    indexes::{SharedIndexes, refresh_indexes},
+    shutdown::shutdown_signal,
+};
 use utoipa::OpenApi;
 use utoipa_axum::{router::OpenApiRouter, routes};
 use utoipa_swagger_ui::SwaggerUi;
@@ -18,6 +26,26 @@ async fn main() {
     env_logger::init();
     dotenvy::dotenv().ok();
 
+    let db = create_connection()
+        .await
+        .expect("Failed to connect to database");
+    let state = AppState {
+        db,
+        syllabus_store: SyllabusStore::from_env(),
+        indexes: SharedIndexes::default(),
+        last_finished: LastFinished::default(),
+    };
+
+    // Without indexes the server still runs, answering the routes that need them with 503
+    // Service Unavailable until a retry builds them
+    match state.indexes.rebuild(&state.db).await {
+        Ok(()) => state
+            .last_finished
+            .set(state.indexes.get().and_then(|indexes| indexes.built_after)),
+        Err(e) => warn!("Failed to build indexes: {e}"),
+    }
+    tokio::spawn(refresh_indexes(state.clone()));
+
     let oidc_issuer_url =
         std::env::var("OIDC_ISSUER_URL").expect("OIDC_ISSUER_URL environment variable must be set");
 
@@ -35,14 +63,28 @@ async fn main() {
         .routes(routes!(root::root))
         .merge(OpenApiRouter::new().routes(routes!(course::get_courses)))
         .merge(OpenApiRouter::new().routes(routes!(course::get_course_filters)))
-        .merge(OpenApiRouter::new().routes(routes!(course::get_course_by_id)));
+        .merge(OpenApiRouter::new().routes(routes!(course::get_course_by_id)))
+        .merge(OpenApiRouter::new().routes(routes!(course::get_course_path)))
+        .merge(OpenApiRouter::new().routes(routes!(course::compare_course)))
+        .merge(OpenApiRouter::new().routes(routes!(course::get_similar_courses)))
+        .merge(OpenApiRouter::new().routes(routes!(autocomplete::autocomplete)))
+        .merge(OpenApiRouter::new().routes(routes!(catalog::get_catalog_course)))
+        .merge(OpenApiRouter::new().routes(routes!(change::get_changes)))
+        .merge(OpenApiRouter::new().routes(routes!(plan::validate)))
+        .merge(OpenApiRouter::new().routes(routes!(program::get_programs)))
+        .merge(OpenApiRouter::new().routes(routes!(program::audit_program)))
+        .merge(OpenApiRouter::new().routes(routes!(report::get_semester_report)))
+        .merge(OpenApiRouter::new().routes(routes!(syllabus::get_syllabus)))
+        .layer(middleware::from_fn_with_state(state.clone(), last_modified));
 
     let (router, _api) = OpenApiRouter::with_openapi(ApiDoc::openapi())
         .merge(protected_routes)
         .merge(public_routes)
         .split_for_parts();
 
-    let app = router.merge(SwaggerUi::new("/swagger").url("/openapi.json", ApiDoc::openapi()));
+    let app = router
+        .merge(SwaggerUi::new("/swagger").url("/openapi.json", ApiDoc::openapi()))
+        .with_state(state);
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
 
     info!("Running axum on http://localhost:3000");
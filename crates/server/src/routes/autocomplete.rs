@@ -0,0 +1,43 @@
+use crate::{
+    dtos::autocomplete::{AutocompleteQueryParams, SuggestionResponse},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use models::autocomplete::{DEFAULT_SUGGESTIONS, MAX_SUGGESTIONS};
+
+/// Complete a search to course numbers, titles, instructors, and departments
+///
+/// Served from an in-memory index rebuilt after each scrape, so it is fast enough to call on
+/// every keystroke. Course numbers can be typed with or without a dash, and misspelled titles
+/// and names are still matched.
+#[utoipa::path(
+    get,
+    path = "/autocomplete",
+    params(AutocompleteQueryParams),
+    responses(
+        (status = 200, description = "Suggestions, best first", body = Vec<SuggestionResponse>),
+        (status = 400, description = "Invalid limit"),
+        (status = 503, description = "Course index not built yet"),
+    ),
+    tag = "Search"
+)]
+pub async fn autocomplete(
+    State(state): State<AppState>,
+    Query(params): Query<AutocompleteQueryParams>,
+) -> Result<Json<Vec<SuggestionResponse>>, StatusCode> {
+    let limit = params.limit.unwrap_or(DEFAULT_SUGGESTIONS);
+    if limit == 0 || limit > MAX_SUGGESTIONS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let suggestions = state
+        .indexes
+        .ready()?
+        .autocomplete
+        .suggest(&params.q, limit);
+    Ok(Json(suggestions.into_iter().map(Into::into).collect()))
+}
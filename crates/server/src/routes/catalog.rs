@@ -0,0 +1,38 @@
+use crate::{
+    dtos::catalog::CatalogCourseResponse, routes::course::parse_course_number, state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use database::services::catalog::CatalogService;
+
+/// Get a course with every semester it has been offered
+#[utoipa::path(
+    get,
+    path = "/catalog/{number}",
+    params(
+        ("number" = String, Path, description = "Course number (e.g., 15-122 or 15122)")
+    ),
+    responses(
+        (status = 200, description = "Course found", body = CatalogCourseResponse),
+        (status = 400, description = "Invalid course number"),
+        (status = 404, description = "Course was never offered"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Catalog"
+)]
+pub async fn get_catalog_course(
+    State(state): State<AppState>,
+    Path(number): Path<String>,
+) -> Result<Json<CatalogCourseResponse>, StatusCode> {
+    let number = parse_course_number(&number)?;
+
+    let course = CatalogService::get_course(&state.db, &number)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(course.into()))
+}
@@ -0,0 +1,51 @@
+use crate::{
+    dtos::change::{ChangeQueryParams, CourseChangeResponse},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use database::services::course_change::CourseChangeService;
+
+/// Get course changes detected by the datafetcher, oldest first
+#[utoipa::path(
+    get,
+    path = "/changes",
+    params(ChangeQueryParams),
+    responses(
+        (status = 200, description = "Course changes retrieved successfully", body = Vec<CourseChangeResponse>),
+        (status = 400, description = "Invalid query parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Changes"
+)]
+pub async fn get_changes(
+    State(state): State<AppState>,
+    Query(params): Query<ChangeQueryParams>,
+) -> Result<Json<Vec<CourseChangeResponse>>, StatusCode> {
+    let changes =
+        CourseChangeService::get_changes(&state.db, params.since, params.season, params.year)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let response = changes
+        .into_iter()
+        .map(|change| CourseChangeResponse {
+            id: change.id.to_string(),
+            scrape_run_id: change.scrape_run_id.to_string(),
+            number: change.number,
+            season: change.season,
+            year: change.year,
+            change_type: change.change_type,
+            section: change.section,
+            old_value: change.old_value,
+            new_value: change.new_value,
+            detected_at: change.detected_at.with_timezone(&Utc),
+        })
+        .collect();
+
+    Ok(Json(response))
+}
@@ -1,21 +1,53 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
-use crate::dtos::course::{
-    ComponentResponse, CourseQueryParams, CourseResponse, MeetingResponse,
-    PaginatedCoursesResponse, PaginationMeta,
+use crate::{
+    dtos::{
+        catalog::{AvailabilityQueryParams, AvailabilityResponse},
+        course::{
+            CompareQueryParams, ComponentResponse, CourseComparisonResponse, CourseDetailResponse,
+            CourseQueryParams, CourseResponse, MeetingResponse, PaginatedCoursesResponse,
+            PaginationMeta, PathQueryParams, PathResponse, PrerequisitesResponse,
+            SimilarCourseResponse, SimilarQueryParams,
+        },
+    },
+    state::AppState,
 };
 use axum::{
     Json,
-    extract::{Path, Query},
+    extract::{Path, Query, State},
     http::StatusCode,
 };
 use database::{
     entities::{components, courses, instructors, meetings},
-    services::query_course::QueryCourseService,
+    services::{
+        catalog::CatalogService,
+        query_course::{CourseFilters, QueryCourseService},
+        scrape_run::ScrapeRunService,
+        syllabus::SyllabusService,
+    },
+};
+use models::{
+    availability::{DEFAULT_PREDICTED_SEMESTERS, MAX_PREDICTED_SEMESTERS, OfferingHistory},
+    course_change::diff_offering,
+    course_data::{CourseNumber, TimeBlock},
+    days::DaySet,
+    facets::Facets,
+    prerequisite_graph::PathError,
+    requisite::Expr,
+    similarity::{DEFAULT_SIMILAR_COURSES, MAX_SIMILAR_COURSES},
+    syllabus_data::Semester,
+};
+use sea_orm::{
+    EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, prelude::Uuid,
 };
-use sea_orm::{EntityTrait, QuerySelect, prelude::Uuid};
 use serde_json::json;
 
+/// Number of "did you mean" suggestions returned for a search that matched nothing
+const SEARCH_SUGGESTIONS: u64 = 5;
+
 /// Get paginated list of courses
 #[utoipa::path(
     get,
@@ -28,35 +60,343 @@ use serde_json::json;
     ),
     tag = "Courses"
 )]
-/// Get a specific course by ID
-#[utoipa::path](
+pub async fn get_courses(
+    State(state): State<AppState>,
+    Query(params): Query<CourseQueryParams>,
+) -> Result<Json<PaginatedCoursesResponse>, StatusCode> {
+    if params.page == 0 || params.per_page == 0 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let filters = CourseFilters {
+        seasons: params.season,
+        years: params.year,
+        departments: params.department,
+        search: params.search.filter(|search| !search.trim().is_empty()),
+        free_days: non_empty(&params.free_days).map(parse_days).transpose()?,
+        required_days: non_empty(&params.required_days)
+            .map(parse_days)
+            .transpose()?,
+        earliest_start: params.earliest_start,
+        latest_end: params.latest_end,
+        free_blocks: non_empty(&params.free_blocks)
+            .map(|blocks| {
+                blocks
+                    .split(',')
+                    .map(|block| TimeBlock::from_str(block).map_err(|_| StatusCode::BAD_REQUEST))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?,
+        include_tba: params.include_tba,
+    };
+    let mut query = courses::Entity::find().filter(filters.condition());
+    if let Some(search) = &filters.search {
+        // Best matches first, instead of by course number
+        query = query.order_by(QueryCourseService::search_rank(search), Order::Desc);
+    }
+
+    let paginator = query
+        .order_by_asc(courses::Column::Number)
+        .order_by_desc(courses::Column::Year)
+        .paginate(&state.db, params.per_page);
+
+    let totals = paginator
+        .num_items_and_pages()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Nothing to narrow down when nothing matched
+    let facets = if totals.number_of_items == 0 {
+        Facets::default()
+    } else {
+        QueryCourseService::get_facets(&state.db, &filters)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    };
+
+    let did_you_mean = match &filters.search {
+        Some(search) if totals.number_of_items == 0 => {
+            QueryCourseService::get_search_suggestions(&state.db, search, SEARCH_SUGGESTIONS)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        }
+        _ => Vec::new(),
+    };
+
+    let page_courses = paginator
+        .fetch_page(params.page - 1)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let course_ids: Vec<Uuid> = page_courses.iter().map(|course| course.id).collect();
+    let mut course_data =
+        QueryCourseService::get_courses_with_components(&state.db, course_ids.clone())
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Keep the paginator's ordering
+    course_data.sort_by_key(|(course, _)| course_ids.iter().position(|id| *id == course.id));
+
+    let syllabus_hashes = SyllabusService::get_hashes(&state.db, &page_courses)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let courses = course_data
+        .into_iter()
+        .map(|(course, components)| {
+            convert_to_course_response(course, components, &syllabus_hashes)
+        })
+        .collect();
+
+    Ok(Json(PaginatedCoursesResponse {
+        courses,
+        facets: facets.into(),
+        did_you_mean,
+        pagination: PaginationMeta {
+            page: params.page,
+            per_page: params.per_page,
+            total_pages: totals.number_of_pages,
+            total_items: totals.number_of_items,
+            has_next: params.page < totals.number_of_pages,
+            has_prev: params.page > 1,
+        },
+    }))
+}
+
+/// Get a specific course by ID, with the predicted availability of its course number
+#[utoipa::path(
     get,
     path = "/courses/{id}",
     params(
-        ("id" = Uuid, Path, description = "Course ID")
+        ("id" = Uuid, Path, description = "Course ID"),
+        AvailabilityQueryParams
     ),
     responses(
-        (status = 200, description = "Course found", body = CourseResponse),
+        (status = 200, description = "Course found", body = CourseDetailResponse),
+        (status = 400, description = "Too many semesters to predict"),
         (status = 404, description = "Course not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Courses"
 )]
-pub async fn get_course_by_id(Path(id): Path<Uuid>) -> Result<Json<CourseResponse>, StatusCode> {
+pub async fn get_course_by_id(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<AvailabilityQueryParams>,
+) -> Result<Json<CourseDetailResponse>, StatusCode> {
+    let semesters = params.semesters.unwrap_or(DEFAULT_PREDICTED_SEMESTERS);
+    if semesters > MAX_PREDICTED_SEMESTERS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
     // Get course by ID
-    let course_data = QueryCourseService::get_course_by_id(&db, id)
+    let course_data = QueryCourseService::get_course_by_id(&state.db, id)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     match course_data {
         Some((course, components)) => {
-            let response = convert_to_course_response(course, components);
-            Ok(Json(response))
+            let syllabus_hashes =
+                SyllabusService::get_hashes(&state.db, std::slice::from_ref(&course))
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let number = course.number.clone();
+            let histories =
+                CatalogService::get_offering_histories(&state.db, std::slice::from_ref(&number))
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let history = histories
+                .get(&number)
+                .cloned()
+                .unwrap_or_else(|| OfferingHistory::new(Vec::new(), Vec::new()));
+
+            let prerequisites = parse_prerequisites(course.prerequisites.as_deref());
+            let mut course = convert_to_course_response(course, components, &syllabus_hashes);
+            course.prerequisites = prerequisites
+                .map(|expr| PrerequisitesResponse::from(expr.clone()).with_options(&expr));
+
+            Ok(Json(CourseDetailResponse {
+                course,
+                availability: AvailabilityResponse::new(&number, &history, semesters),
+            }))
         }
         None => Err(StatusCode::NOT_FOUND),
     }
 }
 
+/// Get the shortest path of courses to become eligible for a course
+#[utoipa::path(
+    get,
+    path = "/courses/{number}/path",
+    params(
+        ("number" = String, Path, description = "Course number (e.g., 15-122 or 15122)"),
+        PathQueryParams
+    ),
+    responses(
+        (status = 200, description = "Path found", body = PathResponse),
+        (status = 400, description = "Invalid course number"),
+        (status = 404, description = "Course not found"),
+        (status = 422, description = "Every path to the course is a prerequisite cycle"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "Course index not built yet")
+    ),
+    tag = "Courses"
+)]
+pub async fn get_course_path(
+    State(state): State<AppState>,
+    Path(number): Path<String>,
+    Query(params): Query<PathQueryParams>,
+) -> Result<Json<PathResponse>, StatusCode> {
+    let target = parse_course_number(&number)?;
+    let completed = params
+        .completed
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter(|number| !number.trim().is_empty())
+        .map(parse_course_number)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match state
+        .indexes
+        .ready()?
+        .prerequisites
+        .path(&target, &completed)
+    {
+        Ok(path) => Ok(Json(path.into())),
+        Err(PathError::UnknownCourse(_)) => Err(StatusCode::NOT_FOUND),
+        Err(PathError::Cycle(_)) => Err(StatusCode::UNPROCESSABLE_ENTITY),
+    }
+}
+
+/// Compare the offerings of a course in two semesters
+#[utoipa::path(
+    get,
+    path = "/courses/{number}/compare",
+    params(
+        ("number" = String, Path, description = "Course number (e.g., 15-122 or 15122)"),
+        CompareQueryParams
+    ),
+    responses(
+        (status = 200, description = "Offerings compared successfully", body = CourseComparisonResponse),
+        (status = 400, description = "Invalid course number or semester"),
+        (status = 404, description = "Course not offered in one of the semesters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Courses"
+)]
+pub async fn compare_course(
+    State(state): State<AppState>,
+    Path(number): Path<String>,
+    Query(params): Query<CompareQueryParams>,
+) -> Result<Json<CourseComparisonResponse>, StatusCode> {
+    let number = parse_course_number(&number)?;
+    let (a, b) = (parse_semester(&params.a)?, parse_semester(&params.b)?);
+
+    let find_offering = |semester: Semester| {
+        QueryCourseService::get_course_offering(&state.db, &number, semester.season, semester.year)
+    };
+    let (old_course, old_components) = find_offering(a)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let (new_course, new_components) = find_offering(b)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let to_course_object = |course: &courses::Model, components: &[ComponentModel]| {
+        QueryCourseService::to_course_object(course.clone(), components.to_vec())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    };
+    let changes = diff_offering(
+        &to_course_object(&old_course, &old_components)?,
+        &to_course_object(&new_course, &new_components)?,
+    );
+
+    let syllabus_hashes =
+        SyllabusService::get_hashes(&state.db, &[old_course.clone(), new_course.clone()])
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(CourseComparisonResponse {
+        a: convert_to_course_response(old_course, old_components, &syllabus_hashes),
+        b: convert_to_course_response(new_course, new_components, &syllabus_hashes),
+        changes: changes.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Get the courses whose description and titles are most similar to a course's, e.g. to find
+/// alternatives to a full course
+#[utoipa::path(
+    get,
+    path = "/courses/{number}/similar",
+    params(
+        ("number" = String, Path, description = "Course number (e.g., 15-122 or 15122)"),
+        SimilarQueryParams
+    ),
+    responses(
+        (status = 200, description = "Similar courses, most similar first", body = Vec<SimilarCourseResponse>),
+        (status = 400, description = "Invalid course number, semester, or limit"),
+        (status = 404, description = "Course not found"),
+        (status = 503, description = "Course index not built yet"),
+    ),
+    tag = "Courses"
+)]
+pub async fn get_similar_courses(
+    State(state): State<AppState>,
+    Path(number): Path<String>,
+    Query(params): Query<SimilarQueryParams>,
+) -> Result<Json<Vec<SimilarCourseResponse>>, StatusCode> {
+    let number = parse_course_number(&number)?;
+    let semester = params.semester.as_deref().map(parse_semester).transpose()?;
+    let limit = params.limit.unwrap_or(DEFAULT_SIMILAR_COURSES);
+    if limit == 0 || limit > MAX_SIMILAR_COURSES {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let similar = state
+        .indexes
+        .ready()?
+        .similarity
+        .similar(&number, semester, limit)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(similar.into_iter().map(Into::into).collect()))
+}
+
+/// Parses a course number given with or without a dash (e.g., "15-122" or "15122")
+///
+/// # Returns
+/// The number without a dash, or `400 Bad Request` if it isn't a course number
+pub fn parse_course_number(number: &str) -> Result<String, StatusCode> {
+    CourseNumber::from_str(&number.trim().replace('-', ""))
+        .map(|number| number.to_string())
+        .map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+/// Parses a semester from a request, written as a season code and year (e.g., "F25")
+///
+/// # Returns
+/// The semester, or `400 Bad Request` if it isn't one
+pub fn parse_semester(semester: &str) -> Result<Semester, StatusCode> {
+    Semester::from_str(semester.trim()).map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+/// A query parameter, treating an empty value like a missing one
+fn non_empty(param: &Option<String>) -> Option<&str> {
+    param.as_deref().filter(|param| !param.trim().is_empty())
+}
+
+/// Parses days of the week from a request, written with day letters (e.g., "MWF")
+///
+/// # Returns
+/// The days, or `400 Bad Request` if a character isn't a day letter
+fn parse_days(days: &str) -> Result<DaySet, StatusCode> {
+    DaySet::parse_strict(days.trim()).ok_or(StatusCode::BAD_REQUEST)
+}
+
 /// Get available seasons and years for filtering
 #[utoipa::path(
     get,
@@ -67,7 +407,9 @@ pub async fn get_course_by_id(Path(id): Path<Uuid>) -> Result<Json<CourseRespons
     ),
     tag = "Courses"
 )]
-pub async fn get_course_filters() -> Result<Json<serde_json::Value>, StatusCode> {
+pub async fn get_course_filters(
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
     // Get distinct seasons and years
     let seasons_and_years = courses::Entity::find()
         .select_only()
@@ -75,12 +417,24 @@ pub async fn get_course_filters() -> Result<Json<serde_json::Value>, StatusCode>
         .column(courses::Column::Year)
         .distinct()
         .into_tuple::<(String, i16)>()
+        .all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let last_updated = ScrapeRunService::get_last_updated(&state.db)
+        .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let mut seasons = HashSet::new();
     let mut years = HashSet::new();
+    let mut semesters = Vec::new();
 
     for (season, year) in seasons_and_years {
+        semesters.push(json!({
+            "season": season,
+            "year": year,
+            "last_updated": last_updated.get(&(season.clone(), year)),
+        }));
         seasons.insert(season);
         years.insert(year);
     }
@@ -93,16 +447,23 @@ pub async fn get_course_filters() -> Result<Json<serde_json::Value>, StatusCode>
     Ok(Json(json!({
         "seasons": seasons_vec,
         "years": years_vec,
+        "semesters": semesters,
     })))
 }
 
 type MeetingModel = (meetings::Model, Vec<instructors::Model>);
 type ComponentModel = (components::Model, Vec<MeetingModel>);
 
+/// Parses the prerequisites stored for a course, if it has any
+fn parse_prerequisites(json: Option<&str>) -> Option<Expr> {
+    json.and_then(|json| serde_json::from_str(json).ok())
+}
+
 /// Helper function to convert database models to API response
 fn convert_to_course_response(
     course: courses::Model,
     components: Vec<ComponentModel>,
+    syllabus_hashes: &HashMap<(Uuid, String), String>,
 ) -> CourseResponse {
     let related_urls: Vec<String> = course
         .related_urls
@@ -131,17 +492,24 @@ fn convert_to_course_response(
                         days_pattern: meeting.days_pattern,
                         time_begin: meeting.time_begin,
                         time_end: meeting.time_end,
+                        bldg_room: meeting.bldg_room,
                         campus: meeting.campus,
+                        instructors: instructor_names,
                     }
                 })
                 .collect();
 
+            let syllabus_archive_url = syllabus_hashes
+                .get(&(course.id, component.code.clone()))
+                .map(|hash| format!("/syllabi/{hash}"));
+
             ComponentResponse {
                 id: component.id.to_string(),
                 title: component.title,
                 component_type: component.component_type,
                 code: component.code,
                 syllabus_url: component.syllabus_url,
+                syllabus_archive_url,
                 meetings: meeting_responses,
             }
         })
@@ -155,7 +523,8 @@ fn convert_to_course_response(
         year: course.year,
         special_permission: course.special_permission,
         description: course.description,
-        prerequisites: course.prerequisites,
+        prerequisites: parse_prerequisites(course.prerequisites.as_deref())
+            .map(PrerequisitesResponse::from),
         notes: course.notes,
         related_urls,
         components: component_responses,
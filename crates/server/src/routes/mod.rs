@@ -1,3 +1,10 @@
 pub mod auth;
+pub mod autocomplete;
+pub mod catalog;
+pub mod change;
 pub mod course;
+pub mod plan;
+pub mod program;
+pub mod report;
 pub mod root;
+pub mod syllabus;
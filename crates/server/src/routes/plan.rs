@@ -0,0 +1,101 @@
+use crate::{
+    dtos::{
+        catalog::AvailabilityResponse,
+        plan::{PlanRequest, PlanResponse},
+    },
+    routes::course::parse_course_number,
+    state::AppState,
+};
+use axum::{Json, extract::State, http::StatusCode};
+use database::services::query_course::QueryCourseService;
+use models::{
+    availability::{DEFAULT_PREDICTED_SEMESTERS, MAX_PREDICTED_SEMESTERS},
+    plan::{DEFAULT_MAX_UNITS, PlannedSemester, SemesterReport, validate_plan},
+    syllabus_data::{Season, Year},
+};
+use std::str::FromStr;
+
+/// Validate a multi-semester plan
+///
+/// Checks that each course's prerequisites are completed in earlier semesters, its
+/// corequisites are taken in the same or an earlier semester, it has been offered in the
+/// planned season before and is likely to be offered that year, and that no semester is an
+/// overload. Also predicts the availability of each course in the coming semesters.
+#[utoipa::path(
+    post,
+    path = "/plans/validate",
+    request_body = PlanRequest,
+    responses(
+        (status = 200, description = "Plan validated", body = PlanResponse),
+        (status = 400, description = "Invalid season, course number, or semester count"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Plans"
+)]
+pub async fn validate(
+    State(state): State<AppState>,
+    Json(request): Json<PlanRequest>,
+) -> Result<Json<PlanResponse>, StatusCode> {
+    let predicted_semesters = request
+        .predicted_semesters
+        .unwrap_or(DEFAULT_PREDICTED_SEMESTERS);
+    if predicted_semesters > MAX_PREDICTED_SEMESTERS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let semesters = request
+        .semesters
+        .into_iter()
+        .map(|semester| {
+            Ok(PlannedSemester {
+                season: Season::from_str(&semester.season).map_err(|_| StatusCode::BAD_REQUEST)?,
+                year: Year(semester.year),
+                courses: semester
+                    .courses
+                    .iter()
+                    .map(|number| parse_course_number(number))
+                    .collect::<Result<_, _>>()?,
+            })
+        })
+        .collect::<Result<Vec<_>, StatusCode>>()?;
+    let completed = request
+        .completed
+        .iter()
+        .map(|number| parse_course_number(number))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut numbers: Vec<String> = semesters
+        .iter()
+        .flat_map(|semester| semester.courses.iter().cloned())
+        .collect();
+    numbers.sort();
+    numbers.dedup();
+    let history = QueryCourseService::get_course_histories(&state.db, &numbers)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let reports = validate_plan(
+        &semesters,
+        &history,
+        &completed,
+        request.max_units.unwrap_or(DEFAULT_MAX_UNITS),
+    );
+
+    let availability = numbers
+        .iter()
+        .filter_map(|number| {
+            let course = history.get(number)?;
+            Some(AvailabilityResponse::new(
+                number,
+                &course.offerings,
+                predicted_semesters,
+            ))
+        })
+        .collect();
+
+    Ok(Json(PlanResponse {
+        valid: reports.iter().all(SemesterReport::is_valid),
+        semesters: reports.into_iter().map(Into::into).collect(),
+        availability,
+    }))
+}
@@ -0,0 +1,67 @@
+use crate::{
+    dtos::program::{AuditQueryParams, AuditResponse, ProgramResponse},
+    routes::course::parse_course_number,
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use database::services::query_course::QueryCourseService;
+use models::program::Program;
+
+/// Get every version of every degree program
+#[utoipa::path(
+    get,
+    path = "/programs",
+    responses(
+        (status = 200, description = "Programs retrieved successfully", body = Vec<ProgramResponse>)
+    ),
+    tag = "Programs"
+)]
+pub async fn get_programs() -> Json<Vec<ProgramResponse>> {
+    Json(Program::all().iter().map(ProgramResponse::from).collect())
+}
+
+/// Audit completed courses against a degree program
+#[utoipa::path(
+    get,
+    path = "/programs/{id}/audit",
+    params(
+        ("id" = String, Path, description = "Program ID (e.g., cs-minor)"),
+        AuditQueryParams
+    ),
+    responses(
+        (status = 200, description = "Audit completed", body = AuditResponse),
+        (status = 400, description = "Invalid course number"),
+        (status = 404, description = "Program or version not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Programs"
+)]
+pub async fn audit_program(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<AuditQueryParams>,
+) -> Result<Json<AuditResponse>, StatusCode> {
+    let program = Program::find(&id, params.version.as_deref()).ok_or(StatusCode::NOT_FOUND)?;
+
+    let completed = params
+        .completed
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter(|number| !number.trim().is_empty())
+        .map(parse_course_number)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let units = QueryCourseService::get_course_histories(&state.db, &completed)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|(number, history)| (number, history.units.min_value()))
+        .collect();
+
+    Ok(Json(program.audit(&completed, &units).into()))
+}
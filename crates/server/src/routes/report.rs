@@ -0,0 +1,53 @@
+use crate::{
+    dtos::report::SemesterChangesResponse, routes::course::parse_semester, state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use database::services::catalog::CatalogService;
+use models::semester_report::compare_semesters;
+
+/// Report the new, returning, and discontinued courses of a semester
+///
+/// Compares the semester with the same season a year earlier and with the previous semester,
+/// listing courses never offered before, courses offered again after a gap, courses no longer
+/// offered, and title changes, grouped by department.
+#[utoipa::path(
+    get,
+    path = "/semesters/{semester}/report",
+    params(
+        ("semester" = String, Path, description = "Season code and year (e.g., F25)")
+    ),
+    responses(
+        (status = 200, description = "Report generated", body = SemesterChangesResponse),
+        (status = 400, description = "Invalid semester"),
+        (status = 404, description = "Nothing was offered in the semester"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Reports"
+)]
+pub async fn get_semester_report(
+    State(state): State<AppState>,
+    Path(semester): Path<String>,
+) -> Result<Json<SemesterChangesResponse>, StatusCode> {
+    let semester = parse_semester(&semester)?;
+    let last_year = semester.year_before();
+    let previous = semester.previous();
+
+    let offerings =
+        CatalogService::get_report_offerings(&state.db, semester, &[last_year, previous])
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if offerings.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(SemesterChangesResponse {
+        season: semester.season.as_str().to_owned(),
+        year: *semester.year,
+        since_last_year: compare_semesters(&offerings, semester, last_year).into(),
+        since_previous_semester: compare_semesters(&offerings, semester, previous).into(),
+    }))
+}
@@ -0,0 +1,62 @@
+use crate::state::AppState;
+use axum::{
+    extract::{Path, State},
+    http::{
+        StatusCode,
+        header::{CACHE_CONTROL, CONTENT_SECURITY_POLICY, CONTENT_TYPE, ETAG},
+    },
+    response::IntoResponse,
+};
+use database::services::syllabus::SyllabusService;
+use log::error;
+
+/// Get the archived copy of a syllabus
+///
+/// Canvas download links expire and require a Canvas login, so the API serves the copy
+/// downloaded by the datafetcher instead.
+#[utoipa::path(
+    get,
+    path = "/syllabi/{hash}",
+    params(
+        ("hash" = String, Path, description = "SHA-256 hash of the syllabus file")
+    ),
+    responses(
+        (status = 200, description = "Syllabus file found"),
+        (status = 404, description = "Syllabus not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Syllabi"
+)]
+pub async fn get_syllabus(
+    State(state): State<AppState>,
+    Path(hash): Path<String>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let file = SyllabusService::get_file(&state.db, &hash)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let store = state.syllabus_store.clone();
+    let bytes = tokio::task::spawn_blocking(move || store.get(&hash))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|e| {
+            error!("Failed to read archived syllabus {}: {e}", file.hash);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let headers = [
+        (CONTENT_TYPE, file.content_type),
+        // The contents of a hash never change
+        (
+            CACHE_CONTROL,
+            "public, max-age=31536000, immutable".to_owned(),
+        ),
+        (ETAG, format!("\"{}\"", file.hash)),
+        // Archived HTML syllabi must not run scripts on our origin
+        (CONTENT_SECURITY_POLICY, "sandbox".to_owned()),
+    ];
+
+    Ok((headers, bytes))
+}
@@ -0,0 +1,14 @@
+use crate::utils::{freshness::LastFinished, indexes::SharedIndexes};
+use database::archive::SyllabusStore;
+use sea_orm::DatabaseConnection;
+
+/// Shared state available to every route
+#[derive(Clone)]
+pub struct AppState {
+    pub db: DatabaseConnection,
+    pub syllabus_store: SyllabusStore,
+    /// Indexes over the stored courses, rebuilt after each scrape
+    pub indexes: SharedIndexes,
+    /// Finish time of the latest scrape run, refreshed with the indexes
+    pub last_finished: LastFinished,
+}
@@ -0,0 +1,43 @@
+use crate::state::AppState;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, header::LAST_MODIFIED},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, RwLock};
+
+/// Finish time of the latest scrape run, kept in memory so responses can be dated without a
+/// database query, and refreshed by [`refresh_indexes`](super::indexes::refresh_indexes)
+#[derive(Debug, Clone, Default)]
+pub struct LastFinished(Arc<RwLock<Option<DateTime<Utc>>>>);
+
+impl LastFinished {
+    pub fn get(&self) -> Option<DateTime<Utc>> {
+        *self.0.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set(&self, finished_at: Option<DateTime<Utc>>) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = finished_at;
+    }
+}
+
+/// Sets the `Last-Modified` header to the finish time of the latest scrape run, so clients can
+/// tell how fresh the course data is
+pub async fn last_modified(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+
+    if let Some(finished_at) = state.last_finished.get() {
+        let http_date = finished_at.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        if let Ok(value) = HeaderValue::from_str(&http_date) {
+            response.headers_mut().insert(LAST_MODIFIED, value);
+        }
+    }
+
+    response
+}
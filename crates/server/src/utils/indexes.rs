@@ -0,0 +1,118 @@
+use crate::state::AppState;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use database::services::{query_course::QueryCourseService, scrape_run::ScrapeRunService};
+use log::{info, warn};
+use models::{
+    autocomplete::AutocompleteIndex, prerequisite_graph::PrerequisiteGraph,
+    similarity::SimilarityIndex,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+/// How often to check whether a scrape run finished since the indexes were built
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long to wait before building the indexes again when they couldn't be built at all
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// In-memory indexes over the stored courses
+#[derive(Debug, Default)]
+pub struct Indexes {
+    pub similarity: SimilarityIndex,
+    pub autocomplete: AutocompleteIndex,
+    /// Prerequisites of the latest offering of each course
+    pub prerequisites: PrerequisiteGraph,
+    /// Finish time of the latest scrape run when the indexes were built
+    pub built_after: Option<DateTime<Utc>>,
+}
+
+impl Indexes {
+    /// Builds every index from the stored courses
+    pub async fn build(db: &DatabaseConnection) -> Result<Self, DbErr> {
+        // Read before the courses, so a scrape finishing mid-build triggers another build
+        let built_after = ScrapeRunService::get_last_finished(db).await?;
+        let documents = QueryCourseService::get_course_documents(db).await?;
+        let departments = QueryCourseService::get_department_names(db).await?;
+        let instructors = QueryCourseService::get_instructor_names(db).await?;
+        let prerequisites = QueryCourseService::get_latest_prerequisites(db).await?;
+
+        // Indexing takes long enough to hold up other requests on the runtime's workers
+        tokio::task::spawn_blocking(move || Self {
+            autocomplete: AutocompleteIndex::new(&documents, &departments, &instructors),
+            similarity: SimilarityIndex::new(documents),
+            prerequisites: PrerequisiteGraph::new(prerequisites),
+            built_after,
+        })
+        .await
+        .map_err(|e| DbErr::Custom(format!("Failed to build indexes: {e}")))
+    }
+}
+
+/// Indexes shared between requests, swapped out whole when they are rebuilt, and empty until
+/// they are first built
+#[derive(Debug, Clone, Default)]
+pub struct SharedIndexes(Arc<RwLock<Option<Arc<Indexes>>>>);
+
+impl SharedIndexes {
+    /// The current indexes, which stay valid for the caller even if they are rebuilt
+    pub fn get(&self) -> Option<Arc<Indexes>> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// The current indexes, or 503 Service Unavailable if they haven't been built yet, as
+    /// empty indexes would answer every request as if no courses were stored
+    pub fn ready(&self) -> Result<Arc<Indexes>, StatusCode> {
+        self.get().ok_or(StatusCode::SERVICE_UNAVAILABLE)
+    }
+
+    fn set(&self, indexes: Indexes) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(indexes));
+    }
+
+    /// Rebuilds the indexes from the stored courses
+    pub async fn rebuild(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        let indexes = Indexes::build(db).await?;
+        info!(
+            "Built indexes over {} courses and {} suggestions",
+            indexes.similarity.len(),
+            indexes.autocomplete.len()
+        );
+        self.set(indexes);
+        Ok(())
+    }
+}
+
+/// Rebuilds the indexes whenever a scrape run finishes, until the server shuts down, keeping
+/// [`AppState::last_finished`] up to date
+///
+/// Until the indexes are first built, the build is retried every [`RETRY_INTERVAL`] instead.
+pub async fn refresh_indexes(state: AppState) {
+    loop {
+        let built_after = state.indexes.get().map(|indexes| indexes.built_after);
+        tokio::time::sleep(match built_after {
+            Some(_) => REFRESH_INTERVAL,
+            None => RETRY_INTERVAL,
+        })
+        .await;
+
+        let last_finished = match ScrapeRunService::get_last_finished(&state.db).await {
+            Ok(last_finished) => last_finished,
+            Err(e) => {
+                warn!("Failed to look up last scrape run: {e}");
+                continue;
+            }
+        };
+        state.last_finished.set(last_finished);
+        if built_after == Some(last_finished) {
+            continue;
+        }
+
+        if let Err(e) = state.indexes.rebuild(&state.db).await {
+            warn!("Failed to rebuild indexes: {e}");
+        }
+    }
+}
@@ -1 +1,3 @@
+pub mod freshness;
+pub mod indexes;
 pub mod shutdown;